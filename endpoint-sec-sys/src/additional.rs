@@ -7,6 +7,8 @@ use core::fmt;
 use std::os::raw::c_int;
 pub use std::os::raw::{c_uint, c_ushort};
 
+#[cfg(feature = "macos_10_15_1")]
+use libc::{c_void, ssize_t};
 use libc::{dev_t, gid_t, pid_t, uid_t};
 pub use mach2::vm_types::user_addr_t;
 
@@ -34,6 +36,45 @@ pub struct _acl {
     _unused: [u8; 0],
 }
 
+/// Pointer to an opaque ACL entry, see `acl_get_entry(3)`.
+#[cfg(feature = "macos_10_15_1")]
+pub type acl_entry_t = *mut _acl_entry;
+
+/// Never use directly, use [`acl_entry_t`] instead
+#[repr(C)]
+#[cfg(feature = "macos_10_15_1")]
+pub struct _acl_entry {
+    _unused: [u8; 0],
+}
+
+/// Pointer to the opaque permission set of an ACL entry, see `acl_get_permset(3)`.
+#[cfg(feature = "macos_10_15_1")]
+pub type acl_permset_t = *mut _acl_permset;
+
+/// Never use directly, use [`acl_permset_t`] instead
+#[repr(C)]
+#[cfg(feature = "macos_10_15_1")]
+pub struct _acl_permset {
+    _unused: [u8; 0],
+}
+
+/// A single permission of an ACL entry, see the `ACL_*` constants.
+pub type acl_perm_t = c_uint;
+
+#[cfg(feature = "macos_10_15_1")]
+ffi_wrap_enum!(
+    /// Tag type of an ACL entry, see `acl_get_tag_type(3)`.
+    acl_tag_t(u32);
+
+    == MACOS_10_15_0;
+    ACL_UNDEFINED_TAG = 0,
+    /// The entry allows the permissions to the qualifier
+    ACL_EXTENDED_ALLOW = 1,
+    --
+    /// The entry denies the permissions to the qualifier
+    ACL_EXTENDED_DENY = 2,
+);
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct au_tid_t {
@@ -203,3 +244,182 @@ extern "C" {
     /// - Returns: The process ID version extracted from the Mach audit token.
     pub fn audit_token_to_pidversion(atoken: audit_token_t) -> c_int;
 }
+
+// File flags from `<sys/stat.h>` that are not exposed by the `libc` crate.
+
+/// File is a data vault, access is restricted to entitled processes.
+pub const UF_DATAVAULT: c_uint = 0x00000080;
+/// File is restricted by System Integrity Protection.
+pub const SF_RESTRICTED: c_uint = 0x00080000;
+/// File may not be removed or renamed.
+pub const SF_NOUNLINK: c_uint = 0x00100000;
+/// File is a firmlink.
+pub const SF_FIRMLINK: c_uint = 0x00800000;
+/// File is dataless: its content must be materialized before use.
+pub const SF_DATALESS: c_uint = 0x40000000;
+
+// Kernel open flags from `<sys/fcntl.h>`, as found in `es_event_open_t.fflag`. The other kernel
+// flags have the same values as their `O_*` counterparts from the `libc` crate.
+
+/// Open for reading, the kernel counterpart of `O_RDONLY`.
+pub const FREAD: c_int = 0x00000001;
+/// Open for writing, the kernel counterpart of `O_WRONLY`.
+pub const FWRITE: c_int = 0x00000002;
+
+// Code signing flags from `<kern/cs_blobs.h>`, as found in `es_process_t.codesigning_flags`.
+
+/// Dynamically valid.
+pub const CS_VALID: u32 = 0x00000001;
+/// Ad hoc signed.
+pub const CS_ADHOC: u32 = 0x00000002;
+/// Has `get-task-allow` entitlement.
+pub const CS_GET_TASK_ALLOW: u32 = 0x00000004;
+/// Has installer entitlement.
+pub const CS_INSTALLER: u32 = 0x00000008;
+/// Library Validation required by Hardened System Policy.
+pub const CS_FORCED_LV: u32 = 0x00000010;
+/// (macOS Only) Page invalidation allowed by task port policy.
+pub const CS_INVALID_ALLOWED: u32 = 0x00000020;
+/// Don't load invalid pages.
+pub const CS_HARD: u32 = 0x00000100;
+/// Kill process if it becomes invalid.
+pub const CS_KILL: u32 = 0x00000200;
+/// Force expiration checking.
+pub const CS_CHECK_EXPIRATION: u32 = 0x00000400;
+/// Tell dyld to treat restricted.
+pub const CS_RESTRICT: u32 = 0x00000800;
+/// Require enforcement.
+pub const CS_ENFORCEMENT: u32 = 0x00001000;
+/// Require library validation.
+pub const CS_REQUIRE_LV: u32 = 0x00002000;
+/// Code signature permits restricted entitlements.
+pub const CS_ENTITLEMENTS_VALIDATED: u32 = 0x00004000;
+/// Has `com.apple.rootless.restricted-nvram-variables.heritable` entitlement.
+pub const CS_NVRAM_UNRESTRICTED: u32 = 0x00008000;
+/// Apply hardened runtime policies.
+pub const CS_RUNTIME: u32 = 0x00010000;
+/// Automatically signed by the linker.
+pub const CS_LINKER_SIGNED: u32 = 0x00020000;
+/// Set `CS_HARD` on any exec'ed process.
+pub const CS_EXEC_SET_HARD: u32 = 0x00100000;
+/// Set `CS_KILL` on any exec'ed process.
+pub const CS_EXEC_SET_KILL: u32 = 0x00200000;
+/// Set `CS_ENFORCEMENT` on any exec'ed process.
+pub const CS_EXEC_SET_ENFORCEMENT: u32 = 0x00400000;
+/// Set `CS_INSTALLER` on any exec'ed process (only for the SIP-protected installer).
+pub const CS_EXEC_INHERIT_SIP: u32 = 0x00800000;
+/// Was killed by kernel for invalidity.
+pub const CS_KILLED: u32 = 0x01000000;
+/// dyld used to load this is a platform binary.
+pub const CS_NO_UNTRUSTED_HELPERS: u32 = 0x02000000;
+/// This is a platform binary.
+pub const CS_PLATFORM_BINARY: u32 = 0x04000000;
+/// Platform binary by the fact of path (macOS only).
+pub const CS_PLATFORM_PATH: u32 = 0x08000000;
+/// Process is currently or has previously been debugged and allowed to run with invalid pages.
+pub const CS_DEBUGGED: u32 = 0x10000000;
+/// Process has a signature (may have gone invalid).
+pub const CS_SIGNED: u32 = 0x20000000;
+/// Code is dev signed, cannot be loaded into prod signed code.
+pub const CS_DEV_CODE: u32 = 0x40000000;
+/// Has Data Vault controller entitlement.
+pub const CS_DATAVAULT_CONTROLLER: u32 = 0x80000000;
+
+// ACL entries and permissions from `<sys/acl.h>`.
+
+/// Entry id of the first entry for `acl_get_entry()`.
+pub const ACL_FIRST_ENTRY: c_int = 0;
+/// Entry id of the entry following the last one returned by `acl_get_entry()`.
+pub const ACL_NEXT_ENTRY: c_int = -1;
+/// Entry id of the last entry for `acl_get_entry()`.
+pub const ACL_LAST_ENTRY: c_int = -2;
+
+/// Read the data of a file.
+pub const ACL_READ_DATA: acl_perm_t = 1 << 1;
+/// List the entries of a directory, same value as [`ACL_READ_DATA`].
+pub const ACL_LIST_DIRECTORY: acl_perm_t = 1 << 1;
+/// Write the data of a file.
+pub const ACL_WRITE_DATA: acl_perm_t = 1 << 2;
+/// Add a file to a directory, same value as [`ACL_WRITE_DATA`].
+pub const ACL_ADD_FILE: acl_perm_t = 1 << 2;
+/// Execute a file.
+pub const ACL_EXECUTE: acl_perm_t = 1 << 3;
+/// Search a directory, same value as [`ACL_EXECUTE`].
+pub const ACL_SEARCH: acl_perm_t = 1 << 3;
+/// Delete the object.
+pub const ACL_DELETE: acl_perm_t = 1 << 4;
+/// Append data to a file.
+pub const ACL_APPEND_DATA: acl_perm_t = 1 << 5;
+/// Add a subdirectory to a directory, same value as [`ACL_APPEND_DATA`].
+pub const ACL_ADD_SUBDIRECTORY: acl_perm_t = 1 << 5;
+/// Delete an entry of a directory.
+pub const ACL_DELETE_CHILD: acl_perm_t = 1 << 6;
+/// Read the attributes of the object.
+pub const ACL_READ_ATTRIBUTES: acl_perm_t = 1 << 7;
+/// Write the attributes of the object.
+pub const ACL_WRITE_ATTRIBUTES: acl_perm_t = 1 << 8;
+/// Read the extended attributes of the object.
+pub const ACL_READ_EXTATTRIBUTES: acl_perm_t = 1 << 9;
+/// Write the extended attributes of the object.
+pub const ACL_WRITE_EXTATTRIBUTES: acl_perm_t = 1 << 10;
+/// Read the ACL of the object.
+pub const ACL_READ_SECURITY: acl_perm_t = 1 << 11;
+/// Write the ACL of the object.
+pub const ACL_WRITE_SECURITY: acl_perm_t = 1 << 12;
+/// Change the owner of the object.
+pub const ACL_CHANGE_OWNER: acl_perm_t = 1 << 13;
+/// Synchronize, unused.
+pub const ACL_SYNCHRONIZE: acl_perm_t = 1 << 20;
+
+// Functions from `<sys/acl.h>`, part of the system library.
+//
+// **Never** call them on an `acl_t` received from Endpoint Security except `acl_size()` and
+// `acl_copy_ext()`, see the warning on `acl_t`.
+#[cfg(feature = "macos_10_15_1")]
+extern "C" {
+    /// Allocate a new empty ACL with room for `count` entries, to free with [`acl_free()`].
+    pub fn acl_init(count: c_int) -> acl_t;
+
+    /// Free memory allocated by the ACL functions ([`acl_t`], qualifiers, ...).
+    pub fn acl_free(obj_p: *mut c_void) -> c_int;
+
+    /// Size in bytes of the external representation of `acl`, for [`acl_copy_ext()`].
+    pub fn acl_size(acl: acl_t) -> ssize_t;
+
+    /// Copy `acl` to `buf_p` (`size` bytes) in its external, contiguous representation.
+    pub fn acl_copy_ext(buf_p: *mut c_void, acl: acl_t, size: ssize_t) -> ssize_t;
+
+    /// New ACL from its external representation, to free with [`acl_free()`].
+    pub fn acl_copy_int(buf_p: *const c_void) -> acl_t;
+
+    /// Get the entry `entry_id` ([`ACL_FIRST_ENTRY`], [`ACL_NEXT_ENTRY`], ...) of `acl`.
+    ///
+    /// This changes the iteration state stored in `acl`. Returns -1 with `errno` set to `EINVAL`
+    /// when there are no more entries.
+    pub fn acl_get_entry(acl: acl_t, entry_id: c_int, entry_p: *mut acl_entry_t) -> c_int;
+
+    /// Add a new entry to the ACL pointed by `acl_p`, which may be reallocated.
+    pub fn acl_create_entry(acl_p: *mut acl_t, entry_p: *mut acl_entry_t) -> c_int;
+
+    /// Get the tag type of `entry_d`.
+    pub fn acl_get_tag_type(entry_d: acl_entry_t, tag_type_p: *mut acl_tag_t) -> c_int;
+
+    /// Set the tag type of `entry_d`.
+    pub fn acl_set_tag_type(entry_d: acl_entry_t, tag_type: acl_tag_t) -> c_int;
+
+    /// Get the permission set of `entry_d`, pointing into the entry.
+    pub fn acl_get_permset(entry_d: acl_entry_t, permset_p: *mut acl_permset_t) -> c_int;
+
+    /// 1 if `perm` is in `permset_d`, 0 if not, -1 on error.
+    pub fn acl_get_perm_np(permset_d: acl_permset_t, perm: acl_perm_t) -> c_int;
+
+    /// Add `perm` to `permset_d`.
+    pub fn acl_add_perm(permset_d: acl_permset_t, perm: acl_perm_t) -> c_int;
+
+    /// Copy of the qualifier of `entry_d`, a `guid_t` for extended entries, to free with
+    /// [`acl_free()`].
+    pub fn acl_get_qualifier(entry_d: acl_entry_t) -> *mut c_void;
+
+    /// Set the qualifier of `entry_d`, a `guid_t` for extended entries.
+    pub fn acl_set_qualifier(entry_d: acl_entry_t, tag_qualifier_p: *const c_void) -> c_int;
+}
@@ -6,13 +6,15 @@
 // Types and methods should be added in the same order as they are in the original header to make
 // maintenance easier.
 
+#[cfg(feature = "macos_13_0_0")]
+use core::fmt;
 use core::hash::Hash;
 use core::mem::ManuallyDrop;
 pub use std::os::raw::c_int;
 
 #[cfg(feature = "macos_13_0_0")]
 pub use libc::{cpu_subtype_t, cpu_type_t};
-pub use libc::{dev_t, gid_t, mode_t, pid_t, stat, statfs, timespec, timeval, uid_t};
+pub use libc::{dev_t, gid_t, ino_t, mode_t, pid_t, stat, statfs, timespec, timeval, uid_t};
 #[cfg(feature = "macos_14_0_0")]
 use mach2::mach_types::uuid_t;
 use objc2::{Encoding, RefEncode};
@@ -31,6 +33,8 @@ use super::{es_address_type_t, es_authentication_type_t};
 use super::{
     es_authorization_rule_class_t, es_od_account_type_t, es_od_member_type_t, es_od_record_type_t, es_xpc_domain_type_t,
 };
+#[cfg(feature = "macos_15_0_0")]
+use super::{es_gatekeeper_user_override_file_type_t, es_sha256_t};
 
 /// Provides the [`stat`][struct@stat] information and path to a file that relates to a security
 /// event. The path may be truncated, which is indicated by the `path_truncated` flag.
@@ -1746,6 +1750,17 @@ ffi_wrap_enum!(
     ES_AUTO_UNLOCK_AUTH_PROMPT = 2,
 );
 
+#[cfg(feature = "macos_13_0_0")]
+impl fmt::Display for es_auto_unlock_type_t {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::ES_AUTO_UNLOCK_MACHINE_UNLOCK => f.write_str("machine unlock"),
+            Self::ES_AUTO_UNLOCK_AUTH_PROMPT => f.write_str("authorization prompt"),
+            Self(v) => write!(f, "unknown auto unlock type ({v})"),
+        }
+    }
+}
+
 /// Auto Unlock authentication data for type
 /// [`ES_AUTHENTICATION_TYPE_TOKEN`][crate::es_authentication_type_t].
 ///
@@ -2757,6 +2772,49 @@ pub struct es_event_xpc_connect_t {
     pub service_domain_type: es_xpc_domain_type_t,
 }
 
+/// Code signing information of a file, see [`es_event_gatekeeper_user_override_t`]
+#[cfg(feature = "macos_15_0_0")]
+#[repr(C)]
+pub struct es_signed_file_info_t {
+    /// Code directory hash of the file
+    pub cdhash: [u8; 20],
+    /// Signing id of the file
+    pub signing_id: es_string_token_t,
+    /// Team id of the file
+    pub team_id: es_string_token_t,
+}
+
+/// Notification for a Gatekeeper override initiated by the user.
+///
+/// This event type does not support caching (notify-only). The file can be designated by a path
+/// only when it could not be looked up, [`Self::file_type`] describes which member of
+/// [`Self::file`] is valid.
+#[cfg(feature = "macos_15_0_0")]
+#[repr(C)]
+pub struct es_event_gatekeeper_user_override_t {
+    /// Describes which member of the `file` union is valid
+    pub file_type: es_gatekeeper_user_override_file_type_t,
+    /// The file that the user overrode the Gatekeeper decision for
+    pub file: es_event_gatekeeper_user_override_t_anon0,
+    /// Optional. SHA-256 of the file
+    pub sha256: *mut es_sha256_t,
+    /// Optional. Code signing information, present when the file is signed
+    pub signing_info: *mut es_signed_file_info_t,
+}
+
+#[cfg(feature = "macos_15_0_0")]
+null_fields!(es_event_gatekeeper_user_override_t; sha256 -> es_sha256_t, signing_info -> es_signed_file_info_t);
+
+/// See [`es_event_gatekeeper_user_override_t`]
+#[cfg(feature = "macos_15_0_0")]
+#[repr(C)]
+pub union es_event_gatekeeper_user_override_t_anon0 {
+    /// Valid when `file_type` is [`es_gatekeeper_user_override_file_type_t::ES_GATEKEEPER_USER_OVERRIDE_FILE_TYPE_PATH`]
+    pub file_path: ManuallyDrop<es_string_token_t>,
+    /// Valid when `file_type` is [`es_gatekeeper_user_override_file_type_t::ES_GATEKEEPER_USER_OVERRIDE_FILE_TYPE_FILE`]
+    pub file: ShouldNotBeNull<es_file_t>,
+}
+
 /// Union of all possible events that can appear in an [`es_message_t`]
 #[repr(C)]
 pub union es_events_t {
@@ -2957,6 +3015,10 @@ pub union es_events_t {
     pub od_delete_group: ShouldNotBeNull<es_event_od_delete_group_t>,
     #[cfg(feature = "macos_14_0_0")]
     pub xpc_connect: ShouldNotBeNull<es_event_xpc_connect_t>,
+
+    // 15.0.0
+    #[cfg(feature = "macos_15_0_0")]
+    pub gatekeeper_user_override: ShouldNotBeNull<es_event_gatekeeper_user_override_t>,
 }
 
 /// Indicates the result of the ES subsystem authorization process
@@ -6,6 +6,8 @@
 use core::fmt;
 use core::hash::Hash;
 use core::slice::from_raw_parts;
+use core::str::Utf8Error;
+use std::borrow::Cow;
 use std::ffi::OsStr;
 use std::os::unix::ffi::OsStrExt;
 
@@ -101,6 +103,26 @@ ffi_wrap_enum!(
     ES_PROC_CHECK_TYPE_UDATA_INFO = 0xe,
 );
 
+/// Name of the `proc_info` call number, e.g. `pidinfo` for `PROC_INFO_CALL_PIDINFO`.
+impl fmt::Display for es_proc_check_type_t {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match *self {
+            Self::ES_PROC_CHECK_TYPE_LISTPIDS => "listpids",
+            Self::ES_PROC_CHECK_TYPE_PIDINFO => "pidinfo",
+            Self::ES_PROC_CHECK_TYPE_PIDFDINFO => "pidfdinfo",
+            Self::ES_PROC_CHECK_TYPE_KERNMSGBUF => "kernmsgbuf",
+            Self::ES_PROC_CHECK_TYPE_SETCONTROL => "setcontrol",
+            Self::ES_PROC_CHECK_TYPE_PIDFILEPORTINFO => "pidfileportinfo",
+            Self::ES_PROC_CHECK_TYPE_TERMINATE => "terminate",
+            Self::ES_PROC_CHECK_TYPE_DIRTYCONTROL => "dirtycontrol",
+            Self::ES_PROC_CHECK_TYPE_PIDRUSAGE => "pidrusage",
+            Self::ES_PROC_CHECK_TYPE_UDATA_INFO => "udata_info",
+            Self(v) => return write!(f, "unknown proc check type ({v})"),
+        };
+        f.write_str(name)
+    }
+}
+
 #[cfg(feature = "macos_14_0_0")]
 ffi_wrap_enum!(
     /// This enum describes the types of XPC service domains.
@@ -324,8 +346,464 @@ ffi_wrap_enum!(
     ES_EVENT_TYPE_NOTIFY_OD_DELETE_GROUP = 144,
     --
     ES_EVENT_TYPE_NOTIFY_XPC_CONNECT = 145,
+
+    == MACOS_15_0_0;
+    --
+    ES_EVENT_TYPE_NOTIFY_GATEKEEPER_USER_OVERRIDE = 146,
 );
 
+/// Pairs of `AUTH` and `NOTIFY` event types describing the same operation.
+///
+/// Raw values are used so that the pairing is available whatever the enabled features: a newer
+/// macOS can send events the crate was not compiled for.
+const AUTH_NOTIFY_PAIRS: &[(es_event_type_t, es_event_type_t)] = &[
+    (es_event_type_t(0), es_event_type_t(9)),     // EXEC
+    (es_event_type_t(1), es_event_type_t(10)),    // OPEN
+    (es_event_type_t(2), es_event_type_t(17)),    // KEXTLOAD
+    (es_event_type_t(3), es_event_type_t(20)),    // MMAP
+    (es_event_type_t(4), es_event_type_t(21)),    // MPROTECT
+    (es_event_type_t(5), es_event_type_t(22)),    // MOUNT
+    (es_event_type_t(6), es_event_type_t(25)),    // RENAME
+    (es_event_type_t(7), es_event_type_t(31)),    // SIGNAL
+    (es_event_type_t(8), es_event_type_t(32)),    // UNLINK
+    (es_event_type_t(34), es_event_type_t(35)),   // FILE_PROVIDER_MATERIALIZE
+    (es_event_type_t(36), es_event_type_t(37)),   // FILE_PROVIDER_UPDATE
+    (es_event_type_t(38), es_event_type_t(39)),   // READLINK
+    (es_event_type_t(40), es_event_type_t(41)),   // TRUNCATE
+    (es_event_type_t(42), es_event_type_t(19)),   // LINK
+    (es_event_type_t(44), es_event_type_t(13)),   // CREATE
+    (es_event_type_t(45), es_event_type_t(26)),   // SETATTRLIST
+    (es_event_type_t(46), es_event_type_t(27)),   // SETEXTATTR
+    (es_event_type_t(47), es_event_type_t(28)),   // SETFLAGS
+    (es_event_type_t(48), es_event_type_t(29)),   // SETMODE
+    (es_event_type_t(49), es_event_type_t(30)),   // SETOWNER
+    (es_event_type_t(50), es_event_type_t(51)),   // CHDIR
+    (es_event_type_t(52), es_event_type_t(53)),   // GETATTRLIST
+    (es_event_type_t(56), es_event_type_t(57)),   // CHROOT
+    (es_event_type_t(58), es_event_type_t(59)),   // UTIMES
+    (es_event_type_t(60), es_event_type_t(61)),   // CLONE
+    (es_event_type_t(63), es_event_type_t(64)),   // GETEXTATTR
+    (es_event_type_t(65), es_event_type_t(66)),   // LISTEXTATTR
+    (es_event_type_t(67), es_event_type_t(68)),   // READDIR
+    (es_event_type_t(69), es_event_type_t(70)),   // DELETEEXTATTR
+    (es_event_type_t(71), es_event_type_t(72)),   // FSGETPATH
+    (es_event_type_t(74), es_event_type_t(75)),   // SETTIME
+    (es_event_type_t(77), es_event_type_t(76)),   // UIPC_BIND
+    (es_event_type_t(79), es_event_type_t(78)),   // UIPC_CONNECT
+    (es_event_type_t(80), es_event_type_t(14)),   // EXCHANGEDATA
+    (es_event_type_t(81), es_event_type_t(82)),   // SETACL
+    (es_event_type_t(85), es_event_type_t(86)),   // PROC_CHECK
+    (es_event_type_t(87), es_event_type_t(16)),   // GET_TASK
+    (es_event_type_t(88), es_event_type_t(89)),   // SEARCHFS
+    (es_event_type_t(90), es_event_type_t(62)),   // FCNTL
+    (es_event_type_t(91), es_event_type_t(24)),   // IOKIT_OPEN
+    (es_event_type_t(92), es_event_type_t(93)),   // PROC_SUSPEND_RESUME
+    (es_event_type_t(98), es_event_type_t(99)),   // REMOUNT
+    (es_event_type_t(100), es_event_type_t(101)), // GET_TASK_READ
+    (es_event_type_t(109), es_event_type_t(110)), // COPYFILE
+];
+
+/// Event types supporting target path muting, as documented for `es_mute_path()`.
+///
+/// Raw values are used for the same reasons as in [`AUTH_NOTIFY_PAIRS`].
+const TARGET_PATH_MUTING_EVENTS: &[es_event_type_t] = &[
+    es_event_type_t(0),
+    es_event_type_t(9), // EXEC
+    es_event_type_t(1),
+    es_event_type_t(10), // OPEN
+    es_event_type_t(3),
+    es_event_type_t(20), // MMAP
+    es_event_type_t(6),
+    es_event_type_t(25), // RENAME
+    es_event_type_t(7),
+    es_event_type_t(31), // SIGNAL
+    es_event_type_t(8),
+    es_event_type_t(32), // UNLINK
+    es_event_type_t(12), // CLOSE
+    es_event_type_t(44),
+    es_event_type_t(13), // CREATE
+    es_event_type_t(87),
+    es_event_type_t(16), // GET_TASK
+    es_event_type_t(42),
+    es_event_type_t(19), // LINK
+    es_event_type_t(45),
+    es_event_type_t(26), // SETATTRLIST
+    es_event_type_t(46),
+    es_event_type_t(27), // SETEXTATTR
+    es_event_type_t(47),
+    es_event_type_t(28), // SETFLAGS
+    es_event_type_t(48),
+    es_event_type_t(29), // SETMODE
+    es_event_type_t(49),
+    es_event_type_t(30), // SETOWNER
+    es_event_type_t(33), // WRITE
+    es_event_type_t(38),
+    es_event_type_t(39), // READLINK
+    es_event_type_t(40),
+    es_event_type_t(41), // TRUNCATE
+    es_event_type_t(50),
+    es_event_type_t(51), // CHDIR
+    es_event_type_t(52),
+    es_event_type_t(53), // GETATTRLIST
+    es_event_type_t(54), // STAT
+    es_event_type_t(55), // ACCESS
+    es_event_type_t(56),
+    es_event_type_t(57), // CHROOT
+    es_event_type_t(58),
+    es_event_type_t(59), // UTIMES
+    es_event_type_t(60),
+    es_event_type_t(61), // CLONE
+    es_event_type_t(90),
+    es_event_type_t(62), // FCNTL
+    es_event_type_t(63),
+    es_event_type_t(64), // GETEXTATTR
+    es_event_type_t(65),
+    es_event_type_t(66), // LISTEXTATTR
+    es_event_type_t(67),
+    es_event_type_t(68), // READDIR
+    es_event_type_t(69),
+    es_event_type_t(70), // DELETEEXTATTR
+    es_event_type_t(73), // DUP
+    es_event_type_t(77),
+    es_event_type_t(76), // UIPC_BIND
+    es_event_type_t(79),
+    es_event_type_t(78), // UIPC_CONNECT
+    es_event_type_t(80),
+    es_event_type_t(14), // EXCHANGEDATA
+    es_event_type_t(81),
+    es_event_type_t(82), // SETACL
+    es_event_type_t(85),
+    es_event_type_t(86), // PROC_CHECK
+    es_event_type_t(88),
+    es_event_type_t(89), // SEARCHFS
+    es_event_type_t(92),
+    es_event_type_t(93), // PROC_SUSPEND_RESUME
+    es_event_type_t(95), // GET_TASK_NAME
+    es_event_type_t(96), // TRACE
+    es_event_type_t(97), // REMOTE_THREAD_CREATE
+    es_event_type_t(100),
+    es_event_type_t(101), // GET_TASK_READ
+    es_event_type_t(102), // GET_TASK_INSPECT
+    es_event_type_t(109),
+    es_event_type_t(110), // COPYFILE
+];
+
+/// Names of the event types, indexed by their raw value. See [`es_event_type_t::name()`].
+///
+/// All known event types are listed, whatever the enabled features, for the same reasons as in
+/// [`AUTH_NOTIFY_PAIRS`].
+const EVENT_TYPE_NAMES: &[&str] = &[
+    "auth_exec",
+    "auth_open",
+    "auth_kextload",
+    "auth_mmap",
+    "auth_mprotect",
+    "auth_mount",
+    "auth_rename",
+    "auth_signal",
+    "auth_unlink",
+    "notify_exec",
+    "notify_open",
+    "notify_fork",
+    "notify_close",
+    "notify_create",
+    "notify_exchangedata",
+    "notify_exit",
+    "notify_get_task",
+    "notify_kextload",
+    "notify_kextunload",
+    "notify_link",
+    "notify_mmap",
+    "notify_mprotect",
+    "notify_mount",
+    "notify_unmount",
+    "notify_iokit_open",
+    "notify_rename",
+    "notify_setattrlist",
+    "notify_setextattr",
+    "notify_setflags",
+    "notify_setmode",
+    "notify_setowner",
+    "notify_signal",
+    "notify_unlink",
+    "notify_write",
+    "auth_file_provider_materialize",
+    "notify_file_provider_materialize",
+    "auth_file_provider_update",
+    "notify_file_provider_update",
+    "auth_readlink",
+    "notify_readlink",
+    "auth_truncate",
+    "notify_truncate",
+    "auth_link",
+    "notify_lookup",
+    "auth_create",
+    "auth_setattrlist",
+    "auth_setextattr",
+    "auth_setflags",
+    "auth_setmode",
+    "auth_setowner",
+    "auth_chdir",
+    "notify_chdir",
+    "auth_getattrlist",
+    "notify_getattrlist",
+    "notify_stat",
+    "notify_access",
+    "auth_chroot",
+    "notify_chroot",
+    "auth_utimes",
+    "notify_utimes",
+    "auth_clone",
+    "notify_clone",
+    "notify_fcntl",
+    "auth_getextattr",
+    "notify_getextattr",
+    "auth_listextattr",
+    "notify_listextattr",
+    "auth_readdir",
+    "notify_readdir",
+    "auth_deleteextattr",
+    "notify_deleteextattr",
+    "auth_fsgetpath",
+    "notify_fsgetpath",
+    "notify_dup",
+    "auth_settime",
+    "notify_settime",
+    "notify_uipc_bind",
+    "auth_uipc_bind",
+    "notify_uipc_connect",
+    "auth_uipc_connect",
+    "auth_exchangedata",
+    "auth_setacl",
+    "notify_setacl",
+    "notify_pty_grant",
+    "notify_pty_close",
+    "auth_proc_check",
+    "notify_proc_check",
+    "auth_get_task",
+    "auth_searchfs",
+    "notify_searchfs",
+    "auth_fcntl",
+    "auth_iokit_open",
+    "auth_proc_suspend_resume",
+    "notify_proc_suspend_resume",
+    "notify_cs_invalidated",
+    "notify_get_task_name",
+    "notify_trace",
+    "notify_remote_thread_create",
+    "auth_remount",
+    "notify_remount",
+    "auth_get_task_read",
+    "notify_get_task_read",
+    "notify_get_task_inspect",
+    "notify_setuid",
+    "notify_setgid",
+    "notify_seteuid",
+    "notify_setegid",
+    "notify_setreuid",
+    "notify_setregid",
+    "auth_copyfile",
+    "notify_copyfile",
+    "notify_authentication",
+    "notify_xp_malware_detected",
+    "notify_xp_malware_remediated",
+    "notify_lw_session_login",
+    "notify_lw_session_logout",
+    "notify_lw_session_lock",
+    "notify_lw_session_unlock",
+    "notify_screensharing_attach",
+    "notify_screensharing_detach",
+    "notify_openssh_login",
+    "notify_openssh_logout",
+    "notify_login_login",
+    "notify_login_logout",
+    "notify_btm_launch_item_add",
+    "notify_btm_launch_item_remove",
+    "notify_profile_add",
+    "notify_profile_remove",
+    "notify_su",
+    "notify_authorization_petition",
+    "notify_authorization_judgement",
+    "notify_sudo",
+    "notify_od_group_add",
+    "notify_od_group_remove",
+    "notify_od_group_set",
+    "notify_od_modify_password",
+    "notify_od_disable_user",
+    "notify_od_enable_user",
+    "notify_od_attribute_value_add",
+    "notify_od_attribute_value_remove",
+    "notify_od_attribute_set",
+    "notify_od_create_user",
+    "notify_od_create_group",
+    "notify_od_delete_user",
+    "notify_od_delete_group",
+    "notify_xpc_connect",
+    "notify_gatekeeper_user_override",
+];
+
+impl es_event_type_t {
+    /// Returns the `AUTH` counterpart of this event type, e.g. `ES_EVENT_TYPE_AUTH_EXEC` for
+    /// `ES_EVENT_TYPE_NOTIFY_EXEC`.
+    ///
+    /// Returns `self` if it is already an `AUTH` event type and `None` for notify-only events like
+    /// `ES_EVENT_TYPE_NOTIFY_FORK` or unknown event types.
+    pub fn auth_variant(&self) -> Option<es_event_type_t> {
+        AUTH_NOTIFY_PAIRS
+            .iter()
+            .find(|(auth, notify)| auth == self || notify == self)
+            .map(|(auth, _)| *auth)
+    }
+
+    /// Returns the `NOTIFY` counterpart of this event type, e.g. `ES_EVENT_TYPE_NOTIFY_EXEC` for
+    /// `ES_EVENT_TYPE_AUTH_EXEC`.
+    ///
+    /// Returns `self` if it is a `NOTIFY` event type with an `AUTH` counterpart. Notify-only
+    /// events like `ES_EVENT_TYPE_NOTIFY_FORK` and unknown event types return `None`.
+    pub fn notify_variant(&self) -> Option<es_event_type_t> {
+        AUTH_NOTIFY_PAIRS
+            .iter()
+            .find(|(auth, notify)| auth == self || notify == self)
+            .map(|(_, notify)| *notify)
+    }
+
+    /// `true` if the event type supports target path muting (`ES_MUTE_PATH_TYPE_TARGET_PREFIX` and
+    /// `ES_MUTE_PATH_TYPE_TARGET_LITERAL`).
+    ///
+    /// Target muting a path for an event type that does not support it is silently a no-op. The
+    /// list of supported events comes from the documentation of
+    #[cfg_attr(feature = "macos_12_0_0", doc = "[`es_mute_path()`][crate::es_mute_path].")]
+    #[cfg_attr(not(feature = "macos_12_0_0"), doc = "`es_mute_path()`.")]
+    pub fn supports_target_path_muting(&self) -> bool {
+        TARGET_PATH_MUTING_EVENTS.contains(self)
+    }
+
+    /// Short name of the event type, lowercased and without the `ES_EVENT_TYPE_` prefix, e.g.
+    /// `"notify_exec"` for `ES_EVENT_TYPE_NOTIFY_EXEC`.
+    ///
+    /// Returns `None` for event types unknown to this crate.
+    pub fn name(&self) -> Option<&'static str> {
+        EVENT_TYPE_NAMES.get(self.0 as usize).copied()
+    }
+
+    /// Event type from its name, the reverse of [`Self::name()`].
+    ///
+    /// Both the short form (`"notify_exec"`) and the full constant name (`"ES_EVENT_TYPE_NOTIFY_EXEC"`)
+    /// are accepted, ignoring ASCII case. Only the event types available with the enabled features
+    /// are recognized, others return `None`.
+    ///
+    /// ```
+    /// use endpoint_sec_sys::es_event_type_t;
+    ///
+    /// let exec = es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXEC;
+    /// assert_eq!(es_event_type_t::from_name("notify_exec"), Some(exec));
+    /// assert_eq!(es_event_type_t::from_name("ES_EVENT_TYPE_NOTIFY_EXEC"), Some(exec));
+    /// assert_eq!(es_event_type_t::from_name(&exec.to_string()), Some(exec));
+    /// assert_eq!(es_event_type_t::from_name("exec"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<es_event_type_t> {
+        const PREFIX: &str = "ES_EVENT_TYPE_";
+
+        let short = match name.get(..PREFIX.len()) {
+            Some(prefix) if prefix.eq_ignore_ascii_case(PREFIX) => &name[PREFIX.len()..],
+            _ => name,
+        };
+
+        EVENT_TYPE_NAMES[..Self::ES_EVENT_TYPE_LAST.0 as usize]
+            .iter()
+            .position(|known| known.eq_ignore_ascii_case(short))
+            .map(|raw| es_event_type_t(raw as u32))
+    }
+
+    /// Coarse category of the event type, to filter or route events without listing them all.
+    ///
+    /// The `AUTH` and `NOTIFY` variants of an event are in the same category. Event types unknown
+    /// to this crate are in [`EventCategory::Other`].
+    pub fn category(&self) -> EventCategory {
+        // Raw values are used for the same reasons as in `AUTH_NOTIFY_PAIRS`
+        match self.0 {
+            // exec, signal, fork, exit, get_task
+            0 | 7 | 9 | 11 | 15 | 16 | 31 => EventCategory::Process,
+            // mmap, mprotect
+            3 | 4 | 20 | 21 => EventCategory::Memory,
+            // kextload, kextunload, iokit_open
+            2 | 17 | 18 | 24 | 91 => EventCategory::System,
+            // open, mount, rename, unlink, close, create, exchangedata, link, unmount, setattrlist,
+            // setextattr, setflags, setmode, setowner, write
+            1 | 5 | 6 | 8 | 10 | 12..=14 | 19 | 22 | 23 | 25..=30 | 32 | 33 => EventCategory::FileSystem,
+            // file_provider_*, readlink, truncate, link, lookup, create, set*, chdir, getattrlist,
+            // stat, access, chroot, utimes, clone, fcntl, *extattr, readdir, fsgetpath, dup
+            34..=73 => EventCategory::FileSystem,
+            // settime
+            74 | 75 => EventCategory::System,
+            // uipc_bind, uipc_connect
+            76..=79 => EventCategory::Ipc,
+            // exchangedata, setacl
+            80..=82 => EventCategory::FileSystem,
+            // pty_grant, pty_close
+            83 | 84 => EventCategory::System,
+            // proc_check, get_task
+            85..=87 => EventCategory::Process,
+            // searchfs, fcntl
+            88..=90 => EventCategory::FileSystem,
+            // proc_suspend_resume, cs_invalidated, get_task_name, trace, remote_thread_create
+            92..=97 => EventCategory::Process,
+            // remount
+            98 | 99 => EventCategory::FileSystem,
+            // get_task_read, get_task_inspect, setuid, setgid, seteuid, setegid, setreuid, setregid
+            100..=108 => EventCategory::Process,
+            // copyfile
+            109 | 110 => EventCategory::FileSystem,
+            // authentication
+            111 => EventCategory::Authentication,
+            // xp_malware_detected, xp_malware_remediated
+            112 | 113 => EventCategory::Xprotect,
+            // lw_session_*, screensharing_*, openssh_*, login_*
+            114..=123 => EventCategory::Authentication,
+            // btm_launch_item_*, profile_*
+            124..=127 => EventCategory::System,
+            // su, authorization_*, sudo, od_*
+            128..=144 => EventCategory::Authentication,
+            // xpc_connect
+            145 => EventCategory::Ipc,
+            // gatekeeper_user_override
+            146 => EventCategory::Xprotect,
+            _ => EventCategory::Other,
+        }
+    }
+}
+
+/// Coarse category of an event type, see [`es_event_type_t::category()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EventCategory {
+    /// Process lifecycle, identity and inspection: exec, fork, exit, signals, task ports, ...
+    Process,
+    /// File system objects and mounts: open, write, rename, attributes, ...
+    FileSystem,
+    /// Memory mappings and their protection
+    Memory,
+    /// Network and inter-process communication: UNIX domain sockets, XPC
+    Ipc,
+    /// Authentication, login sessions and account management
+    Authentication,
+    /// System configuration: kernel extensions, I/O Kit, time, background tasks, profiles, ...
+    System,
+    /// XProtect and Gatekeeper
+    Xprotect,
+    /// Event types unknown to this crate
+    Other,
+}
+
+/// Uses [`es_event_type_t::name()`], falling back to the raw value for unknown event types.
+impl fmt::Display for es_event_type_t {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
 ffi_wrap_enum!(
     /// Valid authorization values to be used when responding to a
     /// [`es_message_t`][super::es_message_t] auth event
@@ -473,6 +951,53 @@ impl es_string_token_t {
         let s = self.as_os_str();
         (s.is_empty() == false).then_some(s)
     }
+
+    /// See the data as a [`str`] if it is valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// See [`Self::as_os_str()`].
+    #[inline]
+    pub unsafe fn as_str(&self) -> Result<&str, Utf8Error> {
+        core::str::from_utf8(self.as_os_str().as_bytes())
+    }
+
+    /// See the data as a [`str`], replacing invalid UTF-8 sequences with
+    /// [`U+FFFD REPLACEMENT CHARACTER`][core::char::REPLACEMENT_CHARACTER].
+    ///
+    /// Only allocates when the data is not valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// See [`Self::as_os_str()`].
+    #[inline]
+    pub unsafe fn as_str_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(self.as_os_str().as_bytes())
+    }
+
+    /// `true` if the data starts with `prefix`, without allocating.
+    ///
+    /// An empty `prefix` is a prefix of every token, including empty ones.
+    ///
+    /// # Safety
+    ///
+    /// See [`Self::as_os_str()`].
+    #[inline]
+    pub unsafe fn starts_with(&self, prefix: &[u8]) -> bool {
+        self.as_os_str().as_bytes().starts_with(prefix)
+    }
+
+    /// `true` if the data is exactly `other`, without allocating.
+    ///
+    /// A token with a `length` of 0 is equal to an empty slice, whatever its `data` pointer.
+    ///
+    /// # Safety
+    ///
+    /// See [`Self::as_os_str()`].
+    #[inline]
+    pub unsafe fn eq_bytes(&self, other: &[u8]) -> bool {
+        self.as_os_str().as_bytes() == other
+    }
 }
 
 ffi_wrap_enum!(
@@ -647,3 +1172,278 @@ ffi_wrap_enum!(
     --
     ES_OD_RECORD_TYPE_GROUP = 1,
 );
+
+/// SHA-256 hash of a file
+#[cfg(feature = "macos_15_0_0")]
+pub type es_sha256_t = [u8; 32];
+
+#[cfg(feature = "macos_15_0_0")]
+ffi_wrap_enum!(
+    /// Type of the file designated in a Gatekeeper user override, see
+    /// [`es_event_gatekeeper_user_override_t`][super::es_event_gatekeeper_user_override_t]
+    es_gatekeeper_user_override_file_type_t(u32);
+
+    == MACOS_15_0_0;
+    /// The file is only known by its path
+    ES_GATEKEEPER_USER_OVERRIDE_FILE_TYPE_PATH = 0,
+    --
+    /// The file is described by an `es_file_t`
+    ES_GATEKEEPER_USER_OVERRIDE_FILE_TYPE_FILE = 1,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_type_name() {
+        assert_eq!(
+            es_event_type_t::ES_EVENT_TYPE_AUTH_EXEC.name(),
+            Some("auth_exec")
+        );
+        assert_eq!(
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXEC.name(),
+            Some("notify_exec")
+        );
+        assert_eq!(
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_FILE_PROVIDER_MATERIALIZE.name(),
+            Some("notify_file_provider_materialize")
+        );
+        assert_eq!(es_event_type_t(145).name(), Some("notify_xpc_connect"));
+        assert_eq!(
+            es_event_type_t(146).name(),
+            Some("notify_gatekeeper_user_override")
+        );
+        assert_eq!(es_event_type_t(147).name(), None);
+        assert_eq!(es_event_type_t(u32::MAX).name(), None);
+
+        assert_eq!(
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_FORK.to_string(),
+            "notify_fork"
+        );
+        assert_eq!(es_event_type_t(1000).to_string(), "1000");
+
+        // Every variant known at compile time has a name matching its constant
+        for raw in 0..es_event_type_t::ES_EVENT_TYPE_LAST.0 {
+            let ty = es_event_type_t(raw);
+            let debug = format!("{ty:?}").to_lowercase();
+            let expected = format!(
+                "es_event_type_t::es_event_type_{}({raw})",
+                ty.name().unwrap()
+            );
+            assert_eq!(debug, expected);
+        }
+    }
+
+    #[test]
+    fn test_event_type_from_name() {
+        let exec = es_event_type_t::ES_EVENT_TYPE_AUTH_EXEC;
+        assert_eq!(es_event_type_t::from_name("auth_exec"), Some(exec));
+        assert_eq!(es_event_type_t::from_name("AUTH_EXEC"), Some(exec));
+        assert_eq!(
+            es_event_type_t::from_name("ES_EVENT_TYPE_AUTH_EXEC"),
+            Some(exec)
+        );
+        assert_eq!(
+            es_event_type_t::from_name("es_event_type_Auth_Exec"),
+            Some(exec)
+        );
+        assert_eq!(
+            es_event_type_t::from_name("notify_file_provider_materialize"),
+            Some(es_event_type_t::ES_EVENT_TYPE_NOTIFY_FILE_PROVIDER_MATERIALIZE)
+        );
+
+        assert_eq!(es_event_type_t::from_name(""), None);
+        assert_eq!(es_event_type_t::from_name("ES_EVENT_TYPE_"), None);
+        assert_eq!(es_event_type_t::from_name("exec"), None);
+        assert_eq!(es_event_type_t::from_name("notify_exec "), None);
+        assert_eq!(es_event_type_t::from_name("ES_EVENT_TYPE_LAST"), None);
+
+        // Only the event types available with the enabled features are known
+        #[cfg(feature = "macos_15_0_0")]
+        assert_eq!(
+            es_event_type_t::from_name("notify_gatekeeper_user_override"),
+            Some(es_event_type_t(146))
+        );
+        #[cfg(not(feature = "macos_15_0_0"))]
+        assert_eq!(
+            es_event_type_t::from_name("notify_gatekeeper_user_override"),
+            None
+        );
+
+        // Round-trip with `name()`
+        for raw in 0..es_event_type_t::ES_EVENT_TYPE_LAST.0 {
+            let ty = es_event_type_t(raw);
+            assert_eq!(es_event_type_t::from_name(ty.name().unwrap()), Some(ty));
+            let constant = format!("ES_EVENT_TYPE_{}", ty.name().unwrap().to_uppercase());
+            assert_eq!(es_event_type_t::from_name(&constant), Some(ty));
+        }
+    }
+
+    #[test]
+    fn test_event_type_category() {
+        use EventCategory::*;
+
+        let cases = [
+            (0, Process),          // auth_exec
+            (11, Process),         // notify_fork
+            (10, FileSystem),      // notify_open
+            (6, FileSystem),       // auth_rename
+            (3, Memory),           // auth_mmap
+            (21, Memory),          // notify_mprotect
+            (17, System),          // notify_kextload
+            (79, Ipc),             // auth_uipc_connect
+            (145, Ipc),            // notify_xpc_connect
+            (120, Authentication), // notify_openssh_login
+            (131, Authentication), // notify_sudo
+            (112, Xprotect),       // notify_xp_malware_detected
+            (147, Other),
+            (u32::MAX, Other),
+        ];
+        for (raw, category) in cases {
+            let ty = es_event_type_t(raw);
+            assert_eq!(ty.category(), category, "{ty}");
+        }
+
+        // Every known event type has a category, shared by its AUTH and NOTIFY variants
+        for raw in 0..EVENT_TYPE_NAMES.len() as u32 {
+            let ty = es_event_type_t(raw);
+            assert_ne!(ty.category(), Other, "{ty}");
+            if let (Some(auth), Some(notify)) = (ty.auth_variant(), ty.notify_variant()) {
+                assert_eq!(auth.category(), notify.category(), "{ty}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_supports_target_path_muting() {
+        let supported = [
+            es_event_type_t::ES_EVENT_TYPE_AUTH_EXEC,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXEC,
+            es_event_type_t::ES_EVENT_TYPE_AUTH_OPEN,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_WRITE,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_CLOSE,
+        ];
+        for ty in supported {
+            assert!(ty.supports_target_path_muting(), "{ty}");
+        }
+
+        let unsupported = [
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_FORK,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXIT,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_KEXTLOAD,
+            es_event_type_t(1000),
+            es_event_type_t(u32::MAX),
+        ];
+        for ty in unsupported {
+            assert!(ty.supports_target_path_muting() == false, "{ty}");
+        }
+
+        // AUTH and NOTIFY variants of an event type agree
+        for raw in 0..EVENT_TYPE_NAMES.len() as u32 {
+            let ty = es_event_type_t(raw);
+            if let (Some(auth), Some(notify)) = (ty.auth_variant(), ty.notify_variant()) {
+                assert_eq!(
+                    auth.supports_target_path_muting(),
+                    notify.supports_target_path_muting(),
+                    "{ty}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_proc_check_type_display() {
+        assert_eq!(
+            es_proc_check_type_t::ES_PROC_CHECK_TYPE_PIDINFO.to_string(),
+            "pidinfo"
+        );
+        assert_eq!(
+            es_proc_check_type_t::ES_PROC_CHECK_TYPE_UDATA_INFO.to_string(),
+            "udata_info"
+        );
+        assert_eq!(
+            es_proc_check_type_t(0xa).to_string(),
+            "unknown proc check type (10)"
+        );
+    }
+
+    #[test]
+    fn test_string_token_as_str() {
+        let valid = es_string_token_t {
+            length: 5,
+            data: b"hello".as_ptr().cast(),
+        };
+        // Safety: `length` and `data` are in sync
+        unsafe {
+            assert_eq!(valid.as_str(), Ok("hello"));
+            assert!(matches!(valid.as_str_lossy(), Cow::Borrowed("hello")));
+        }
+
+        let invalid = es_string_token_t {
+            length: 4,
+            data: b"a\xFFb\xFE".as_ptr().cast(),
+        };
+        // Safety: `length` and `data` are in sync
+        unsafe {
+            assert_eq!(invalid.as_str().unwrap_err().valid_up_to(), 1);
+            assert_eq!(invalid.as_str_lossy(), "a\u{FFFD}b\u{FFFD}");
+        }
+
+        let empty = es_string_token_t {
+            length: 0,
+            data: b"ignored".as_ptr().cast(),
+        };
+        let null = es_string_token_t {
+            length: 0,
+            data: core::ptr::null(),
+        };
+        for token in [empty, null] {
+            // Safety: `length` is 0, `data` is never read
+            unsafe {
+                assert_eq!(token.as_str(), Ok(""));
+                assert!(matches!(token.as_str_lossy(), Cow::Borrowed("")));
+            }
+        }
+    }
+
+    #[test]
+    fn test_string_token_starts_with_eq_bytes() {
+        let token = es_string_token_t {
+            length: 8,
+            data: b"/usr/bin/ls".as_ptr().cast(),
+        };
+        // Safety: `length` and `data` are in sync, `data` is longer than `length`
+        unsafe {
+            assert!(token.starts_with(b"/usr/"));
+            assert!(token.starts_with(b"/usr/bin"));
+            assert!(token.starts_with(b""));
+            // Only the first `length` bytes are part of the token
+            assert!(token.starts_with(b"/usr/bin/") == false);
+            assert!(token.starts_with(b"/bin") == false);
+
+            assert!(token.eq_bytes(b"/usr/bin"));
+            assert!(token.eq_bytes(b"/usr/bi") == false);
+            assert!(token.eq_bytes(b"/usr/bin/ls") == false);
+            assert!(token.eq_bytes(b"") == false);
+        }
+
+        let empty = es_string_token_t {
+            length: 0,
+            data: b"ignored".as_ptr().cast(),
+        };
+        let null = es_string_token_t {
+            length: 0,
+            data: core::ptr::null(),
+        };
+        for token in [empty, null] {
+            // Safety: `length` is 0, `data` is never read
+            unsafe {
+                assert!(token.starts_with(b""));
+                assert!(token.starts_with(b"ignored") == false);
+                assert!(token.eq_bytes(b""));
+                assert!(token.eq_bytes(b"ignored") == false);
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Helpers shared by the examples.
+//!
+//! The examples must be run as root, from a binary signed with the
+//! `com.apple.developer.endpoint-security.client` entitlement (see `Entitlement.plist`), by a
+//! process that has been granted Full Disk Access.
+
+use std::io::BufRead;
+use std::process::Command;
+
+use endpoint_sec::version;
+
+/// Detect the running version of macOS with `sw_vers` and give it to [`version::set_runtime_version()`].
+pub fn set_runtime_version() {
+    let output = Command::new("sw_vers")
+        .arg("-productVersion")
+        .output()
+        .expect("failed to run sw_vers");
+    let output = String::from_utf8_lossy(&output.stdout);
+
+    let mut parts = output.trim().split('.').map(|part| part.parse::<u64>().unwrap_or(0));
+    let major = parts.next().unwrap_or(10);
+    let minor = parts.next().unwrap_or(15);
+    let patch = parts.next().unwrap_or(0);
+
+    version::set_runtime_version(major, minor, patch);
+}
+
+/// Block until the user presses Enter, while the client handles messages in the background.
+pub fn wait_for_enter() {
+    println!("Press Enter to stop");
+    let _ = std::io::stdin().lock().lines().next();
+}
@@ -0,0 +1,35 @@
+//! Minimal exec logger: prints every program execution with its arguments.
+//!
+//! See `common/mod.rs` for the requirements to run the examples.
+
+mod common;
+
+use endpoint_sec::sys::es_event_type_t;
+use endpoint_sec::{Client, Event};
+
+fn main() {
+    common::set_runtime_version();
+
+    let mut client = Client::new(|_client, message| {
+        let Some(Event::NotifyExec(exec)) = message.event() else {
+            return;
+        };
+
+        let target = exec.target();
+        let args: Vec<_> = exec.args().map(|arg| arg.to_string_lossy()).collect();
+
+        println!(
+            "[{}] {:?} {:?}",
+            target.audit_token().pid(),
+            target.executable().path(),
+            args,
+        );
+    })
+    .expect("failed to create the client");
+
+    client
+        .subscribe(&[es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXEC])
+        .expect("failed to subscribe");
+
+    common::wait_for_enter();
+}
@@ -0,0 +1,103 @@
+//! Process tree builder correlating `fork`, `exec` and `exit` events, printing the ancestry of
+//! each executed program.
+//!
+//! See the documentation of `EventFork` for the details of the correlation, and `common/mod.rs`
+//! for the requirements to run the examples.
+
+mod common;
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::sync::Mutex;
+
+use endpoint_sec::sys::es_event_type_t;
+use endpoint_sec::{AuditToken, Client, Event, Message};
+
+/// Identity of a process: `pid` and `pidversion`, the `pid` alone is reused by the OS
+type ProcessKey = (i32, i32);
+
+fn key(token: &AuditToken) -> ProcessKey {
+    (token.pid(), token.pidversion())
+}
+
+#[derive(Default)]
+struct ProcessTree {
+    parents: HashMap<ProcessKey, ProcessKey>,
+    executables: HashMap<ProcessKey, OsString>,
+}
+
+impl ProcessTree {
+    fn handle(&mut self, message: &Message) {
+        let process = key(&message.process().audit_token());
+
+        match message.event() {
+            Some(Event::NotifyFork(fork)) => {
+                let child = key(&fork.child().audit_token());
+                self.parents.insert(child, process);
+                // Until it execs, the child runs the same executable as its parent
+                if let Some(exe) = self.executables.get(&process).cloned() {
+                    self.executables.insert(child, exe);
+                }
+            },
+            Some(Event::NotifyExec(exec)) => {
+                let target = exec.target();
+                let after = key(&target.audit_token());
+
+                // Same process, new identity: move its parent link
+                if let Some(parent) = self.parents.remove(&process) {
+                    self.parents.insert(after, parent);
+                }
+                self.executables.remove(&process);
+                self.executables.insert(after, target.executable().path().into());
+
+                println!("{}", self.ancestry(after));
+            },
+            Some(Event::NotifyExit(_)) => {
+                self.parents.remove(&process);
+                self.executables.remove(&process);
+            },
+            _ => (),
+        }
+    }
+
+    /// `exe (pid) <- parent exe (pid) <- ...`, stopping at processes started before the client
+    fn ancestry(&self, mut process: ProcessKey) -> String {
+        let mut out = String::new();
+
+        loop {
+            if !out.is_empty() {
+                out.push_str(" <- ");
+            }
+            match self.executables.get(&process) {
+                Some(exe) => out.push_str(&format!("{} ({})", exe.to_string_lossy(), process.0)),
+                None => out.push_str(&format!("? ({})", process.0)),
+            }
+
+            match self.parents.get(&process) {
+                Some(&parent) => process = parent,
+                None => return out,
+            }
+        }
+    }
+}
+
+fn main() {
+    common::set_runtime_version();
+
+    let tree = Mutex::new(ProcessTree::default());
+
+    let mut client = Client::new(move |_client, message| {
+        tree.lock().unwrap_or_else(|e| e.into_inner()).handle(&message);
+    })
+    .expect("failed to create the client");
+
+    client
+        .subscribe(&[
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_FORK,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXEC,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXIT,
+        ])
+        .expect("failed to subscribe");
+
+    common::wait_for_enter();
+}
@@ -0,0 +1,71 @@
+//! `AUTH` policy denying to open files of a directory for writing: `protect_directory <dir>`.
+//!
+//! This only covers `open(2)`: a complete policy would also handle `AUTH_UNLINK`, `AUTH_RENAME`,
+//! `AUTH_TRUNCATE`, etc.
+//!
+//! See `common/mod.rs` for the requirements to run the examples.
+
+mod common;
+
+use std::path::PathBuf;
+
+use endpoint_sec::sys::{es_event_type_t, es_mute_path_type_t, FWRITE};
+use endpoint_sec::{Client, Event};
+
+fn main() {
+    let protected: PathBuf = std::env::args_os()
+        .nth(1)
+        .expect("usage: protect_directory <dir>")
+        .into();
+    let protected = protected.canonicalize().expect("failed to canonicalize the directory");
+
+    common::set_runtime_version();
+    println!("Protecting {}", protected.display());
+
+    let mut client = Client::new(move |client, message| {
+        let Some(Event::AuthOpen(open)) = message.event() else {
+            return;
+        };
+
+        let requested = open.fflag();
+        let file = open.file();
+
+        // The response is the set of flags that are authorized: the open is denied if any of the
+        // requested flags is missing from it.
+        let authorized = if file.as_path().starts_with(&protected) {
+            if open.is_write() {
+                println!("Denied write to {:?}", file.path());
+            }
+            // Never authorize `FWRITE` for protected files, even when it was not requested: the
+            // cached result must not cover later write opens
+            (requested & !FWRITE) as u32
+        } else {
+            // Allow everything, not only what was requested this time, so that the cached result
+            // also covers later opens with other flags
+            u32::MAX
+        };
+
+        // The decision depends only on the file, never on the process, so it can be cached. A
+        // cached result only covers opens with a subset of the authorized flags: since `FWRITE` is
+        // never authorized for the protected files, writing to them always sends a new message.
+        if let Err(err) = client.respond_flags_result(&message, authorized, true) {
+            eprintln!("Failed to respond: {err}");
+        }
+    })
+    .expect("failed to create the client");
+
+    // Mute ourselves: handling our own `AUTH` messages makes it easy to deadlock
+    let me = std::env::current_exe().expect("failed to get the current executable");
+    client
+        .mute_path(
+            me.as_os_str(),
+            es_mute_path_type_t::ES_MUTE_PATH_TYPE_LITERAL,
+        )
+        .expect("failed to mute self");
+
+    client
+        .subscribe(&[es_event_type_t::ES_EVENT_TYPE_AUTH_OPEN])
+        .expect("failed to subscribe");
+
+    common::wait_for_enter();
+}
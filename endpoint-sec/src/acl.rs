@@ -3,14 +3,23 @@
 //! **Important**: ACLs generated by Endpoint Security are **not** directly compatible
 //! with `sys/acl.h`, be sure to check up-to-date documentation for your macOS target.
 
-use endpoint_sec_sys::{_acl, acl_t};
+use std::io;
+use std::os::raw::c_int;
+use std::ptr::{self, NonNull};
+
+use endpoint_sec_sys::{
+    _acl, acl_copy_ext, acl_copy_int, acl_entry_t, acl_free, acl_get_entry, acl_get_perm_np, acl_get_permset,
+    acl_get_qualifier, acl_get_tag_type, acl_size, acl_t, acl_tag_t, ACL_FIRST_ENTRY, ACL_NEXT_ENTRY,
+};
+
+use crate::{AclPerms, Flags};
 
 /// ACL from Endpoint Security.
 // **DO NOT USE DIRECTLY WITH `sys/acl.h`**
 // TODO: correctly implement Debug/Eq/Hash for ACLs
 #[doc(alias = "acl_t")]
 #[doc(alias = "_acl")]
-pub struct Acl<'a>(#[allow(unused)] &'a _acl);
+pub struct Acl<'a>(&'a _acl);
 
 impl<'a> Acl<'a> {
     /// Builds an ACL wrapper from the raw one.
@@ -21,6 +30,44 @@ impl<'a> Acl<'a> {
         unsafe { r.as_ref() }.map(Self)
     }
 
+    /// Iterator over the entries of the ACL.
+    ///
+    /// Iterating with `acl_get_entry(3)` changes the ACL, which is not allowed on the ones found in
+    /// Endpoint Security messages (see [`acl_t`]). The entries are read from a copy made with
+    /// `acl_copy_ext(3)` followed by `acl_copy_int(3)`, as advised by Apple, which is freed when
+    /// the iterator is dropped.
+    ///
+    /// # Errors
+    ///
+    /// The OS error of the failed copy.
+    pub fn entries(&self) -> io::Result<AclEntries> {
+        let raw = self.0 as *const _acl as acl_t;
+
+        // Safety: `raw` is valid by construction, `acl_size` does not change it
+        let size = unsafe { acl_size(raw) };
+        if size < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // u32 elements: the external representation is made of 32 bits fields
+        let mut buf = vec![0_u32; (size as usize + 3) / 4];
+        // Safety:
+        // - `raw` is valid by construction, `acl_copy_ext` does not change it
+        // - `buf` is at least `size` bytes long
+        if unsafe { acl_copy_ext(buf.as_mut_ptr().cast(), raw, size) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // Safety: `buf` contains the external representation written just above
+        let copy = unsafe { acl_copy_int(buf.as_ptr().cast()) };
+        let acl = NonNull::new(copy).ok_or_else(io::Error::last_os_error)?;
+
+        Ok(AclEntries {
+            acl,
+            next: ACL_FIRST_ENTRY,
+        })
+    }
+
     // NOTE: in the future, if we need to interact with `sys/acl.h`, remember to read the docs
     // in ESMessage.h, there are details to the way it should be done.
 }
@@ -28,3 +75,201 @@ impl<'a> Acl<'a> {
 static_assertions::assert_impl_all!(Acl<'_>: Send);
 
 impl_debug_eq_hash_with_functions!(Acl<'a>;);
+
+/// Iterator over the entries of an [`Acl`], see [`Acl::entries()`].
+pub struct AclEntries {
+    /// Copy of the ACL owned by the iterator, freed on drop
+    acl: NonNull<_acl>,
+    /// Entry id given to the next call to `acl_get_entry()`
+    next: c_int,
+}
+
+impl Iterator for AclEntries {
+    type Item = AclEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut entry: acl_entry_t = ptr::null_mut();
+        // Safety:
+        // - `self.acl` is a valid ACL owned by `self`, it can be changed
+        // - `entry` is a valid pointer
+        if unsafe { acl_get_entry(self.acl.as_ptr(), self.next, &mut entry) } != 0 {
+            return None;
+        }
+        self.next = ACL_NEXT_ENTRY;
+
+        // Safety: `entry` was just obtained from a valid ACL
+        Some(unsafe { AclEntry::from_raw(entry) })
+    }
+}
+
+impl Drop for AclEntries {
+    fn drop(&mut self) {
+        // Safety: `self.acl` was allocated by `acl_copy_int()` and is only freed here
+        unsafe { acl_free(self.acl.as_ptr().cast()) };
+    }
+}
+
+// Safety: the ACL is owned by the iterator and never shared
+unsafe impl Send for AclEntries {}
+
+impl std::fmt::Debug for AclEntries {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AclEntries").finish_non_exhaustive()
+    }
+}
+
+/// Entry of an [`Acl`], read by [`AclEntries`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AclEntry {
+    /// Tag type of the entry
+    tag_type: acl_tag_t,
+    /// Permissions of the entry
+    permset: AclPerms,
+    /// GUID the entry applies to
+    qualifier: Option<[u8; 16]>,
+}
+
+impl AclEntry {
+    /// Read the entry.
+    ///
+    /// # Safety
+    ///
+    /// `entry` must be a valid entry of an ACL that was not obtained from Endpoint Security.
+    unsafe fn from_raw(entry: acl_entry_t) -> Self {
+        let mut tag_type = acl_tag_t::ACL_UNDEFINED_TAG;
+        // Safety: `entry` is valid per the function contract and `tag_type` is a valid pointer
+        if unsafe { acl_get_tag_type(entry, &mut tag_type) } != 0 {
+            tag_type = acl_tag_t::ACL_UNDEFINED_TAG;
+        }
+
+        let mut permset = AclPerms::default();
+        let mut raw_permset = ptr::null_mut();
+        // Safety: `entry` is valid per the function contract and `raw_permset` is a valid pointer
+        if unsafe { acl_get_permset(entry, &mut raw_permset) } == 0 {
+            for &(_, perm) in AclPerms::KNOWN {
+                // Safety: `raw_permset` was just obtained from a valid entry
+                if unsafe { acl_get_perm_np(raw_permset, perm) } == 1 {
+                    permset = permset | AclPerms(perm);
+                }
+            }
+        }
+
+        let qualifier = match tag_type {
+            acl_tag_t::ACL_EXTENDED_ALLOW | acl_tag_t::ACL_EXTENDED_DENY => {
+                // Safety: `entry` is valid per the function contract
+                let raw = unsafe { acl_get_qualifier(entry) };
+                if raw.is_null() {
+                    None
+                } else {
+                    // Safety: the qualifier of extended entries is a `guid_t`, 16 bytes long
+                    let guid = unsafe { raw.cast::<[u8; 16]>().read_unaligned() };
+                    // Safety: the qualifier is a copy allocated by `acl_get_qualifier()`
+                    unsafe { acl_free(raw) };
+                    Some(guid)
+                }
+            },
+            _ => None,
+        };
+
+        Self {
+            tag_type,
+            permset,
+            qualifier,
+        }
+    }
+
+    /// Whether the entry allows or denies its permissions.
+    #[inline(always)]
+    pub fn tag_type(&self) -> acl_tag_t {
+        self.tag_type
+    }
+
+    /// Permissions allowed or denied by the entry.
+    #[inline(always)]
+    pub fn permset(&self) -> AclPerms {
+        self.permset
+    }
+
+    /// GUID of the user or group the entry applies to, `None` if the entry has no qualifier.
+    ///
+    /// Use `mbr_uuid_to_id(3)` to get the corresponding UID or GID.
+    #[inline(always)]
+    pub fn qualifier(&self) -> Option<[u8; 16]> {
+        self.qualifier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::{
+        acl_add_perm, acl_create_entry, acl_init, acl_set_qualifier, acl_set_tag_type, ACL_EXECUTE, ACL_READ_DATA,
+        ACL_WRITE_DATA,
+    };
+
+    use super::*;
+
+    /// Add an entry to `acl`
+    fn add_entry(acl: &mut acl_t, tag_type: acl_tag_t, perms: &[u32], guid: [u8; 16]) {
+        let mut entry = ptr::null_mut();
+        let mut permset = ptr::null_mut();
+        // Safety: `acl` was created by `acl_init()` and is not shared, pointers are valid
+        unsafe {
+            assert_eq!(acl_create_entry(acl, &mut entry), 0);
+            assert_eq!(acl_set_tag_type(entry, tag_type), 0);
+            assert_eq!(acl_set_qualifier(entry, guid.as_ptr().cast()), 0);
+            assert_eq!(acl_get_permset(entry, &mut permset), 0);
+            for &perm in perms {
+                assert_eq!(acl_add_perm(permset, perm), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_entries() {
+        // Safety: always safe to call
+        let mut raw = unsafe { acl_init(2) };
+        assert!(raw.is_null() == false);
+        add_entry(
+            &mut raw,
+            acl_tag_t::ACL_EXTENDED_ALLOW,
+            &[ACL_READ_DATA, ACL_EXECUTE],
+            [1; 16],
+        );
+        add_entry(
+            &mut raw,
+            acl_tag_t::ACL_EXTENDED_DENY,
+            &[ACL_WRITE_DATA],
+            [2; 16],
+        );
+
+        let acl = Acl::from_raw(raw).unwrap();
+        let entries: Vec<_> = acl.entries().unwrap().collect();
+        assert_eq!(entries, [
+            AclEntry {
+                tag_type: acl_tag_t::ACL_EXTENDED_ALLOW,
+                permset: AclPerms::ACL_READ_DATA | AclPerms::ACL_EXECUTE,
+                qualifier: Some([1; 16]),
+            },
+            AclEntry {
+                tag_type: acl_tag_t::ACL_EXTENDED_DENY,
+                permset: AclPerms::ACL_WRITE_DATA,
+                qualifier: Some([2; 16]),
+            },
+        ]);
+        assert_eq!(entries[1].tag_type(), acl_tag_t::ACL_EXTENDED_DENY);
+        assert_eq!(entries[1].permset().to_string(), "ACL_WRITE_DATA");
+        assert_eq!(entries[1].qualifier(), Some([2; 16]));
+
+        // Each iterator works on its own copy
+        let mut first = acl.entries().unwrap();
+        let mut second = acl.entries().unwrap();
+        assert_eq!(first.next(), Some(entries[0]));
+        assert_eq!(second.next(), Some(entries[0]));
+        assert_eq!(first.next(), Some(entries[1]));
+        assert_eq!(first.next(), None);
+        assert_eq!(second.count(), 1);
+
+        // Safety: `raw` was allocated by `acl_init()`, `acl` is not used anymore
+        unsafe { acl_free(raw.cast()) };
+    }
+}
@@ -2,6 +2,8 @@
 
 use endpoint_sec_sys::{es_auth_result_t, es_event_id_t, es_result_t, es_result_type_t};
 
+use crate::ExpectedResponseType;
+
 /// When a [`Message`][crate::Message] is received, it is associated with an `Action`
 #[doc(alias = "es_event_id_t")]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -39,6 +41,193 @@ impl ActionResult {
             _ => None,
         }
     }
+
+    /// `true` if the action was allowed.
+    ///
+    /// For [`Self::Flags`], the action is allowed when at least one flag was authorized, even if
+    /// only part of the requested ones were: an `open(2)` asking for `FREAD | FWRITE` that was
+    /// only authorized `FREAD` is considered allowed. Use [`Self::is_allowed_for()`] to check that
+    /// all the requested flags were authorized.
+    #[inline]
+    pub fn is_allowed(&self) -> bool {
+        match *self {
+            Self::Auth(result) => result == es_auth_result_t::ES_AUTH_RESULT_ALLOW,
+            Self::Flags(flags) => flags != 0,
+        }
+    }
+
+    /// `true` if the action was allowed for all the `requested` flags.
+    ///
+    /// For [`Self::Flags`], the action is allowed when every flag of `requested` was authorized.
+    /// For [`Self::Auth`], `requested` is ignored and this is the same as [`Self::is_allowed()`].
+    #[inline]
+    pub fn is_allowed_for(&self, requested: u32) -> bool {
+        match *self {
+            Self::Auth(_) => self.is_allowed(),
+            Self::Flags(flags) => flags & requested == requested,
+        }
+    }
+
+    /// `true` if the action was denied.
+    ///
+    /// For [`Self::Flags`], the action is denied when no flag was authorized.
+    #[inline]
+    pub fn is_denied(&self) -> bool {
+        match *self {
+            Self::Auth(result) => result == es_auth_result_t::ES_AUTH_RESULT_DENY,
+            Self::Flags(flags) => flags == 0,
+        }
+    }
 }
 
 static_assertions::assert_impl_all!(ActionResult: Send);
+
+/// Decision for an `AUTH` event, given to [`Client::respond()`][crate::Client::respond] which
+/// translates it to the response expected by the event.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AuthDecision {
+    /// Allow the event.
+    ///
+    /// For events responded to with flags, all the flags of the original event are authorized.
+    Allow,
+    /// Deny the event.
+    ///
+    /// For events responded to with flags, no flag is authorized.
+    Deny,
+    /// Only authorize these flags, for events responded to with flags (like `AUTH_OPEN`).
+    AllowFlags(u32),
+}
+
+impl AuthDecision {
+    /// Response to give for an event expecting `expected`, `None` if the decision cannot be used
+    /// for it.
+    pub(crate) fn to_response(self, expected: ExpectedResponseType) -> Option<ActionResult> {
+        let response = match (expected, self) {
+            (ExpectedResponseType::Auth, Self::Allow) => ActionResult::Auth(es_auth_result_t::ES_AUTH_RESULT_ALLOW),
+            (ExpectedResponseType::Auth, Self::Deny) => ActionResult::Auth(es_auth_result_t::ES_AUTH_RESULT_DENY),
+            (ExpectedResponseType::Auth, Self::AllowFlags(_)) => return None,
+            (ExpectedResponseType::Flags { flags }, Self::Allow) => ActionResult::Flags(flags),
+            (ExpectedResponseType::Flags { .. }, Self::Deny) => ActionResult::Flags(0),
+            (ExpectedResponseType::Flags { .. }, Self::AllowFlags(flags)) => ActionResult::Flags(flags),
+        };
+
+        Some(response)
+    }
+
+    /// Response to give for an `AUTH` event whose expected response is unknown, like
+    /// [`Event::Unknown`][crate::Event::Unknown] ones: [`Self::AllowFlags`] gives a flags response,
+    /// the others an auth response.
+    pub(crate) fn to_unknown_response(self) -> ActionResult {
+        match self {
+            Self::Allow => ActionResult::Auth(es_auth_result_t::ES_AUTH_RESULT_ALLOW),
+            Self::Deny => ActionResult::Auth(es_auth_result_t::ES_AUTH_RESULT_DENY),
+            Self::AllowFlags(flags) => ActionResult::Flags(flags),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::es_result_t_anon_0;
+
+    use super::*;
+
+    #[test]
+    fn test_action_result() {
+        let raw = |result_type, result| es_result_t { result_type, result };
+
+        let allow = ActionResult::from_raw(raw(
+            es_result_type_t::ES_RESULT_TYPE_AUTH,
+            es_result_t_anon_0 {
+                auth: es_auth_result_t::ES_AUTH_RESULT_ALLOW,
+            },
+        ))
+        .unwrap();
+        assert_eq!(
+            allow,
+            ActionResult::Auth(es_auth_result_t::ES_AUTH_RESULT_ALLOW)
+        );
+        assert!(allow.is_allowed());
+        assert!(allow.is_denied() == false);
+
+        let deny = ActionResult::Auth(es_auth_result_t::ES_AUTH_RESULT_DENY);
+        assert!(deny.is_allowed() == false);
+        assert!(deny.is_denied());
+
+        // Unknown auth results are neither allowed nor denied
+        let unknown = ActionResult::Auth(es_auth_result_t(2));
+        assert!(unknown.is_allowed() == false);
+        assert!(unknown.is_denied() == false);
+
+        let flags = ActionResult::from_raw(raw(
+            es_result_type_t::ES_RESULT_TYPE_FLAGS,
+            es_result_t_anon_0 { flags: 0x3 },
+        ))
+        .unwrap();
+        assert_eq!(flags, ActionResult::Flags(0x3));
+        assert!(flags.is_allowed());
+        assert!(flags.is_denied() == false);
+
+        let no_flags = ActionResult::Flags(0);
+        assert!(no_flags.is_allowed() == false);
+        assert!(no_flags.is_denied());
+
+        // Partially authorized flags are allowed, but not for all the requested flags
+        let partial = ActionResult::Flags(0x1);
+        assert!(partial.is_allowed());
+        assert!(partial.is_allowed_for(0x1));
+        assert!(partial.is_allowed_for(0x3) == false);
+        assert!(flags.is_allowed_for(0x3));
+        assert!(flags.is_allowed_for(0x2));
+        assert!(no_flags.is_allowed_for(0x1) == false);
+        assert!(no_flags.is_allowed_for(0));
+
+        assert!(allow.is_allowed_for(0x3));
+        assert!(deny.is_allowed_for(0x3) == false);
+
+        let unknown_type = raw(es_result_type_t(2), es_result_t_anon_0 { flags: 0x3 });
+        assert_eq!(ActionResult::from_raw(unknown_type), None);
+    }
+
+    #[test]
+    fn test_auth_decision_to_response() {
+        let auth = ExpectedResponseType::Auth;
+        let flags = ExpectedResponseType::Flags { flags: 0b11 };
+
+        assert_eq!(
+            AuthDecision::Allow.to_response(auth),
+            Some(ActionResult::Auth(es_auth_result_t::ES_AUTH_RESULT_ALLOW))
+        );
+        assert_eq!(
+            AuthDecision::Deny.to_response(auth),
+            Some(ActionResult::Auth(es_auth_result_t::ES_AUTH_RESULT_DENY))
+        );
+        assert_eq!(AuthDecision::AllowFlags(1).to_response(auth), None);
+
+        assert_eq!(
+            AuthDecision::Allow.to_response(flags),
+            Some(ActionResult::Flags(0b11))
+        );
+        assert_eq!(
+            AuthDecision::Deny.to_response(flags),
+            Some(ActionResult::Flags(0))
+        );
+        assert_eq!(
+            AuthDecision::AllowFlags(0b01).to_response(flags),
+            Some(ActionResult::Flags(0b01))
+        );
+
+        assert_eq!(
+            AuthDecision::Allow.to_unknown_response(),
+            ActionResult::Auth(es_auth_result_t::ES_AUTH_RESULT_ALLOW)
+        );
+        assert_eq!(
+            AuthDecision::Deny.to_unknown_response(),
+            ActionResult::Auth(es_auth_result_t::ES_AUTH_RESULT_DENY)
+        );
+        assert_eq!(
+            AuthDecision::AllowFlags(0b10).to_unknown_response(),
+            ActionResult::Flags(0b10)
+        );
+    }
+}
@@ -1,6 +1,7 @@
 //! Expose a wrapper around [`audit_token_t`]: [`AuditToken`]
 
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 use endpoint_sec_sys::{
     au_asid_t, audit_token_t, audit_token_to_asid, audit_token_to_auid, audit_token_to_egid, audit_token_to_euid,
@@ -40,6 +41,9 @@ impl AuditToken {
     /// processes when first connecting a client, but it is relatively easy to list the PIDs of the
     /// current processes.
     ///
+    /// Returns `None` if the process has exited, does not exist or cannot be inspected by the
+    /// caller.
+    ///
     /// ## Implementation details
     ///
     /// Currently this method is implemented following the method [described here][method], with
@@ -167,6 +171,7 @@ impl AuditToken {
     /// The audit session ID.
     ///
     /// **NOTE**: Used to identify Mach tasks and senders of Mach messages as subjects of the audit system.
+    #[doc(alias = "audit_session_id")]
     #[inline(always)]
     pub fn asid(&self) -> au_asid_t {
         // Safety: The audit_token_t is owned by self.
@@ -181,6 +186,32 @@ impl AuditToken {
         // Safety: The audit_token_t is owned by self.
         unsafe { audit_token_to_pidversion(self.0) }
     }
+
+    /// `true` if both tokens identify the same process, that is they have the same
+    /// [`pid`][Self::pid] and [`pidversion`][Self::pidversion].
+    ///
+    /// This is the same comparison as [`PartialEq`], use [`Self::raw_eq()`] to compare the other
+    /// fields too.
+    #[inline]
+    pub fn is_same_process(&self, other: &AuditToken) -> bool {
+        self == other
+    }
+
+    /// `true` if both tokens are identical byte for byte, including the user and group IDs that can
+    /// change during the lifetime of a process.
+    #[inline]
+    pub fn raw_eq(&self, other: &AuditToken) -> bool {
+        self.0.val == other.0.val
+    }
+
+    /// Identity of the process: its pid and pidversion.
+    ///
+    /// The values are read directly at the indices used by `audit_token_to_pid()` and
+    /// `audit_token_to_pidversion()` in libbsm, to avoid two calls each time a token is hashed.
+    #[inline(always)]
+    fn identity(&self) -> (u32, u32) {
+        (self.0.val[5], self.0.val[7])
+    }
 }
 
 /// Crate-private methods
@@ -200,26 +231,83 @@ impl AuditToken {
 
 static_assertions::assert_impl_all!(AuditToken: Send);
 
-impl_debug_eq_hash_with_functions!(
-    AuditToken;
-    auid,
-    euid,
-    egid,
-    ruid,
-    rgid,
-    pid,
-    asid,
-    pidversion,
-);
+impl fmt::Debug for AuditToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuditToken")
+            .field("auid", &self.auid())
+            .field("euid", &self.euid())
+            .field("egid", &self.egid())
+            .field("ruid", &self.ruid())
+            .field("rgid", &self.rgid())
+            .field("pid", &self.pid())
+            .field("asid", &self.asid())
+            .field("pidversion", &self.pidversion())
+            .finish()
+    }
+}
+
+/// Audit tokens are equal when they identify the same process: same pid and pidversion, which is
+/// how macOS tells processes apart. The user and group IDs can change during the lifetime of a
+/// process and are ignored, use [`AuditToken::raw_eq()`] to compare them too.
+impl PartialEq for AuditToken {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.identity() == other.identity()
+    }
+}
+
+impl Eq for AuditToken {}
+
+/// Consistent with [`PartialEq`]: only the pid and pidversion are hashed.
+impl Hash for AuditToken {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.identity().hash(state);
+    }
+}
 
 #[cfg(test)]
-#[cfg(feature = "audit_token_from_pid")]
 mod test {
+    use std::collections::hash_map::DefaultHasher;
+
+    #[cfg(feature = "audit_token_from_pid")]
     use sysinfo::{Pid, PidExt, ProcessExt, ProcessRefreshKind, System, SystemExt};
 
     use super::*;
 
+    /// Token with the given pid, pidversion and euid, other fields zeroed
+    fn token(pid: u32, pidversion: u32, euid: u32) -> AuditToken {
+        let mut val = [0; 8];
+        val[1] = euid;
+        val[5] = pid;
+        val[7] = pidversion;
+        AuditToken(audit_token_t { val })
+    }
+
+    /// Hash of `token` with the default hasher
+    fn hash(token: &AuditToken) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        token.hash(&mut hasher);
+        hasher.finish()
+    }
+
     #[test]
+    fn test_eq_by_process_identity() {
+        let root = token(42, 3, 0);
+        let user = token(42, 3, 501);
+
+        assert!(root == user);
+        assert!(root.is_same_process(&user));
+        assert_eq!(hash(&root), hash(&user));
+        assert!(root.raw_eq(&user) == false);
+        assert!(root.raw_eq(&root));
+
+        assert!(root != token(42, 4, 0));
+        assert!(root != token(43, 3, 0));
+    }
+
+    #[test]
+    #[cfg(feature = "audit_token_from_pid")]
     fn audit_token_from_pid() {
         let raw_pid = std::process::id();
 
@@ -234,4 +322,16 @@ mod test {
         assert_eq!(process.group_id().map_or(0, |x| *x), audit.egid());
         assert_eq!(process.pid().as_u32(), audit.pid() as u32);
     }
+
+    #[test]
+    #[cfg(feature = "audit_token_from_pid")]
+    fn audit_token_from_pid_exited() {
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let pid = child.id() as pid_t;
+        child.wait().unwrap();
+
+        // The child has been reaped, its pid does not designate a process anymore (barring reuse)
+        assert!(AuditToken::from_pid(pid).is_none());
+        assert!(AuditToken::from_pid(-1).is_none());
+    }
 }
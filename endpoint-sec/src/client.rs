@@ -1,19 +1,28 @@
 //! Expose a wrapper around [`es_client_t`]: [`Client`]
+use std::any::Any;
 use std::ffi::OsStr;
 #[cfg(doc)]
 use std::ffi::OsString;
 use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
 use std::os::unix::prelude::OsStrExt;
-use std::panic::{catch_unwind, RefUnwindSafe};
+use std::panic::{catch_unwind, AssertUnwindSafe, RefUnwindSafe};
 use std::ptr::NonNull;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 use endpoint_sec_sys::*;
 
-use crate::utils::convert_byte_slice_to_cow_cstr;
-use crate::{AuditToken, Message};
+use crate::utils::{convert_byte_slice_to_cow_cstr, current_thread_id};
+#[cfg(feature = "macos_13_0_0")]
+use crate::MutingExplanation;
+use crate::{ActionResult, AuditToken, AuthDecision, Event, ExpectedResponseType, Message, Subscription};
 #[cfg(feature = "macos_12_0_0")]
 use crate::{MutedPath, MutedProcess};
 
+mod builder;
+pub use builder::*;
+
 /// Wrapper around the opaque type that stores the ES client state.
 ///
 /// Note: this implementation ignores the return value of [`es_delete_client`] if you use [`Drop`],
@@ -31,6 +40,17 @@ pub struct Client<'b> {
 
     /// Ensure the client cannot outlive its message handling closure.
     block_lifetime: PhantomData<&'b ()>,
+
+    /// Watchdog thread started by [`ClientBuilder::liveness_check()`], stopped before the client is
+    /// deleted.
+    liveness_check: Option<LivenessCheck>,
+
+    /// Number of messages received with an event unknown to this crate, shared with the handler.
+    unknown_events: Arc<AtomicU64>,
+
+    /// ID of the thread that last ran the handler, 0 before the first message, shared with the
+    /// handler.
+    handler_thread_id: Arc<AtomicU64>,
 }
 
 static_assertions::assert_not_impl_any!(Client: Send, Sync);
@@ -94,28 +114,76 @@ impl Client<'_> {
     /// - Be running as root when launching the client (and while it is active)
     /// - Not have previously reached the maximum number of connected clients
     ///
+    /// **Note**: creating a client clears the result caches of **all** clients in the system, which
+    /// affects their performance until the caches fill up again. Avoid destroying and recreating
+    /// clients to change their configuration, use [`Client::reconfigure()`] instead.
+    ///
     /// See [`es_new_client()`].
+    ///
+    /// A panic in `handler` is caught and ignored, the next messages are still given to it. See
+    /// [`Client::new_with_panic_handler()`] to be notified of such panics.
     #[doc(alias = "es_new_client")]
     pub fn new<'b, F>(handler: F) -> Result<Client<'b>, NewClientError>
     where
         F: Fn(&mut Client<'_>, Message) + RefUnwindSafe + 'b,
+    {
+        Self::new_with_panic_handler(handler, |_| {})
+    }
+
+    /// Creates a new [`Client`] like [`Client::new()`], calling `on_panic` with the payload of the
+    /// panics caught in `handler`.
+    ///
+    /// **A panicking handler does not respond to its message**: when the message is an `AUTH` one,
+    /// the client will be killed if it is not responded to before its deadline. `on_panic` is a
+    /// good place to log the panic and decide how such messages should be handled, for example by
+    /// setting a flag checked by a watchdog. It is called on the thread of the handler, after the
+    /// message has been dropped. A panic in `on_panic` itself is caught and ignored.
+    ///
+    /// The panic hook of the process runs before `on_panic`, like for any other panic.
+    #[doc(alias = "es_new_client")]
+    pub fn new_with_panic_handler<'b, F, P>(handler: F, on_panic: P) -> Result<Client<'b>, NewClientError>
+    where
+        F: Fn(&mut Client<'_>, Message) + RefUnwindSafe + 'b,
+        P: Fn(Box<dyn Any + Send>) + RefUnwindSafe + 'b,
     {
         let mut client = std::ptr::null_mut();
+        let unknown_events = Arc::new(AtomicU64::new(0));
+        let unknown_events_for_handler = Arc::clone(&unknown_events);
+        let handler_thread_id = Arc::new(AtomicU64::new(0));
+        let thread_id_for_handler = Arc::clone(&handler_thread_id);
 
         let block_handler = block2::RcBlock::new(
             move |client: NonNull<es_client_t>, message: NonNull<es_message_t>| {
-                let _err = catch_unwind(|| {
+                thread_id_for_handler.store(current_thread_id(), Ordering::Relaxed);
+
+                // Never dropped: the client is owned by the `Client` returned to the app, deleting it
+                // here would be a double free
+                let mut client = ManuallyDrop::new(Client {
+                    inner: client,
+                    block_lifetime: PhantomData,
+                    liveness_check: None,
+                    unknown_events: Arc::clone(&unknown_events_for_handler),
+                    handler_thread_id: Arc::clone(&thread_id_for_handler),
+                });
+
+                // `client` is only a pointer and shared counters, a panic cannot leave it broken
+                let res = catch_unwind(AssertUnwindSafe(|| {
                     // Safety: Apple guarantees the received message is non-null and valid
                     let message = unsafe { Message::from_raw(message) };
-                    let mut client = Client {
-                        inner: client,
-                        block_lifetime: PhantomData,
-                    };
+                    if matches!(message.event(), None | Some(Event::Unknown { .. })) {
+                        unknown_events_for_handler.fetch_add(1, Ordering::Relaxed);
+                    }
 
                     handler(&mut client, message);
-                    // Forget the client, else it would be double-dropped
-                    std::mem::forget(client);
-                });
+                }));
+
+                // Safety: `client` is not used anymore
+                unsafe { client.release_without_deleting() };
+
+                if let Err(payload) = res {
+                    // The payload is moved into `on_panic`, nothing observes it afterwards
+                    let _err = catch_unwind(AssertUnwindSafe(|| on_panic(payload)));
+                }
             },
         );
 
@@ -138,11 +206,47 @@ impl Client<'_> {
         Ok(Client {
             inner: unsafe { NonNull::new_unchecked(client) },
             block_lifetime: PhantomData,
+            liveness_check: None,
+            unknown_events,
+            handler_thread_id,
         })
     }
 
+    /// Number of messages received by this client for which [`Message::event()`] returned
+    /// [`Event::Unknown`], which happens when running on a newer macOS that sends events unknown to
+    /// this crate (or to the features it was compiled with). Messages for which it returned `None`
+    /// are counted too.
+    ///
+    /// See [`ClientBuilder::auto_allow_unknown_auth()`] to avoid being killed by such `AUTH` events.
+    #[inline]
+    pub fn unknown_events_count(&self) -> u64 {
+        self.unknown_events.load(Ordering::Relaxed)
+    }
+
+    /// ID of the thread that last ran the handler of this client, `None` before the first message.
+    ///
+    /// Endpoint Security calls the handler from a serial dispatch queue it creates and manages
+    /// itself: [`es_new_client()`] gives no way to choose, observe or tag this queue. A serial
+    /// queue runs one handler call at a time but is not bound to a thread, so the ID can change
+    /// between calls even though the calls never overlap.
+    ///
+    /// From inside the handler, this is the ID of the current thread. The ID is the same kind as
+    /// [`Thread::thread_id()`][crate::Thread::thread_id] and as given by `pthread_threadid_np()`.
+    #[inline]
+    pub fn handler_thread_id(&self) -> Option<u64> {
+        match self.handler_thread_id.load(Ordering::Relaxed) {
+            0 => None,
+            id => Some(id),
+        }
+    }
+
     /// Subscribe the client to `events`, without removing previous subscriptions.
     ///
+    /// Event types introduced in a macOS version newer than the
+    /// [runtime version][crate::version::set_runtime_version] are rejected with
+    /// [`SubscribeError::UnavailableEvents`] listing all of them before calling Endpoint Security,
+    /// which would ignore them. See [`Client::subscribe_available()`] to skip them instead.
+    ///
     /// # Panics
     ///
     /// `events` can contain at most `u32::MAX` elements. This is a limitation of Apple's API.
@@ -150,15 +254,62 @@ impl Client<'_> {
     /// See [`es_subscribe`].
     #[doc(alias = "es_subscribe")]
     #[inline(always)]
-    pub fn subscribe(&mut self, events: &[es_event_type_t]) -> Result<(), ReturnError> {
-        assert!(events.len() < u32::MAX as usize);
+    pub fn subscribe(&mut self, events: &[es_event_type_t]) -> Result<(), SubscribeError> {
+        let unavailable = unavailable_events(events);
+        if unavailable.is_empty() == false {
+            return Err(SubscribeError::UnavailableEvents(unavailable));
+        }
 
-        // Safety:
-        // - `self.as_mut()` is a valid client by construction
-        // - `events` is a slice for which we have checked the length, `.as_ptr()` and `.len() as
-        //   u32` are both valid
-        // - the result is checked with `.ok()`
-        unsafe { es_subscribe(self.as_mut(), events.as_ptr(), events.len() as u32) }.ok()
+        self.subscribe_unchecked(events)?;
+        Ok(())
+    }
+
+    /// Subscribe the client to the events of `events` that exist on the
+    /// [runtime version][crate::version::set_runtime_version] of macOS, ignoring the others.
+    ///
+    /// Returns the events that were subscribed to. Useful for apps supporting several macOS
+    /// versions with a single list of events.
+    ///
+    /// # Panics
+    ///
+    /// `events` can contain at most `u32::MAX` elements. This is a limitation of Apple's API.
+    pub fn subscribe_available(&mut self, events: &[es_event_type_t]) -> Result<Vec<es_event_type_t>, SubscribeError> {
+        let available: Vec<_> = events.iter().copied().filter(|&ev| is_event_available(ev)).collect();
+
+        self.subscribe_unchecked(&available)?;
+        Ok(available)
+    }
+
+    /// Subscribe the client to `events` like [`Client::subscribe()`], then check with
+    /// [`Client::subscriptions()`] that all of them are active.
+    ///
+    /// `es_subscribe` can succeed while ignoring some events, for example events that the running
+    /// macOS does not support. This method catches such partial failures by returning
+    /// [`SubscribeError::Missing`] with the requested events that are not active. The events that
+    /// could be subscribed to are **not** unsubscribed from in this case.
+    ///
+    /// Useful at startup for apps that must guarantee their whole subscription set is active.
+    ///
+    /// # Panics
+    ///
+    /// `events` can contain at most `u32::MAX` elements. This is a limitation of Apple's API.
+    pub fn subscribe_verified(&mut self, events: &[es_event_type_t]) -> Result<(), SubscribeError> {
+        self.subscribe(events)?;
+
+        let active = self.subscriptions()?;
+        let mut missing: Vec<_> = events
+            .iter()
+            .copied()
+            .filter(|ev| active.contains(ev) == false)
+            .collect();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            missing.sort_unstable_by_key(|ev| ev.0);
+            missing.dedup();
+            Err(SubscribeError::Missing(missing))
+        }
     }
 
     /// Unsubscribe the client from `events`, without removing other subscriptions.
@@ -193,6 +344,25 @@ impl Client<'_> {
         unsafe { es_unsubscribe_all(self.as_mut()) }.ok()
     }
 
+    /// Replace the subscriptions of the client by `events`, minimizing the window where events
+    /// can be missed compared to [`Client::unsubscribe_all()`] followed by [`Client::subscribe()`].
+    ///
+    /// This is [`Client::apply_subscription()`] for a slice of events: the client is first
+    /// subscribed to the new events, then unsubscribed from the previous subscriptions that are
+    /// not part of `events`. Events present in both sets are never interrupted and unavailable
+    /// events are rejected before anything is changed.
+    ///
+    /// Apple's API offers no way to do this atomically, so:
+    ///
+    /// - between the two calls, the client receives both the old and the new events, the handler
+    ///   must be ready for both
+    /// - on error, the client can be left partially reconfigured, e.g. subscribed to the new
+    ///   events without having been unsubscribed from the old ones
+    #[inline]
+    pub fn resubscribe(&mut self, events: &[es_event_type_t]) -> Result<(), SubscribeError> {
+        self.apply_subscription(&Subscription::from(events))
+    }
+
     /// List current subscriptions of client.
     ///
     /// See [`es_subscriptions`].
@@ -201,8 +371,35 @@ impl Client<'_> {
         to_vec_and_free!(self, es_subscriptions)
     }
 
+    /// List current subscriptions of client, sorted by raw value and deduplicated.
+    ///
+    /// [`Self::subscriptions()`] returns them in the order given by Endpoint Security, this
+    /// version is more convenient to compare the current subscriptions with the desired ones.
+    #[doc(alias = "es_subscriptions")]
+    pub fn subscriptions_sorted(&mut self) -> Result<Vec<es_event_type_t>, ReturnError> {
+        let mut subscriptions = self.subscriptions()?;
+        sort_dedup(&mut subscriptions);
+        Ok(subscriptions)
+    }
+
+    /// `true` if the client is currently subscribed to `event`.
+    ///
+    /// See [`Self::subscriptions()`].
+    #[doc(alias = "es_subscriptions")]
+    pub fn is_subscribed(&mut self, event: es_event_type_t) -> Result<bool, ReturnError> {
+        Ok(self.subscriptions()?.contains(&event))
+    }
+
     /// Respond to an auth event.
     ///
+    /// # Errors
+    ///
+    /// Each `AUTH` message must be responded to exactly once. Responding a second time, for example
+    /// because both the handler and a timeout fallback responded, returns
+    /// [`RespondError::DuplicateResponse`], and responding to a message Endpoint Security does
+    /// not know about anymore returns [`RespondError::MessageNotFound`]. These errors usually
+    /// reveal a race in the app and should not be ignored.
+    ///
     /// See [`es_respond_auth_result`]
     #[doc(alias = "es_respond_auth_result")]
     #[inline(always)]
@@ -221,6 +418,14 @@ impl Client<'_> {
 
     /// Respong to an auth event that needs a flag response.
     ///
+    /// # Errors
+    ///
+    /// Each `AUTH` message must be responded to exactly once. Responding a second time, for example
+    /// because both the handler and a timeout fallback responded, returns
+    /// [`RespondError::DuplicateResponse`], and responding to a message Endpoint Security does
+    /// not know about anymore returns [`RespondError::MessageNotFound`]. These errors usually
+    /// reveal a race in the app and should not be ignored.
+    ///
     /// See [`es_respond_flags_result`]
     #[doc(alias = "es_respond_flags_result")]
     #[inline(always)]
@@ -237,6 +442,45 @@ impl Client<'_> {
         unsafe { es_respond_flags_result(self.as_mut(), msg.get_raw_ref(), authorized_flags, cache) }.ok()
     }
 
+    /// Respond to an auth event with `decision`, using [`Self::respond_auth_result()`] or
+    /// [`Self::respond_flags_result()`] depending on the
+    /// [response expected][crate::Event::expected_response_type] by the event of `msg`.
+    ///
+    /// See [`AuthDecision`] for how decisions are translated.
+    ///
+    /// Whether `msg` expects a response is given by its [action type][Message::action_type], so
+    /// `AUTH` events unknown to this crate ([`Event::Unknown`][crate::Event::Unknown]) can be
+    /// responded to too. The response they expect is unknown: [`AuthDecision::AllowFlags`] is sent
+    /// with [`Self::respond_flags_result()`], the other decisions with
+    /// [`Self::respond_auth_result()`].
+    ///
+    /// # Errors
+    ///
+    /// - [`RespondDecisionError::NotAuth`] if `msg` does not expect a response (`NOTIFY` events),
+    ///   nothing is sent
+    /// - [`RespondDecisionError::Mismatch`] if `decision` cannot be used for the event, e.g.
+    ///   [`AuthDecision::AllowFlags`] for `AUTH_EXEC`, nothing is sent
+    /// - [`RespondDecisionError::Respond`] if responding failed, see [`Self::respond_auth_result()`]
+    pub fn respond(&mut self, msg: &Message, decision: AuthDecision, cache: bool) -> Result<(), RespondDecisionError> {
+        if msg.action_type() != es_action_type_t::ES_ACTION_TYPE_AUTH {
+            return Err(RespondDecisionError::NotAuth);
+        }
+
+        let response = match msg.event().and_then(|ev| ev.expected_response_type()) {
+            Some(expected) => decision
+                .to_response(expected)
+                .ok_or(RespondDecisionError::Mismatch { expected, decision })?,
+            None => decision.to_unknown_response(),
+        };
+
+        match response {
+            ActionResult::Auth(result) => self.respond_auth_result(msg, result, cache)?,
+            ActionResult::Flags(flags) => self.respond_flags_result(msg, flags, cache)?,
+        }
+
+        Ok(())
+    }
+
     /// Fully mute the given process.
     ///
     /// See [`es_mute_process`].
@@ -249,16 +493,64 @@ impl Client<'_> {
         unsafe { es_mute_process(self.as_mut(), process.get_raw_ref()) }.ok()
     }
 
+    /// Fully mute all the given processes.
+    ///
+    /// Every process is muted even if muting one of them fails, the first failure is returned with
+    /// the token it happened for. An empty slice does nothing.
+    ///
+    /// See [`Client::mute_process()`].
+    pub fn mute_processes(&mut self, processes: &[AuditToken]) -> Result<(), MuteProcessesError> {
+        for_each_process(processes, |process| self.mute_process(process))
+    }
+
+    /// Fully mute the given process and all its current descendants.
+    ///
+    /// Endpoint Security mutes processes one by one, this method lists the processes currently
+    /// running to find the descendants of `root` and mutes each of them, returning their audit
+    /// tokens (including the one of `root`). Processes that exit while the tree is walked are
+    /// skipped.
+    ///
+    /// **Note**: processes forked after this call are **not** muted automatically, handle
+    /// `ES_EVENT_TYPE_NOTIFY_FORK` events to extend the muting to them.
+    #[cfg(feature = "audit_token_from_pid")]
+    pub fn mute_process_tree(&mut self, root: &AuditToken) -> Result<Vec<AuditToken>, ReturnError> {
+        self.mute_process(root)?;
+
+        let mut muted = vec![*root];
+        for pid in crate::utils::descendant_pids(root.pid()) {
+            let Some(token) = AuditToken::from_pid(pid) else {
+                continue;
+            };
+            self.mute_process(&token)?;
+            muted.push(token);
+        }
+
+        Ok(muted)
+    }
+
     /// Mute only some events for the given process.
     ///
     /// See [`es_mute_process_events`].
     ///
     /// Only available on macOS 12.0+.
+    ///
+    /// Event types introduced in a macOS version newer than the
+    /// [runtime version][crate::version::set_runtime_version] are rejected with
+    /// [`MuteEventsError::UnavailableEvents`] listing all of them, without muting anything.
     #[doc(alias = "es_mute_process_events")]
     #[cfg(feature = "macos_12_0_0")]
-    pub fn mute_process_events(&mut self, process: &AuditToken, events: &[es_event_type_t]) -> Result<(), ReturnError> {
+    pub fn mute_process_events(
+        &mut self,
+        process: &AuditToken,
+        events: &[es_event_type_t],
+    ) -> Result<(), MuteEventsError> {
         if crate::version::is_version_or_more(12, 0, 0) == false {
-            return Err(ReturnError::ApiUnavailable);
+            return Err(ReturnError::ApiUnavailable.into());
+        }
+
+        let unavailable = unavailable_events(events);
+        if unavailable.is_empty() == false {
+            return Err(MuteEventsError::UnavailableEvents(unavailable));
         }
 
         // Safety:
@@ -273,7 +565,9 @@ impl Client<'_> {
                 events.len(),
             )
         }
-        .ok()
+        .ok()?;
+
+        Ok(())
     }
 
     /// Fully unmute the given process.
@@ -288,6 +582,16 @@ impl Client<'_> {
         unsafe { es_unmute_process(self.as_mut(), process.get_raw_ref()) }.ok()
     }
 
+    /// Fully unmute all the given processes.
+    ///
+    /// Every process is unmuted even if unmuting one of them fails, the first failure is returned
+    /// with the token it happened for. An empty slice does nothing.
+    ///
+    /// See [`Client::unmute_process()`].
+    pub fn unmute_processes(&mut self, processes: &[AuditToken]) -> Result<(), MuteProcessesError> {
+        for_each_process(processes, |process| self.unmute_process(process))
+    }
+
     /// Unmute only some events for the given process.
     ///
     /// See [`es_unmute_process_events`].
@@ -448,6 +752,12 @@ impl Client<'_> {
     /// if it has one or add it itself if it does not (in which case there will be an allocation).
     ///
     /// Only available on macOS 12.0+.
+    ///
+    /// Target muting a path for an event type that does not support it is silently a no-op in
+    /// Endpoint Security. To avoid that confusion, this method returns
+    /// [`MutePathEventsError::TargetPathUnsupported`] listing the events of `events` that do not
+    /// [support target path muting][es_event_type_t::supports_target_path_muting], without muting
+    /// anything, when `ty` is a target path type.
     #[doc(alias = "es_mute_path_events")]
     #[cfg(feature = "macos_12_0_0")]
     pub fn mute_path_events(
@@ -455,9 +765,14 @@ impl Client<'_> {
         path: &OsStr,
         ty: es_mute_path_type_t,
         events: &[es_event_type_t],
-    ) -> Result<(), ReturnError> {
+    ) -> Result<(), MutePathEventsError> {
         if crate::version::is_version_or_more(12, 0, 0) == false {
-            return Err(ReturnError::ApiUnavailable);
+            return Err(ReturnError::ApiUnavailable.into());
+        }
+
+        let unsupported = target_path_unsupported_events(ty, events);
+        if unsupported.is_empty() == false {
+            return Err(MutePathEventsError::TargetPathUnsupported(unsupported));
         }
 
         let cow = convert_byte_slice_to_cow_cstr(path.as_bytes());
@@ -474,7 +789,8 @@ impl Client<'_> {
                 events.len(),
             )
         }
-        .ok()
+        .ok()?;
+        Ok(())
     }
 
     /// Unmute all paths for all events types.
@@ -621,6 +937,59 @@ impl Client<'_> {
         Ok(transformed)
     }
 
+    /// `true` if `path` is muted as the executable of the process performing the action, for
+    /// `event` or for all events when `event` is `None`.
+    ///
+    /// The rules are fetched with [`Self::muted_paths_events()`] and matched with
+    /// [`MutedPath::matches_path()`]: literal rules must be equal to `path`, prefix rules must be
+    /// a byte prefix of it. Rules with no events mute all events.
+    ///
+    /// Target path rules and [inverted muting][Self::invert_muting] are not taken into account,
+    /// see [`Self::explain_muting()`] for them.
+    ///
+    /// Only available on macOS 12.0+.
+    #[cfg(feature = "macos_12_0_0")]
+    pub fn is_path_muted(&mut self, path: &OsStr, event: Option<es_event_type_t>) -> Result<bool, ReturnError> {
+        let rules = self.muted_paths_events()?;
+        Ok(crate::mute::is_path_muted_by(&rules, path, event))
+    }
+
+    /// Explain whether an event of type `event_type` involving `path` would be suppressed by the
+    /// current muting rules of the client, following the decision flowchart documented in
+    /// [`es_invert_muting()`].
+    ///
+    /// `path` is checked both as the executable of the process performing the action and as the
+    /// target of the event. Process muting (by audit token) is not part of the explanation since
+    /// it does not depend on paths, see [`Client::muted_processes_events()`] for it.
+    ///
+    /// Only available on macOS 13.0+.
+    #[cfg(feature = "macos_13_0_0")]
+    pub fn explain_muting(
+        &mut self,
+        path: &OsStr,
+        event_type: es_event_type_t,
+    ) -> Result<MutingExplanation, ReturnError> {
+        let is_inverted = |client: &mut Self, ty| match client.muting_inverted(ty) {
+            Ok(inverted) => Ok(inverted == MuteInvertedType::Inverted),
+            Err(MuteTypeError::ApiUnavailable) => Err(ReturnError::ApiUnavailable),
+            Err(_) => Err(ReturnError::Error),
+        };
+        let path_inverted = is_inverted(self, es_mute_inversion_type_t::ES_MUTE_INVERSION_TYPE_PATH)?;
+        let target_inverted = is_inverted(
+            self,
+            es_mute_inversion_type_t::ES_MUTE_INVERSION_TYPE_TARGET_PATH,
+        )?;
+
+        let rules = self.muted_paths_events()?;
+        Ok(crate::mute::explain_muting_by(
+            &rules,
+            path,
+            event_type,
+            path_inverted,
+            target_inverted,
+        ))
+    }
+
     /// Invert the mute state of a given mute dimension.
     ///
     /// See [`es_invert_muting()`]
@@ -665,12 +1034,40 @@ impl Client<'_> {
         unsafe { es_clear_cache(self.as_mut()) }.ok()
     }
 
+    /// Replace the subscriptions and muted paths of the client, without recreating it.
+    ///
+    /// Creating a client clears the caches of all clients in the system (see [`Client::new()`]),
+    /// this method should be preferred to recreating a client when the configuration changes.
+    ///
+    /// The subscriptions are replaced with [`Client::resubscribe()`], so that no event of `events`
+    /// is missed in between. All muted paths are then unmuted before muting `muted_paths`.
+    /// Processes muted with [`Client::mute_process()`] are kept as is.
+    ///
+    /// On error, the client can be left partially reconfigured.
+    pub fn reconfigure(
+        &mut self,
+        events: &[es_event_type_t],
+        muted_paths: &[(&OsStr, es_mute_path_type_t)],
+    ) -> Result<(), ReconfigureError> {
+        self.resubscribe(events).map_err(ReconfigureError::Subscribe)?;
+
+        self.unmute_all_paths().map_err(ReconfigureError::MutePath)?;
+        for &(path, ty) in muted_paths {
+            self.mute_path(path, ty).map_err(ReconfigureError::MutePath)?;
+        }
+
+        Ok(())
+    }
+
     /// Delete a client and returns the result, whereas [`Drop`] ignores it.
     ///
     /// See [`es_delete_client()`].
     #[doc(alias = "es_delete_client")]
     #[inline(always)]
     pub fn delete(mut self) -> Result<(), ReturnError> {
+        // Stop the watchdog before the client becomes invalid
+        drop(self.liveness_check.take());
+
         // Safety:
         // - We took ownership, this will only run once
         // - By construction our client is valid
@@ -678,10 +1075,271 @@ impl Client<'_> {
         let res = unsafe { es_delete_client(self.as_mut()) }.ok();
 
         // Avoid the double free since `self` would normally be dropped here
-        std::mem::forget(self);
+        let mut this = ManuallyDrop::new(self);
+        // Safety: `this` is not used anymore
+        unsafe { this.release_without_deleting() };
 
         res
     }
+
+    /// Drop the fields of the client without deleting the client itself, for clients that must
+    /// not run [`Drop`] (kept in a [`ManuallyDrop`]).
+    ///
+    /// # Safety
+    ///
+    /// The client must not be used afterwards, not even dropped.
+    unsafe fn release_without_deleting(&mut self) {
+        // Safety: the fields are valid and the caller guarantees they are not used again
+        unsafe {
+            std::ptr::drop_in_place(&mut self.liveness_check);
+            std::ptr::drop_in_place(&mut self.unknown_events);
+            std::ptr::drop_in_place(&mut self.handler_thread_id);
+        }
+    }
+}
+
+/// Error returned by [`Client::subscribe()`] and its variants.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SubscribeError {
+    /// A call to Endpoint Security failed
+    Return(ReturnError),
+    /// The call succeeded but these requested events are not part of the active subscriptions
+    Missing(Vec<es_event_type_t>),
+    /// These event types were introduced in a macOS version newer than the runtime one, nothing
+    /// was subscribed to
+    UnavailableEvents(Vec<es_event_type_t>),
+}
+
+impl From<ReturnError> for SubscribeError {
+    #[inline(always)]
+    fn from(err: ReturnError) -> Self {
+        Self::Return(err)
+    }
+}
+
+impl std::error::Error for SubscribeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Return(err) => Some(err),
+            Self::Missing(_) | Self::UnavailableEvents(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SubscribeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Return(err) => write!(f, "Subscription failed: {err}"),
+            Self::Missing(events) => {
+                f.write_str("Events missing from the active subscriptions:")?;
+                for ev in events {
+                    write!(f, " {ev}")?;
+                }
+                Ok(())
+            },
+            Self::UnavailableEvents(events) => {
+                f.write_str("Events not available on the running macOS version:")?;
+                for ev in events {
+                    write!(f, " {ev}")?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Error returned by [`Client::mute_process_events()`].
+#[cfg(feature = "macos_12_0_0")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MuteEventsError {
+    /// A call to Endpoint Security failed
+    Return(ReturnError),
+    /// These event types were introduced in a macOS version newer than the runtime one, nothing
+    /// was muted
+    UnavailableEvents(Vec<es_event_type_t>),
+}
+
+#[cfg(feature = "macos_12_0_0")]
+impl From<ReturnError> for MuteEventsError {
+    #[inline(always)]
+    fn from(err: ReturnError) -> Self {
+        Self::Return(err)
+    }
+}
+
+#[cfg(feature = "macos_12_0_0")]
+impl std::error::Error for MuteEventsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Return(err) => Some(err),
+            Self::UnavailableEvents(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "macos_12_0_0")]
+impl std::fmt::Display for MuteEventsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Return(err) => write!(f, "Muting failed: {err}"),
+            Self::UnavailableEvents(events) => {
+                f.write_str("Events not available on the running macOS version:")?;
+                for ev in events {
+                    write!(f, " {ev}")?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Error returned by [`Client::mute_path_events()`].
+#[cfg(feature = "macos_12_0_0")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MutePathEventsError {
+    /// A call to Endpoint Security failed
+    Return(ReturnError),
+    /// The path type is a target one and these event types do not support target path muting,
+    /// nothing was muted
+    TargetPathUnsupported(Vec<es_event_type_t>),
+}
+
+#[cfg(feature = "macos_12_0_0")]
+impl From<ReturnError> for MutePathEventsError {
+    #[inline(always)]
+    fn from(err: ReturnError) -> Self {
+        Self::Return(err)
+    }
+}
+
+#[cfg(feature = "macos_12_0_0")]
+impl std::error::Error for MutePathEventsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Return(err) => Some(err),
+            Self::TargetPathUnsupported(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "macos_12_0_0")]
+impl std::fmt::Display for MutePathEventsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Return(err) => write!(f, "Muting failed: {err}"),
+            Self::TargetPathUnsupported(events) => {
+                f.write_str("Events not supporting target path muting:")?;
+                for ev in events {
+                    write!(f, " {ev}")?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Error returned by [`Client::reconfigure()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ReconfigureError {
+    /// Replacing the subscriptions failed, the muted paths were not changed
+    Subscribe(SubscribeError),
+    /// Unmuting the previous paths or muting one of the new ones failed
+    MutePath(ReturnError),
+}
+
+impl std::error::Error for ReconfigureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Subscribe(err) => Some(err),
+            Self::MutePath(err) => Some(err),
+        }
+    }
+}
+
+impl std::fmt::Display for ReconfigureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Subscribe(err) => write!(f, "Subscribing failed: {err}"),
+            Self::MutePath(err) => write!(f, "Muting a path failed: {err}"),
+        }
+    }
+}
+
+/// Error returned by [`Client::respond()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RespondDecisionError {
+    /// Responding failed
+    Respond(RespondError),
+    /// The message does not expect a response, nothing was sent
+    NotAuth,
+    /// The decision cannot be used for the response expected by the message, nothing was sent
+    Mismatch {
+        /// Response expected by the message
+        expected: ExpectedResponseType,
+        /// Decision given to [`Client::respond()`]
+        decision: AuthDecision,
+    },
+}
+
+impl From<RespondError> for RespondDecisionError {
+    #[inline(always)]
+    fn from(err: RespondError) -> Self {
+        Self::Respond(err)
+    }
+}
+
+impl std::error::Error for RespondDecisionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Respond(err) => Some(err),
+            Self::NotAuth | Self::Mismatch { .. } => None,
+        }
+    }
+}
+
+impl std::fmt::Display for RespondDecisionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Respond(err) => write!(f, "Response failed: {err}"),
+            Self::NotAuth => f.write_str("Message does not expect a response"),
+            Self::Mismatch { expected, decision } => {
+                write!(
+                    f,
+                    "Decision {decision:?} cannot be used for a {expected:?} response"
+                )
+            },
+        }
+    }
+}
+
+/// Error returned by [`Client::mute_processes()`] and [`Client::unmute_processes()`], for the
+/// first process that failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MuteProcessesError {
+    /// Index of the process in the given slice
+    pub index: usize,
+    /// Audit token of the process
+    pub process: AuditToken,
+    /// Error returned by Endpoint Security for the process
+    pub error: ReturnError,
+}
+
+impl std::error::Error for MuteProcessesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl std::fmt::Display for MuteProcessesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Failed for process {} at index {}: {}",
+            self.process.pid(),
+            self.index,
+            self.error
+        )
+    }
 }
 
 /// Private helper methods
@@ -691,6 +1349,109 @@ impl Client<'_> {
         // Safety: `inner` is valid by construction
         unsafe { self.inner.as_mut() }
     }
+
+    /// Subscribe the client to `events`, without checking they are available.
+    ///
+    /// # Panics
+    ///
+    /// `events` can contain at most `u32::MAX` elements. This is a limitation of Apple's API.
+    fn subscribe_unchecked(&mut self, events: &[es_event_type_t]) -> Result<(), ReturnError> {
+        assert!(events.len() < u32::MAX as usize);
+
+        // Safety:
+        // - `self.as_mut()` is a valid client by construction
+        // - `events` is a slice for which we have checked the length, `.as_ptr()` and `.len() as
+        //   u32` are both valid
+        // - the result is checked with `.ok()`
+        unsafe { es_subscribe(self.as_mut(), events.as_ptr(), events.len() as u32) }.ok()
+    }
+}
+
+/// macOS version in which `event` was introduced, `None` if it is unknown to this crate.
+///
+/// Raw values are used so that this works whatever the enabled features.
+fn introduced_in(event: es_event_type_t) -> Option<(u64, u64, u64)> {
+    match event.0 {
+        0..=49 => Some((10, 15, 0)),
+        50..=82 => Some((10, 15, 1)),
+        83..=87 => Some((10, 15, 4)),
+        88..=99 => Some((11, 0, 0)),
+        100..=102 => Some((11, 3, 0)),
+        103..=110 => Some((12, 0, 0)),
+        111..=125 => Some((13, 0, 0)),
+        126..=145 => Some((14, 0, 0)),
+        146 => Some((15, 0, 0)),
+        _ => None,
+    }
+}
+
+/// `true` if `event` exists on the [runtime version][crate::version::set_runtime_version] of macOS.
+///
+/// Events unknown to this crate are considered available: they can only come from a newer macOS
+/// that this crate does not know about, so Endpoint Security is left to judge them.
+fn is_event_available(event: es_event_type_t) -> bool {
+    match introduced_in(event) {
+        Some((major, minor, patch)) => crate::version::is_version_or_more(major, minor, patch),
+        None => true,
+    }
+}
+
+/// Events of `events` that are not [available][is_event_available], sorted and deduplicated.
+fn unavailable_events(events: &[es_event_type_t]) -> Vec<es_event_type_t> {
+    let mut unavailable: Vec<_> = events
+        .iter()
+        .copied()
+        .filter(|&ev| is_event_available(ev) == false)
+        .collect();
+    sort_dedup(&mut unavailable);
+    unavailable
+}
+
+/// Events of `events` that do not [support target path muting][es_event_type_t::supports_target_path_muting],
+/// sorted and deduplicated, when `ty` is a target path type. Empty for other path types.
+#[cfg(feature = "macos_12_0_0")]
+fn target_path_unsupported_events(ty: es_mute_path_type_t, events: &[es_event_type_t]) -> Vec<es_event_type_t> {
+    // Target path types only exist from macOS 13, their raw values are used to avoid a feature
+    // gate: `ES_MUTE_PATH_TYPE_TARGET_PREFIX` and `ES_MUTE_PATH_TYPE_TARGET_LITERAL`
+    if matches!(ty.0, 2 | 3) == false {
+        return Vec::new();
+    }
+
+    let mut unsupported: Vec<_> = events
+        .iter()
+        .copied()
+        .filter(|ev| ev.supports_target_path_muting() == false)
+        .collect();
+    sort_dedup(&mut unsupported);
+    unsupported
+}
+
+/// Call `f` on every process of `processes`, returning the first error.
+fn for_each_process(
+    processes: &[AuditToken],
+    mut f: impl FnMut(&AuditToken) -> Result<(), ReturnError>,
+) -> Result<(), MuteProcessesError> {
+    let mut first_err = None;
+    for (index, process) in processes.iter().enumerate() {
+        if let Err(error) = f(process) {
+            first_err.get_or_insert(MuteProcessesError {
+                index,
+                process: *process,
+                error,
+            });
+        }
+    }
+
+    match first_err {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Sort `events` by raw value and remove the duplicates.
+fn sort_dedup(events: &mut Vec<es_event_type_t>) {
+    events.sort_unstable_by_key(|ev| ev.0);
+    events.dedup();
 }
 
 impl Drop for Client<'_> {
@@ -699,8 +1460,220 @@ impl Drop for Client<'_> {
     #[doc(alias = "es_delete_client")]
     #[inline(always)]
     fn drop(&mut self) {
+        // Stop the watchdog before the client becomes invalid
+        drop(self.liveness_check.take());
+
         // Safety: Our client is non-null and valid by construction, and we are in `Drop` which will
         // only run once so no double free.
         let _ = unsafe { es_delete_client(self.as_mut()) };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::{set_runtime_version, TEST_LOCK};
+
+    #[test]
+    fn test_release_without_deleting() {
+        let unknown_events = Arc::new(AtomicU64::new(0));
+        let handler_thread_id = Arc::new(AtomicU64::new(0));
+
+        let mut client = ManuallyDrop::new(Client {
+            // Never dereferenced: the client is not deleted
+            inner: NonNull::dangling(),
+            block_lifetime: PhantomData,
+            liveness_check: None,
+            unknown_events: Arc::clone(&unknown_events),
+            handler_thread_id: Arc::clone(&handler_thread_id),
+        });
+        assert_eq!(Arc::strong_count(&unknown_events), 2);
+        assert_eq!(Arc::strong_count(&handler_thread_id), 2);
+
+        // Safety: `client` is not used anymore
+        unsafe { client.release_without_deleting() };
+        assert_eq!(Arc::strong_count(&unknown_events), 1);
+        assert_eq!(Arc::strong_count(&handler_thread_id), 1);
+    }
+
+    #[test]
+    fn test_current_thread_id() {
+        let id = current_thread_id();
+        assert_ne!(id, 0);
+        assert_eq!(current_thread_id(), id);
+
+        let other = std::thread::spawn(current_thread_id).join().unwrap();
+        assert_ne!(other, 0);
+        assert_ne!(other, id);
+    }
+
+    #[test]
+    fn test_introduced_in() {
+        assert_eq!(introduced_in(es_event_type_t(0)), Some((10, 15, 0)));
+        assert_eq!(introduced_in(es_event_type_t(49)), Some((10, 15, 0)));
+        assert_eq!(introduced_in(es_event_type_t(50)), Some((10, 15, 1)));
+        assert_eq!(introduced_in(es_event_type_t(102)), Some((11, 3, 0)));
+        assert_eq!(introduced_in(es_event_type_t(145)), Some((14, 0, 0)));
+        assert_eq!(introduced_in(es_event_type_t(146)), Some((15, 0, 0)));
+        assert_eq!(introduced_in(es_event_type_t(147)), None);
+
+        // Every event known at compile time has a version
+        for raw in 0..es_event_type_t::ES_EVENT_TYPE_LAST.0 {
+            assert!(introduced_in(es_event_type_t(raw)).is_some());
+        }
+    }
+
+    #[test]
+    fn test_is_event_available() {
+        let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let events = [
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXEC,
+            es_event_type_t(51),  // NOTIFY_CHDIR, 10.15.1
+            es_event_type_t(103), // NOTIFY_SETUID, 12.0.0
+            es_event_type_t(131), // NOTIFY_SUDO, 14.0.0
+            es_event_type_t(1000),
+        ];
+        let available = || events.iter().filter(|&&ev| is_event_available(ev)).count();
+
+        set_runtime_version(10, 15, 0);
+        assert_eq!(available(), 2);
+        assert!(is_event_available(es_event_type_t(51)) == false);
+
+        set_runtime_version(12, 0, 0);
+        assert_eq!(available(), 4);
+        assert!(is_event_available(es_event_type_t(131)) == false);
+
+        set_runtime_version(14, 0, 0);
+        assert_eq!(available(), 5);
+    }
+
+    #[test]
+    fn test_for_each_process() {
+        let token = |pid: u32| {
+            let mut token = AuditToken(audit_token_t { val: [0; 8] });
+            token.0.val[5] = pid;
+            token
+        };
+        let processes = [token(1), token(2), token(3), token(4)];
+
+        // Empty slice is a no-op success
+        assert_eq!(for_each_process(&[], |_| unreachable!()), Ok(()));
+
+        let mut visited = Vec::new();
+        let res = for_each_process(&processes, |p| {
+            visited.push(p.pid());
+            Ok(())
+        });
+        assert_eq!(res, Ok(()));
+        assert_eq!(visited, [1, 2, 3, 4]);
+
+        // All processes are visited, the first failure is reported
+        visited.clear();
+        let res = for_each_process(&processes, |p| {
+            visited.push(p.pid());
+            if p.pid() % 2 == 0 {
+                Err(ReturnError::ApiUnavailable)
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(visited, [1, 2, 3, 4]);
+        assert_eq!(
+            res,
+            Err(MuteProcessesError {
+                index: 1,
+                process: processes[1],
+                error: ReturnError::ApiUnavailable,
+            })
+        );
+    }
+
+    #[test]
+    fn test_sort_dedup() {
+        let mut events = vec![
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_OPEN,
+            es_event_type_t::ES_EVENT_TYPE_AUTH_EXEC,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXIT,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_OPEN,
+            es_event_type_t::ES_EVENT_TYPE_AUTH_EXEC,
+        ];
+        sort_dedup(&mut events);
+        assert_eq!(events, [
+            es_event_type_t::ES_EVENT_TYPE_AUTH_EXEC,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_OPEN,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXIT,
+        ]);
+
+        let mut empty = Vec::new();
+        sort_dedup(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "macos_12_0_0")]
+    fn test_target_path_unsupported_events() {
+        let events = [
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_FORK,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_OPEN,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXIT,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_FORK,
+        ];
+        let unsupported = [
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_FORK,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXIT,
+        ];
+
+        // Target prefix and target literal
+        for ty in [es_mute_path_type_t(2), es_mute_path_type_t(3)] {
+            assert_eq!(target_path_unsupported_events(ty, &events), unsupported);
+            assert!(target_path_unsupported_events(ty, &[es_event_type_t::ES_EVENT_TYPE_AUTH_OPEN]).is_empty());
+        }
+
+        // Process path types accept all events
+        assert!(target_path_unsupported_events(es_mute_path_type_t::ES_MUTE_PATH_TYPE_PREFIX, &events).is_empty());
+        assert!(target_path_unsupported_events(es_mute_path_type_t::ES_MUTE_PATH_TYPE_LITERAL, &events).is_empty());
+
+        let err = MutePathEventsError::TargetPathUnsupported(unsupported.to_vec());
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Events not supporting target path muting: {} {}",
+                unsupported[0], unsupported[1]
+            )
+        );
+    }
+
+    #[test]
+    fn test_unavailable_events() {
+        let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let events = [
+            es_event_type_t(131), // NOTIFY_SUDO, 14.0.0
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_OPEN,
+            es_event_type_t(111), // NOTIFY_AUTHENTICATION, 13.0.0
+            es_event_type_t(103), // NOTIFY_SETUID, 12.0.0
+            es_event_type_t(131),
+        ];
+
+        set_runtime_version(12, 0, 0);
+        assert_eq!(unavailable_events(&events), [
+            es_event_type_t(111),
+            es_event_type_t(131)
+        ]);
+
+        set_runtime_version(13, 5, 0);
+        assert_eq!(unavailable_events(&events), [es_event_type_t(131)]);
+
+        set_runtime_version(14, 0, 0);
+        assert!(unavailable_events(&events).is_empty());
+
+        let err = SubscribeError::UnavailableEvents(vec![es_event_type_t(111), es_event_type_t(131)]);
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Events not available on the running macOS version: {} {}",
+                es_event_type_t(111),
+                es_event_type_t(131)
+            )
+        );
+    }
+}
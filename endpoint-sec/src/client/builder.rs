@@ -0,0 +1,326 @@
+//! Expose a builder for [`Client`]: [`ClientBuilder`]
+
+use std::ffi::OsString;
+use std::panic::RefUnwindSafe;
+use std::ptr::NonNull;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use endpoint_sec_sys::*;
+
+use super::{Client, SubscribeError};
+use crate::{Event, ExpectedResponseType, Message};
+
+/// Handler of a [`Client`], boxed to avoid a type parameter on the builder
+type BoxedHandler<'b> = Box<dyn Fn(&mut Client<'_>, Message) + RefUnwindSafe + 'b>;
+
+/// Pre-filter of a [`Client`], see [`ClientBuilder::pre_filter()`]
+type BoxedPreFilter<'b> = Box<dyn Fn(&Message) -> bool + RefUnwindSafe + 'b>;
+
+/// Callback of the liveness check, see [`ClientBuilder::liveness_check()`]
+type BoxedOnDead = Box<dyn FnOnce() + Send + 'static>;
+
+/// Builder to configure a [`Client`] before creating it.
+///
+/// See [`Client::new()`] for the requirements that must be met for the creation to succeed.
+///
+/// **Note**: each client creation clears the result caches of all clients in the system. To change
+/// the configuration of an existing client, prefer [`Client::reconfigure()`] to building a new one.
+pub struct ClientBuilder<'b> {
+    /// Handler for the messages received by the client
+    handler: BoxedHandler<'b>,
+    /// Interval and callback of the liveness check, if enabled
+    liveness_check: Option<(Duration, BoxedOnDead)>,
+    /// Automatically allow `AUTH` events unknown to this crate
+    auto_allow_unknown_auth: bool,
+    /// Called before the handler to drop messages early
+    pre_filter: Option<BoxedPreFilter<'b>>,
+    /// Events to subscribe to once the client is created
+    subscriptions: Vec<es_event_type_t>,
+    /// Paths to mute once the client is created
+    muted_paths: Vec<(OsString, es_mute_path_type_t)>,
+}
+
+impl<'b> ClientBuilder<'b> {
+    /// Starts configuring a [`Client`] that will call `handler` for each message it receives.
+    pub fn new<F>(handler: F) -> Self
+    where
+        F: Fn(&mut Client<'_>, Message) + RefUnwindSafe + 'b,
+    {
+        Self {
+            handler: Box::new(handler),
+            liveness_check: None,
+            auto_allow_unknown_auth: false,
+            pre_filter: None,
+            subscriptions: Vec::new(),
+            muted_paths: Vec::new(),
+        }
+    }
+
+    /// Subscribe the client to `events` when it is built. Can be called several times to add more
+    /// events.
+    ///
+    /// The subscriptions are applied by [`ClientBuilder::build()`] with [`Client::subscribe()`],
+    /// after the paths given to [`ClientBuilder::mute_path()`] have been muted.
+    pub fn subscribe(mut self, events: &[es_event_type_t]) -> Self {
+        self.subscriptions.extend_from_slice(events);
+        self
+    }
+
+    /// Mute `path` with `ty` when the client is built, before any subscription is made so that no
+    /// event for the path is received in between. Can be called several times to mute more paths.
+    ///
+    /// See [`Client::mute_path()`].
+    pub fn mute_path(mut self, path: OsString, ty: es_mute_path_type_t) -> Self {
+        self.muted_paths.push((path, ty));
+        self
+    }
+
+    /// Periodically check that the client is still alive, calling `on_dead` once if it appears not
+    /// to be.
+    ///
+    /// If macOS kills the client (for example because of a missed `AUTH` deadline), the handler
+    /// simply stops being called: Endpoint Security does not notify the app. When this option is
+    /// set, a watchdog thread calls [`es_subscriptions()`] every `interval` and invokes `on_dead`
+    /// the first time it fails, so that the app can delete and recreate its client.
+    ///
+    /// **This is a heuristic**: a failing call is the only observable sign of a dead client, and
+    /// a client can miss messages without the call failing. The watchdog is stopped when the client
+    /// is dropped or [deleted][Client::delete], and after `on_dead` has been called.
+    pub fn liveness_check<F>(mut self, interval: Duration, on_dead: F) -> Self
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.liveness_check = Some((interval, Box::new(on_dead)));
+        self
+    }
+
+    /// Automatically allow `AUTH` events that are unknown to this crate (disabled by default).
+    ///
+    /// When running on a newer macOS than the one this crate (or its enabled features) knows
+    /// about, Endpoint Security can send events for which [`Message::event()`] returns
+    /// [`Event::Unknown`]. A handler that does not know how to respond to them would get the client
+    /// killed when their deadline is reached. `AUTH` messages for which it returns `None` are
+    /// allowed too.
+    ///
+    /// With this option, such `AUTH` messages are allowed **without caching** before the handler
+    /// is called. The handler still receives them, to log them for example, but must not respond
+    /// to them again. They are counted in [`Client::unknown_events_count()`] like all unknown
+    /// events.
+    ///
+    /// Allowing unknown events is a policy decision: it favors availability over security.
+    pub fn auto_allow_unknown_auth(mut self, enabled: bool) -> Self {
+        self.auto_allow_unknown_auth = enabled;
+        self
+    }
+
+    /// Call `filter` for each message before the handler, to drop the messages the app is not
+    /// interested in as early as possible.
+    ///
+    /// When `filter` returns `false`, the handler is not called and `AUTH` messages are
+    /// automatically **allowed** (without caching). `filter` should be cheap: it runs for every
+    /// message, before any other processing. A typical filter checks the audit token of
+    /// [`Message::process()`] against a set of processes ignored by policy.
+    pub fn pre_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&Message) -> bool + RefUnwindSafe + 'b,
+    {
+        self.pre_filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Creates the [`Client`] with the configuration of the builder, then mutes the paths and
+    /// applies the subscriptions it was given.
+    ///
+    /// If muting or subscribing fails, the client is deleted before returning the error: no
+    /// half-configured client is left behind.
+    ///
+    /// See [`Client::new()`].
+    pub fn build(self) -> Result<Client<'b>, ClientBuildError> {
+        let Self {
+            handler,
+            liveness_check,
+            auto_allow_unknown_auth,
+            pre_filter,
+            subscriptions,
+            muted_paths,
+        } = self;
+
+        let mut client = if auto_allow_unknown_auth || pre_filter.is_some() {
+            Client::new(move |client, message| {
+                let is_auth = message.action_type() == es_action_type_t::ES_ACTION_TYPE_AUTH;
+
+                if let Some(ref filter) = pre_filter {
+                    if filter(&message) == false {
+                        if is_auth {
+                            allow(client, &message);
+                        }
+                        return;
+                    }
+                }
+
+                if auto_allow_unknown_auth && is_auth && matches!(message.event(), None | Some(Event::Unknown { .. })) {
+                    allow(client, &message);
+                }
+
+                handler(client, message);
+            })?
+        } else {
+            Client::new(handler)?
+        };
+
+        for (path, ty) in &muted_paths {
+            if let Err(err) = client.mute_path(path, *ty) {
+                let _ = client.delete();
+                return Err(ClientBuildError::MutePath(err));
+            }
+        }
+
+        if subscriptions.is_empty() == false {
+            if let Err(err) = client.subscribe(&subscriptions) {
+                let _ = client.delete();
+                return Err(ClientBuildError::Subscribe(err));
+            }
+        }
+
+        if let Some((interval, on_dead)) = liveness_check {
+            client.liveness_check = Some(LivenessCheck::start(client.inner, interval, on_dead));
+        }
+
+        Ok(client)
+    }
+}
+
+/// Error returned by [`ClientBuilder::build()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClientBuildError {
+    /// Creating the client failed
+    New(NewClientError),
+    /// Muting one of the paths failed, the client was deleted
+    MutePath(ReturnError),
+    /// Subscribing failed, the client was deleted
+    Subscribe(SubscribeError),
+}
+
+impl From<NewClientError> for ClientBuildError {
+    #[inline(always)]
+    fn from(err: NewClientError) -> Self {
+        Self::New(err)
+    }
+}
+
+impl std::error::Error for ClientBuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::New(err) => Some(err),
+            Self::MutePath(err) => Some(err),
+            Self::Subscribe(err) => Some(err),
+        }
+    }
+}
+
+impl std::fmt::Display for ClientBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::New(err) => write!(f, "Client creation failed: {err}"),
+            Self::MutePath(err) => write!(f, "Muting a path failed: {err}"),
+            Self::Subscribe(err) => write!(f, "Subscribing failed: {err}"),
+        }
+    }
+}
+
+/// Pointer to a client, sent to the watchdog thread
+struct WatchedClient(NonNull<es_client_t>);
+
+// Safety: the ES functions used by the watchdog can be called from any thread, and the thread is
+// stopped before the client is deleted
+unsafe impl Send for WatchedClient {}
+
+/// Watchdog thread of [`ClientBuilder::liveness_check()`]
+pub(super) struct LivenessCheck {
+    /// Set to `true` to ask the thread to stop, with the condition variable used to wake it up
+    stop: Arc<(Mutex<bool>, Condvar)>,
+    /// Handle used to wait for the thread to stop
+    thread: Option<JoinHandle<()>>,
+}
+
+impl LivenessCheck {
+    /// Start the watchdog thread for `client`.
+    ///
+    /// The returned value must be dropped before `client` is deleted.
+    fn start(client: NonNull<es_client_t>, interval: Duration, on_dead: BoxedOnDead) -> Self {
+        let stop = Arc::new((Mutex::new(false), Condvar::new()));
+        let thread_stop = Arc::clone(&stop);
+        let client = WatchedClient(client);
+
+        let thread = std::thread::spawn(move || {
+            let client = client;
+            let (lock, cvar) = &*thread_stop;
+
+            loop {
+                let guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+                let (stopped, _) = cvar
+                    .wait_timeout_while(guard, interval, |stopped| *stopped == false)
+                    .unwrap_or_else(|e| e.into_inner());
+                if *stopped {
+                    return;
+                }
+                drop(stopped);
+
+                let mut count = 0;
+                let mut data = std::ptr::null_mut();
+
+                // Safety:
+                // - `client.0` is valid: the client is deleted only after this thread is stopped
+                // - `count` and `data` are valid out parameters
+                // - the result is checked below
+                let res = unsafe { es_subscriptions(client.0.as_ptr(), &mut count, &mut data) };
+
+                if data.is_null() == false {
+                    // Safety: Apple says in its docs we have ownership of the memory
+                    unsafe { libc::free(data.cast()) };
+                }
+
+                if res != es_return_t::ES_RETURN_SUCCESS {
+                    on_dead();
+                    return;
+                }
+            }
+        });
+
+        Self {
+            stop,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for LivenessCheck {
+    fn drop(&mut self) {
+        let (lock, cvar) = &*self.stop;
+        *lock.lock().unwrap_or_else(|e| e.into_inner()) = true;
+        cvar.notify_all();
+
+        if let Some(thread) = self.thread.take() {
+            // A panic in `on_dead` has already been reported by the thread itself
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Allow an `AUTH` message on behalf of the handler, without caching the result
+fn allow(client: &mut Client<'_>, message: &Message) {
+    let _ = match message.event().and_then(|ev| ev.expected_response_type()) {
+        Some(ExpectedResponseType::Auth) => {
+            client.respond_auth_result(message, es_auth_result_t::ES_AUTH_RESULT_ALLOW, false)
+        },
+        Some(ExpectedResponseType::Flags { .. }) => client.respond_flags_result(message, u32::MAX, false),
+        // The event is unknown so the expected response type is unknown too: try the most common
+        // one first
+        None => match client.respond_auth_result(message, es_auth_result_t::ES_AUTH_RESULT_ALLOW, false) {
+            Err(RespondError::EventType) => client.respond_flags_result(message, u32::MAX, false),
+            res => res,
+        },
+    };
+}
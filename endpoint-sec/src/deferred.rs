@@ -0,0 +1,111 @@
+//! Respond to `AUTH` messages outside of the handler: [`DeferredAuth`]
+//!
+//! To respond to an `AUTH` message after the handler returned, Endpoint Security asks for the
+//! message to be retained until the response is sent. [`Message::into_deferred()`] packages the
+//! retained message with its deadline in a [`DeferredAuth`], which is consumed by the response.
+
+use std::time::Instant;
+
+use endpoint_sec_sys::{es_action_type_t, es_auth_result_t, RespondError};
+
+use crate::{Client, Message, TimeError};
+
+/// An `AUTH` message waiting for a response, created by [`Message::into_deferred()`].
+///
+/// The message is retained as long as the `DeferredAuth` lives, it can be moved out of the handler
+/// to respond later, for example after some asynchronous analysis. Responding consumes the value
+/// so that a message cannot be responded to twice through it.
+///
+/// Dropping a `DeferredAuth` without responding does **not** respond on its behalf: the response
+/// must still be sent before the [deadline][Self::deadline] else the client will be killed.
+#[must_use = "the message must be responded to before its deadline"]
+pub struct DeferredAuth {
+    /// Retained message
+    message: Message,
+    /// Deadline of `message`, computed once
+    deadline: Result<Instant, TimeError>,
+}
+
+static_assertions::assert_impl_all!(DeferredAuth: Send, Sync);
+
+impl DeferredAuth {
+    /// The message waiting for a response.
+    #[inline(always)]
+    pub fn message(&self) -> &Message {
+        &self.message
+    }
+
+    /// Time before which the message **must** be responded to, see [`Message::deadline()`].
+    #[inline(always)]
+    pub fn deadline(&self) -> Result<Instant, TimeError> {
+        self.deadline
+    }
+
+    /// Respond to the message with [`Client::respond_auth_result()`].
+    #[inline]
+    pub fn respond_auth(
+        self,
+        client: &mut Client<'_>,
+        result: es_auth_result_t,
+        cache: bool,
+    ) -> Result<(), RespondError> {
+        client.respond_auth_result(&self.message, result, cache)
+    }
+
+    /// Respond to the message with [`Client::respond_flags_result()`].
+    #[inline]
+    pub fn respond_flags(
+        self,
+        client: &mut Client<'_>,
+        authorized_flags: u32,
+        cache: bool,
+    ) -> Result<(), RespondError> {
+        client.respond_flags_result(&self.message, authorized_flags, cache)
+    }
+}
+
+impl std::fmt::Debug for DeferredAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeferredAuth")
+            .field("message", &self.message)
+            .field("deadline", &self.deadline)
+            .finish()
+    }
+}
+
+/// Error returned by [`Message::into_deferred()`] for messages that do not expect a response,
+/// giving the message back.
+#[derive(Debug)]
+pub struct NotAuthMessage(pub Message);
+
+impl std::error::Error for NotAuthMessage {}
+
+impl std::fmt::Display for NotAuthMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Message does not expect a response")
+    }
+}
+
+/// Deferred responses
+impl Message {
+    /// Keep the message to respond to it after the handler returned.
+    ///
+    /// The handler owns its [`Message`], which is already retained: no copy is made.
+    ///
+    /// # Errors
+    ///
+    /// [`NotAuthMessage`] with the message if it is not an `AUTH` one (see
+    /// [`Self::action_type()`]): `NOTIFY` messages cannot be responded to.
+    #[inline]
+    pub fn into_deferred(self) -> Result<DeferredAuth, NotAuthMessage> {
+        if self.action_type() != es_action_type_t::ES_ACTION_TYPE_AUTH {
+            return Err(NotAuthMessage(self));
+        }
+
+        let deadline = self.deadline();
+        Ok(DeferredAuth {
+            message: self,
+            deadline,
+        })
+    }
+}
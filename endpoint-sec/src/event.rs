@@ -1,6 +1,10 @@
 //! Definitions of Endpoint Security events.
 
-use endpoint_sec_sys::{es_event_type_t, es_events_t};
+use std::ffi::OsStr;
+
+use endpoint_sec_sys::{es_action_type_t, es_event_type_t, es_events_t};
+
+use crate::{File, Process};
 
 /// Helper macro to define the whole Event enum at once, avoiding endless repetitions of the CFGs
 macro_rules! define_event_enum {
@@ -28,6 +32,17 @@ macro_rules! define_event_enum {
         pub enum $enum_name<'a> {
             $( $(#[$b_v_doc])* $b_v_name($b_v_inner<'a>), )*
             $( $( #[$v_cfg] $(#[$v_doc])* $v_name($v_inner<'a>), )* )*
+            /// Event of a type unknown to this crate, either because it was introduced in a newer
+            /// version of macOS or because the corresponding feature is not enabled.
+            ///
+            /// Its data cannot be read safely, see [`Message::raw_event_bytes()`][crate::Message::raw_event_bytes]
+            /// to forward it as is.
+            Unknown {
+                /// Raw type of the event
+                event_type: es_event_type_t,
+                /// Action type of the message, tells if the event is an `AUTH` one
+                action_type: es_action_type_t,
+            },
         }
 
         ::static_assertions::assert_impl_all!(Event<'_>: Send);
@@ -37,10 +52,15 @@ macro_rules! define_event_enum {
             ///
             /// # Safety
             ///
-            /// `event_type`, `raw_event` and `version` must be coming from the same [`crate::message::Message`].
+            /// `event_type`, `action_type`, `raw_event` and `version` must be coming from the same
+            /// [`crate::message::Message`].
+            ///
+            /// Unknown event types give [`Self::Unknown`], `None` is only returned when the data of a
+            /// known event type is missing.
             #[inline(always)]
             pub(crate) unsafe fn from_raw_parts(
                 event_type: es_event_type_t,
+                action_type: es_action_type_t,
                 $raw_ev: &'a es_events_t,
                 $version: u32,
             ) -> Option<Self> {
@@ -50,17 +70,57 @@ macro_rules! define_event_enum {
                     match event_type {
                         $( es_event_type_t::$b_v_const => Self::$b_v_name($b_v_inner { $( $b_v_new_name $(: $b_v_new_expr)? ),* }), )*
                         $( $( #[$v_cfg] es_event_type_t::$v_const => Self::$v_name($v_inner { $( $v_new_name $(: $v_new_expr)? ),* }), )* )*
-                        _ => return None,
+                        _ => Self::Unknown { event_type, action_type },
                     }
                 };
                 Some(v)
             }
 
             /// For `Auth` events, returns the type of response to use when allowing or denying.
+            ///
+            /// Always `None` for [`Self::Unknown`] events, even `AUTH` ones: the response they expect
+            /// cannot be known, see [`Self::kind()`].
             pub fn expected_response_type(&self) -> Option<ExpectedResponseType> {
                 match self {
                     $( Self::$b_v_name($b_v_var) => $b_v_expected_resp_type, )*
                     $( $( #[$v_cfg] Self::$v_name($v_var) => $v_expected_resp_type, )* )*
+                    Self::Unknown { .. } => None,
+                }
+            }
+
+            /// Event type of the variant, as given to [`Client::subscribe()`][crate::Client::subscribe].
+            ///
+            /// `AUTH` and `NOTIFY` variants sharing the same data are told apart.
+            #[inline]
+            pub fn event_type(&self) -> es_event_type_t {
+                match self {
+                    $( Self::$b_v_name(_) => es_event_type_t::$b_v_const, )*
+                    $( $( #[$v_cfg] Self::$v_name(_) => es_event_type_t::$v_const, )* )*
+                    Self::Unknown { event_type, .. } => *event_type,
+                }
+            }
+        }
+
+        /// Serialized as an externally tagged enum, the variant index being the raw event type.
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $enum_name<'_> {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                const NAME: &str = ::core::stringify!($enum_name);
+                match self {
+                    $( Self::$b_v_name(ev) => serializer.serialize_newtype_variant(
+                        NAME, es_event_type_t::$b_v_const.0, ::core::stringify!($b_v_name), ev,
+                    ), )*
+                    $( $( #[$v_cfg] Self::$v_name(ev) => serializer.serialize_newtype_variant(
+                        NAME, es_event_type_t::$v_const.0, ::core::stringify!($v_name), ev,
+                    ), )* )*
+                    Self::Unknown { event_type, action_type } => {
+                        use ::serde::ser::SerializeStructVariant;
+
+                        let mut s = serializer.serialize_struct_variant(NAME, event_type.0, "Unknown", 2)?;
+                        s.serialize_field("event_type", &event_type.0)?;
+                        s.serialize_field("action_type", &action_type.0)?;
+                        s.end()
+                    },
                 }
             }
         }
@@ -377,9 +437,280 @@ define_event_enum!(
         ES_EVENT_TYPE_NOTIFY_OD_DELETE_GROUP => NotifyOdDeleteGroup (EventOdDeleteGroup [_ => None] { raw: raw_event.od_delete_group.as_opt()?, version, }),
         /// Notification for an XPC connection being established to a named service.
         ES_EVENT_TYPE_NOTIFY_XPC_CONNECT => NotifyXpcConnect (EventXpcConnect [_ => None] { raw: raw_event.xpc_connect.as_opt()?, }),
+
+        == #[cfg(feature = "macos_15_0_0")]
+        /// Notification for a Gatekeeper override initiated by the user.
+        ES_EVENT_TYPE_NOTIFY_GATEKEEPER_USER_OVERRIDE => NotifyGatekeeperUserOverride (EventGatekeeperUserOverride [_ => None] { raw: raw_event.gatekeeper_user_override.as_opt()?, }),
     }
 );
 
+impl<'a> Event<'a> {
+    /// Whether this is an `AUTH` event, which must be responded to, or a `NOTIFY` one.
+    ///
+    /// For known events, this is the same as checking if [`Self::expected_response_type()`] is
+    /// `Some`. For [`Self::Unknown`] events, it is given by the action type of the message, which
+    /// is authoritative: unknown `AUTH` events must still be responded to.
+    #[inline]
+    pub fn kind(&self) -> EventKind {
+        let is_auth = match self {
+            Self::Unknown { action_type, .. } => *action_type == es_action_type_t::ES_ACTION_TYPE_AUTH,
+            _ => self.expected_response_type().is_some(),
+        };
+        if is_auth {
+            EventKind::Auth
+        } else {
+            EventKind::Notify
+        }
+    }
+
+    /// Name of the operation this event is about, shared by its `AUTH` and `NOTIFY` variants, e.g.
+    /// `"exec"` for both [`Event::AuthExec`] and [`Event::NotifyExec`].
+    ///
+    /// It is the [name][es_event_type_t::name] of the event type without its `auth_` or `notify_`
+    /// prefix, or an empty string for [`Self::Unknown`] events with no known name.
+    pub fn base_event_name(&self) -> &'static str {
+        // All known variants have a name, this is checked by the tests of the sys crate
+        let name = self.event_type().name().unwrap_or_default();
+        name.strip_prefix("auth_")
+            .or_else(|| name.strip_prefix("notify_"))
+            .unwrap_or(name)
+    }
+
+    /// The file this event is mostly about, for generic file activity consumers.
+    ///
+    /// The file chosen for each variant is:
+    ///
+    /// - The source file for `Rename`, `Clone`, `CopyFile`, `Link`, `ReadLink`, `Mmap` and
+    ///   `FileProviderUpdate`, the source directory for `Lookup`
+    /// - The existing file or the parent directory of the new file for `Create`, the directory for
+    ///   `UipcBind`
+    /// - The first file for `ExchangeData`
+    /// - The opened file for `Open` and the socket file for `UipcConnect`
+    /// - The target file for all other file events (`Write`, `Unlink`, `SetMode`, ...)
+    /// - `None` for events that are not about a file, including process events like `Exec` or
+    ///   `Signal`, and for [`Self::Unknown`] events
+    ///
+    /// See [`Self::primary_path()`] to also get the paths of process events.
+    pub fn target_file(&self) -> Option<File<'a>> {
+        let file = match self {
+            Self::AuthOpen(e) | Self::NotifyOpen(e) => e.file(),
+            Self::AuthMmap(e) | Self::NotifyMmap(e) => e.source(),
+            Self::AuthRename(e) | Self::NotifyRename(e) => e.source(),
+            Self::AuthUnlink(e) | Self::NotifyUnlink(e) => e.target(),
+            Self::NotifyClose(e) => e.target(),
+            Self::AuthCreate(e) | Self::NotifyCreate(e) => match e.destination()? {
+                EventCreateDestinationFile::ExistingFile(file) => file,
+                EventCreateDestinationFile::NewPath { directory, .. } => directory,
+            },
+            Self::NotifyExchangeData(e) => e.file1(),
+            Self::AuthLink(e) | Self::NotifyLink(e) => e.source(),
+            Self::AuthSetAttrlist(e) | Self::NotifySetAttrlist(e) => e.target(),
+            Self::AuthSetExtAttr(e) | Self::NotifySetExtAttr(e) => e.target(),
+            Self::AuthSetFlags(e) | Self::NotifySetFlags(e) => e.target(),
+            Self::AuthSetMode(e) | Self::NotifySetMode(e) => e.target(),
+            Self::AuthSetOwner(e) | Self::NotifySetOwner(e) => e.target(),
+            Self::NotifyWrite(e) => e.target(),
+            Self::AuthFileProviderMaterialize(e) | Self::NotifyFileProviderMaterialize(e) => e.target(),
+            Self::AuthFileProviderUpdate(e) | Self::NotifyFileProviderUpdate(e) => e.source(),
+            Self::AuthReadLink(e) | Self::NotifyReadLink(e) => e.source(),
+            Self::AuthTruncate(e) | Self::NotifyTruncate(e) => e.target(),
+            Self::NotifyLookup(e) => e.source_dir(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthChdir(e) | Self::NotifyChdir(e) => e.target(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthGetAttrlist(e) | Self::NotifyGetAttrlist(e) => e.target(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::NotifyStat(e) => e.target(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::NotifyAccess(e) => e.target(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthChroot(e) | Self::NotifyChroot(e) => e.target(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthUTimes(e) | Self::NotifyUTimes(e) => e.target(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthClone(e) | Self::NotifyClone(e) => e.source(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::NotifyFcntl(e) => e.target(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthGetExtAttr(e) | Self::NotifyGetExtAttr(e) => e.target(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthListExtAttr(e) | Self::NotifyListExtAttr(e) => e.target(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthReadDir(e) | Self::NotifyReadDir(e) => e.target(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthDeleteExtAttr(e) | Self::NotifyDeleteExtAttr(e) => e.target(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthFsGetPath(e) | Self::NotifyFsGetPath(e) => e.target(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::NotifyDup(e) => e.target(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthUipcBind(e) | Self::NotifyUipcBind(e) => e.dir(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthUipcConnect(e) | Self::NotifyUipcConnect(e) => e.file(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthExchangeData(e) => e.file1(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthSetAcl(e) | Self::NotifySetAcl(e) => e.target(),
+            #[cfg(feature = "macos_11_0_0")]
+            Self::AuthSearchFs(e) | Self::NotifySearchFs(e) => e.target(),
+            #[cfg(feature = "macos_11_0_0")]
+            Self::AuthFcntl(e) => e.target(),
+            #[cfg(feature = "macos_12_0_0")]
+            Self::AuthCopyFile(e) | Self::NotifyCopyFile(e) => e.source(),
+            _ => return None,
+        };
+
+        Some(file)
+    }
+
+    /// The process targeted by this event, for events where a process acts on another one:
+    /// `Signal`, `GetTask*`, `Trace`, `ProcCheck`, `ProcSuspendResume` and `RemoteThreadCreate`.
+    ///
+    /// `None` for other events, for `ProcCheck` and `ProcSuspendResume` events without a target
+    /// and for [`Self::Unknown`] events.
+    pub fn target_process(&self) -> Option<Process<'a>> {
+        let process = match self {
+            Self::AuthSignal(e) | Self::NotifySignal(e) => e.target(),
+            Self::NotifyGetTask(e) => e.target(),
+            #[cfg(feature = "macos_10_15_4")]
+            Self::AuthProcCheck(e) | Self::NotifyProcCheck(e) => e.target()?,
+            #[cfg(feature = "macos_10_15_4")]
+            Self::AuthGetTask(e) => e.target(),
+            #[cfg(feature = "macos_11_0_0")]
+            Self::AuthProcSuspendResume(e) | Self::NotifyProcSuspendResume(e) => e.target()?,
+            #[cfg(feature = "macos_11_0_0")]
+            Self::NotifyGetTaskName(e) => e.target(),
+            #[cfg(feature = "macos_11_0_0")]
+            Self::NotifyTrace(e) => e.target(),
+            #[cfg(feature = "macos_11_0_0")]
+            Self::NotifyRemoteThreadCreate(e) => e.target(),
+            #[cfg(feature = "macos_11_3_0")]
+            Self::AuthGetTaskRead(e) | Self::NotifyGetTaskRead(e) => e.target(),
+            #[cfg(feature = "macos_11_3_0")]
+            Self::NotifyGetTaskInspect(e) => e.target(),
+            _ => return None,
+        };
+
+        Some(process)
+    }
+
+    /// The path this event is mostly about, for generic logging and indexing.
+    ///
+    /// The path chosen for each variant is:
+    ///
+    /// - The executable of the target process for process events: `Exec` (the new image), `Fork`
+    ///   (the child), `Signal`, `GetTask*`, `ProcCheck`, `ProcSuspendResume`, `Trace` and
+    ///   `RemoteThreadCreate`
+    /// - The source file for `Rename`, `Clone`, `CopyFile`, `Link`, `ReadLink`, `Mmap` and
+    ///   `FileProviderUpdate`, the source directory for `Lookup`
+    /// - The existing file or the parent directory of the new file for `Create`, the directory for
+    ///   `UipcBind`
+    /// - The first file for `ExchangeData`
+    /// - The opened file for `Open` and the socket file for `UipcConnect`
+    /// - The target file for all other file events (`Write`, `Unlink`, `SetMode`, ...)
+    /// - The detected or remediated path for the XProtect events, the executable path for
+    ///   `BtmLaunchItemAdd`
+    /// - `None` for events that are not about a path (`Exit`, `SetUid`, `KextLoad`, ...) and for
+    ///   [`Self::Unknown`] events
+    pub fn primary_path(&self) -> Option<&'a OsStr> {
+        let path = match self {
+            Self::AuthExec(e) | Self::NotifyExec(e) => e.target().executable().path(),
+            Self::NotifyFork(e) => e.child().executable().path(),
+            Self::AuthSignal(e) | Self::NotifySignal(e) => e.target().executable().path(),
+            Self::NotifyGetTask(e) => e.target().executable().path(),
+            Self::AuthOpen(e) | Self::NotifyOpen(e) => e.file().path(),
+            Self::AuthMmap(e) | Self::NotifyMmap(e) => e.source().path(),
+            Self::AuthRename(e) | Self::NotifyRename(e) => e.source().path(),
+            Self::AuthUnlink(e) | Self::NotifyUnlink(e) => e.target().path(),
+            Self::NotifyClose(e) => e.target().path(),
+            Self::AuthCreate(e) | Self::NotifyCreate(e) => match e.destination()? {
+                EventCreateDestinationFile::ExistingFile(file) => file.path(),
+                EventCreateDestinationFile::NewPath { directory, .. } => directory.path(),
+            },
+            Self::NotifyExchangeData(e) => e.file1().path(),
+            Self::AuthLink(e) | Self::NotifyLink(e) => e.source().path(),
+            Self::AuthSetAttrlist(e) | Self::NotifySetAttrlist(e) => e.target().path(),
+            Self::AuthSetExtAttr(e) | Self::NotifySetExtAttr(e) => e.target().path(),
+            Self::AuthSetFlags(e) | Self::NotifySetFlags(e) => e.target().path(),
+            Self::AuthSetMode(e) | Self::NotifySetMode(e) => e.target().path(),
+            Self::AuthSetOwner(e) | Self::NotifySetOwner(e) => e.target().path(),
+            Self::NotifyWrite(e) => e.target().path(),
+            Self::AuthFileProviderMaterialize(e) | Self::NotifyFileProviderMaterialize(e) => e.target().path(),
+            Self::AuthFileProviderUpdate(e) | Self::NotifyFileProviderUpdate(e) => e.source().path(),
+            Self::AuthReadLink(e) | Self::NotifyReadLink(e) => e.source().path(),
+            Self::AuthTruncate(e) | Self::NotifyTruncate(e) => e.target().path(),
+            Self::NotifyLookup(e) => e.source_dir().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthChdir(e) | Self::NotifyChdir(e) => e.target().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthGetAttrlist(e) | Self::NotifyGetAttrlist(e) => e.target().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::NotifyStat(e) => e.target().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::NotifyAccess(e) => e.target().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthChroot(e) | Self::NotifyChroot(e) => e.target().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthUTimes(e) | Self::NotifyUTimes(e) => e.target().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthClone(e) | Self::NotifyClone(e) => e.source().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::NotifyFcntl(e) => e.target().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthGetExtAttr(e) | Self::NotifyGetExtAttr(e) => e.target().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthListExtAttr(e) | Self::NotifyListExtAttr(e) => e.target().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthReadDir(e) | Self::NotifyReadDir(e) => e.target().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthDeleteExtAttr(e) | Self::NotifyDeleteExtAttr(e) => e.target().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthFsGetPath(e) | Self::NotifyFsGetPath(e) => e.target().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::NotifyDup(e) => e.target().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthUipcBind(e) | Self::NotifyUipcBind(e) => e.dir().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthUipcConnect(e) | Self::NotifyUipcConnect(e) => e.file().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthExchangeData(e) => e.file1().path(),
+            #[cfg(feature = "macos_10_15_1")]
+            Self::AuthSetAcl(e) | Self::NotifySetAcl(e) => e.target().path(),
+            #[cfg(feature = "macos_10_15_4")]
+            Self::AuthProcCheck(e) | Self::NotifyProcCheck(e) => e.target()?.executable().path(),
+            #[cfg(feature = "macos_10_15_4")]
+            Self::AuthGetTask(e) => e.target().executable().path(),
+            #[cfg(feature = "macos_11_0_0")]
+            Self::AuthSearchFs(e) | Self::NotifySearchFs(e) => e.target().path(),
+            #[cfg(feature = "macos_11_0_0")]
+            Self::AuthFcntl(e) => e.target().path(),
+            #[cfg(feature = "macos_11_0_0")]
+            Self::AuthProcSuspendResume(e) | Self::NotifyProcSuspendResume(e) => e.target()?.executable().path(),
+            #[cfg(feature = "macos_11_0_0")]
+            Self::NotifyGetTaskName(e) => e.target().executable().path(),
+            #[cfg(feature = "macos_11_0_0")]
+            Self::NotifyTrace(e) => e.target().executable().path(),
+            #[cfg(feature = "macos_11_0_0")]
+            Self::NotifyRemoteThreadCreate(e) => e.target().executable().path(),
+            #[cfg(feature = "macos_11_3_0")]
+            Self::AuthGetTaskRead(e) | Self::NotifyGetTaskRead(e) => e.target().executable().path(),
+            #[cfg(feature = "macos_11_3_0")]
+            Self::NotifyGetTaskInspect(e) => e.target().executable().path(),
+            #[cfg(feature = "macos_12_0_0")]
+            Self::AuthCopyFile(e) | Self::NotifyCopyFile(e) => e.source().path(),
+            #[cfg(feature = "macos_13_0_0")]
+            Self::NotifyXpMalwareDetected(e) => e.detected_path(),
+            #[cfg(feature = "macos_13_0_0")]
+            Self::NotifyXpMalwareRemediated(e) => e.remediated_path()?,
+            #[cfg(feature = "macos_13_0_0")]
+            Self::NotifyBtmLaunchItemAdd(e) => e.executable_path()?,
+            _ => return None,
+        };
+
+        Some(path)
+    }
+}
+
 /// Type of response function to use for this event.
 ///
 /// - [`Client::respond_auth_result()`][crate::Client::respond_auth_result]
@@ -395,6 +726,15 @@ pub enum ExpectedResponseType {
     },
 }
 
+/// Kind of an [`Event`], see [`Event::kind()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    /// Authorization request, it must be responded to before its deadline
+    Auth,
+    /// Notification, it needs no response
+    Notify,
+}
+
 /// Generate an iterator implementation for an array component of an event.
 ///
 /// Safety:
@@ -514,6 +854,57 @@ unsafe fn as_os_str<'a>(x: endpoint_sec_sys::es_string_token_t) -> &'a std::ffi:
     unsafe { &*(x.as_os_str() as *const _) }
 }
 
+/// Implement `get_task_type()` for the events about obtaining a task port, which all share the
+/// `target` and `type_` fields and a `version`.
+macro_rules! impl_get_task_type {
+    ($name:ident) => {
+        impl $name<'_> {
+            /// How the process is obtaining the task port of the target process.
+            ///
+            /// `None` if the message version is below 5, where the field is not available.
+            #[inline(always)]
+            pub fn get_task_type(&self) -> Option<endpoint_sec_sys::es_get_task_type_t> {
+                if self.version >= 5 {
+                    Some(self.raw.type_)
+                } else {
+                    None
+                }
+            }
+        }
+    };
+}
+
+/// Implement the accessors reading the `statfs()` of the events about mounting file systems.
+macro_rules! impl_statfs_accessors {
+    ($name:ident) => {
+        impl<'a> $name<'a> {
+            /// Mounted resource, e.g. `/dev/disk1s1`, from `f_mntfromname`.
+            #[inline(always)]
+            pub fn mount_from(&self) -> &'a ::std::ffi::OsStr {
+                crate::utils::c_array_to_os_str(&self.statfs().f_mntfromname)
+            }
+
+            /// Directory on which the file system is mounted, from `f_mntonname`.
+            #[inline(always)]
+            pub fn mount_on(&self) -> &'a ::std::ffi::OsStr {
+                crate::utils::c_array_to_os_str(&self.statfs().f_mntonname)
+            }
+
+            /// Type of the file system, e.g. `apfs`, from `f_fstypename`.
+            #[inline(always)]
+            pub fn fs_type(&self) -> &'a ::std::ffi::OsStr {
+                crate::utils::c_array_to_os_str(&self.statfs().f_fstypename)
+            }
+
+            /// Mount flags (`MNT_*`), from `f_flags`.
+            #[inline(always)]
+            pub fn flags(&self) -> u32 {
+                self.statfs().f_flags
+            }
+        }
+    };
+}
+
 /// Helper macro to define the event modules without copying the cfgs dozens of times.
 macro_rules! cfg_mod {
     (
@@ -645,4 +1036,262 @@ cfg_mod! {
     mod event_od_delete_user;
     mod event_od_delete_group;
     mod event_xpc_connect;
+
+    == #[cfg(feature = "macos_15_0_0")];
+    mod event_gatekeeper_user_override;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::zeroed_raw;
+
+    /// Build the event of type `event_type` from zeroed raw data, `None` for events whose raw data
+    /// is behind a pointer.
+    fn zeroed_event(raw: &es_events_t, event_type: es_event_type_t) -> Option<Event<'_>> {
+        // Safety: all zeroes is a valid value for the union, the events built from it are never
+        // dereferenced beyond their top-level fields
+        unsafe { Event::from_raw_parts(event_type, es_action_type_t::ES_ACTION_TYPE_NOTIFY, raw, 1) }
+    }
+
+    #[test]
+    fn test_event_type() {
+        let raw: es_events_t = zeroed_raw();
+
+        let types = [
+            es_event_type_t::ES_EVENT_TYPE_AUTH_EXEC,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXEC,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_WRITE,
+            #[cfg(feature = "macos_10_15_1")]
+            es_event_type_t::ES_EVENT_TYPE_AUTH_CLONE,
+            #[cfg(feature = "macos_11_0_0")]
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_REMOTE_THREAD_CREATE,
+            #[cfg(feature = "macos_12_0_0")]
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_SETUID,
+        ];
+        for ty in types {
+            assert_eq!(zeroed_event(&raw, ty).unwrap().event_type(), ty);
+        }
+    }
+
+    #[test]
+    fn test_kind_and_base_event_name() {
+        let raw: es_events_t = zeroed_raw();
+
+        let pairs = [
+            (
+                es_event_type_t::ES_EVENT_TYPE_AUTH_EXEC,
+                es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXEC,
+                "exec",
+            ),
+            (
+                es_event_type_t::ES_EVENT_TYPE_AUTH_OPEN,
+                es_event_type_t::ES_EVENT_TYPE_NOTIFY_OPEN,
+                "open",
+            ),
+            (
+                es_event_type_t::ES_EVENT_TYPE_AUTH_RENAME,
+                es_event_type_t::ES_EVENT_TYPE_NOTIFY_RENAME,
+                "rename",
+            ),
+            (
+                es_event_type_t::ES_EVENT_TYPE_AUTH_SETMODE,
+                es_event_type_t::ES_EVENT_TYPE_NOTIFY_SETMODE,
+                "setmode",
+            ),
+            #[cfg(feature = "macos_10_15_1")]
+            (
+                es_event_type_t::ES_EVENT_TYPE_AUTH_CHDIR,
+                es_event_type_t::ES_EVENT_TYPE_NOTIFY_CHDIR,
+                "chdir",
+            ),
+            #[cfg(feature = "macos_11_0_0")]
+            (
+                es_event_type_t::ES_EVENT_TYPE_AUTH_SEARCHFS,
+                es_event_type_t::ES_EVENT_TYPE_NOTIFY_SEARCHFS,
+                "searchfs",
+            ),
+        ];
+        for (auth, notify, name) in pairs {
+            let auth = zeroed_event(&raw, auth).unwrap();
+            let notify = zeroed_event(&raw, notify).unwrap();
+            assert_eq!(auth.kind(), EventKind::Auth);
+            assert_eq!(notify.kind(), EventKind::Notify);
+            assert_eq!(auth.base_event_name(), name);
+            assert_eq!(notify.base_event_name(), name);
+        }
+
+        let fork = zeroed_event(&raw, es_event_type_t::ES_EVENT_TYPE_NOTIFY_FORK).unwrap();
+        assert_eq!(fork.kind(), EventKind::Notify);
+        assert_eq!(fork.base_event_name(), "fork");
+
+        // Every variant agrees with the name of its event type
+        for ty in (0..es_event_type_t::ES_EVENT_TYPE_LAST.0).map(es_event_type_t) {
+            let Some(event) = zeroed_event(&raw, ty) else { continue };
+            assert_eq!(event.event_type(), ty);
+            if let Event::Unknown { .. } = event {
+                continue;
+            }
+            let name = ty.name().unwrap();
+            let expected = match event.kind() {
+                EventKind::Auth => format!("auth_{}", event.base_event_name()),
+                EventKind::Notify => format!("notify_{}", event.base_event_name()),
+            };
+            assert_eq!(name, expected);
+        }
+    }
+
+    #[test]
+    fn test_unknown_event() {
+        let raw: es_events_t = zeroed_raw();
+
+        for ty in [es_event_type_t::ES_EVENT_TYPE_LAST, es_event_type_t(u32::MAX)] {
+            let event = zeroed_event(&raw, ty).unwrap();
+            assert_eq!(event, Event::Unknown {
+                event_type: ty,
+                action_type: es_action_type_t::ES_ACTION_TYPE_NOTIFY,
+            });
+            assert_eq!(event.event_type(), ty);
+            assert_eq!(event.expected_response_type(), None);
+            assert_eq!(event.kind(), EventKind::Notify);
+            // Without any macOS feature, `ES_EVENT_TYPE_LAST` is `notify_chdir`: unknown to this
+            // crate but named by the sys crate
+            if ty.name().is_none() {
+                assert_eq!(event.base_event_name(), "");
+            }
+            assert_eq!(event.primary_path(), None);
+
+            // The kind of unknown events comes from the action type of the message
+            // Safety: see `zeroed_event()`
+            let event = unsafe { Event::from_raw_parts(ty, es_action_type_t::ES_ACTION_TYPE_AUTH, &raw, 1) }.unwrap();
+            assert_eq!(event.kind(), EventKind::Auth);
+            assert_eq!(event.expected_response_type(), None);
+        }
+    }
+
+    #[test]
+    fn test_statfs_accessors() {
+        use endpoint_sec_sys::{es_event_mount_t, es_event_unmount_t, ShouldNotBeNull};
+
+        use crate::utils::statfs_with_names;
+
+        let mut statfs = statfs_with_names("/dev/disk4s1", "/Volumes/USB", "msdos");
+        statfs.f_flags = (libc::MNT_RDONLY | libc::MNT_NOSUID) as u32;
+
+        let mut mount: es_event_mount_t = zeroed_raw();
+        mount.statfs = ShouldNotBeNull::new(&mut statfs);
+        let event = EventMount { raw: &mount };
+        assert_eq!(event.mount_from(), "/dev/disk4s1");
+        assert_eq!(event.mount_on(), "/Volumes/USB");
+        assert_eq!(event.fs_type(), "msdos");
+        assert_eq!(event.flags(), (libc::MNT_RDONLY | libc::MNT_NOSUID) as u32);
+
+        // The accessors are shared, check they read the right event
+        let mut statfs = statfs_with_names("/dev/disk3s1s1", "/", "apfs");
+        let mut unmount: es_event_unmount_t = zeroed_raw();
+        unmount.statfs = ShouldNotBeNull::new(&mut statfs);
+        let event = EventUnmount { raw: &unmount };
+        assert_eq!(event.mount_from(), "/dev/disk3s1s1");
+        assert_eq!(event.mount_on(), "/");
+        assert_eq!(event.fs_type(), "apfs");
+        assert_eq!(event.flags(), 0);
+
+        #[cfg(feature = "macos_11_0_0")]
+        {
+            let mut remount: endpoint_sec_sys::es_event_remount_t = zeroed_raw();
+            remount.statfs = ShouldNotBeNull::new(&mut statfs);
+            let event = EventRemount { raw: &remount };
+            assert_eq!(event.mount_on(), "/");
+            assert_eq!(event.fs_type(), "apfs");
+        }
+    }
+
+    #[test]
+    fn test_target_file() {
+        use endpoint_sec_sys::{
+            es_destination_type_t, es_event_create_t, es_event_exec_t, es_event_exit_t, es_event_open_t,
+            es_event_signal_t, es_event_unlink_t, ShouldNotBeNull,
+        };
+
+        use crate::utils::file_with_path;
+
+        let mut file = file_with_path("/tmp/file");
+        let mut dir = file_with_path("/tmp");
+        let path = |event: &Event<'_>| event.target_file().map(|f| f.path().to_os_string());
+
+        let mut open: es_event_open_t = zeroed_raw();
+        open.file = ShouldNotBeNull::new(&mut file);
+        assert_eq!(
+            path(&Event::AuthOpen(EventOpen { raw: &open })),
+            Some("/tmp/file".into())
+        );
+
+        let mut unlink: es_event_unlink_t = zeroed_raw();
+        unlink.target = ShouldNotBeNull::new(&mut file);
+        unlink.parent_dir = ShouldNotBeNull::new(&mut dir);
+        assert_eq!(
+            path(&Event::NotifyUnlink(EventUnlink { raw: &unlink })),
+            Some("/tmp/file".into())
+        );
+
+        let mut create: es_event_create_t = zeroed_raw();
+        create.destination_type = es_destination_type_t::ES_DESTINATION_TYPE_NEW_PATH;
+        // Safety: `new_path` is the union field used for `ES_DESTINATION_TYPE_NEW_PATH`
+        unsafe { (*create.destination.new_path).dir = ShouldNotBeNull::new(&mut dir) };
+        assert_eq!(
+            path(&Event::NotifyCreate(EventCreate {
+                raw: &create,
+                version: 1
+            })),
+            Some("/tmp".into())
+        );
+
+        // Events not about a file, their data is never read
+        let (exec, signal, exit): (es_event_exec_t, es_event_signal_t, es_event_exit_t) =
+            (zeroed_raw(), zeroed_raw(), zeroed_raw());
+        assert_eq!(
+            path(&Event::NotifyExec(EventExec { raw: &exec, version: 1 })),
+            None
+        );
+        assert_eq!(
+            path(&Event::AuthSignal(EventSignal {
+                raw: &signal,
+                version: 1
+            })),
+            None
+        );
+        assert_eq!(path(&Event::NotifyExit(EventExit { raw: &exit })), None);
+        assert_eq!(
+            path(&Event::Unknown {
+                event_type: es_event_type_t(u32::MAX),
+                action_type: es_action_type_t::ES_ACTION_TYPE_NOTIFY,
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_target_process() {
+        use endpoint_sec_sys::{es_event_open_t, es_event_signal_t, es_process_t, ShouldNotBeNull};
+
+        use crate::utils::file_with_path;
+
+        let mut target: es_process_t = zeroed_raw();
+        target.ppid = 42;
+
+        let mut signal: es_event_signal_t = zeroed_raw();
+        signal.sig = libc::SIGKILL;
+        signal.target = ShouldNotBeNull::new(&mut target);
+        let event = Event::AuthSignal(EventSignal {
+            raw: &signal,
+            version: 1,
+        });
+        assert_eq!(event.target_process().map(|p| p.ppid()), Some(42));
+
+        let mut file = file_with_path("/tmp/file");
+        let mut open: es_event_open_t = zeroed_raw();
+        open.file = ShouldNotBeNull::new(&mut file);
+        let event = Event::AuthOpen(EventOpen { raw: &open });
+        assert!(event.target_process().is_none());
+    }
 }
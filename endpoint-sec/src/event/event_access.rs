@@ -2,9 +2,9 @@
 
 use endpoint_sec_sys::es_event_access_t;
 
-use crate::File;
+use crate::{AccessMode, File};
 
-/// View stat information of a file event.
+/// Check the accessibility of a file, as with `access(2)`.
 #[doc(alias = "es_event_access_t")]
 pub struct EventAccess<'a> {
     /// Raw event
@@ -18,6 +18,12 @@ impl<'a> EventAccess<'a> {
         self.raw.mode
     }
 
+    /// Access permission to check, as [`AccessMode`].
+    #[inline(always)]
+    pub fn mode_typed(&self) -> AccessMode {
+        AccessMode(self.raw.mode)
+    }
+
     /// The file to check for access.
     #[inline(always)]
     pub fn target(&self) -> File<'a> {
@@ -34,3 +40,32 @@ impl_debug_eq_hash_with_functions!(
     mode,
     target,
 );
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::ShouldNotBeNull;
+
+    use super::*;
+    use crate::utils::{file_with_path, zeroed_raw};
+
+    #[test]
+    fn test_access() {
+        let mut target = file_with_path("/etc/sudoers");
+
+        let mut raw: es_event_access_t = zeroed_raw();
+        raw.mode = libc::R_OK | libc::W_OK;
+        raw.target = ShouldNotBeNull::new(&mut target);
+
+        let event = EventAccess { raw: &raw };
+        assert_eq!(event.mode(), libc::R_OK | libc::W_OK);
+        assert!(event.mode_typed().reads());
+        assert!(event.mode_typed().writes());
+        assert!(event.mode_typed().executes() == false);
+        assert!(event.mode_typed().exists_only() == false);
+        assert_eq!(event.target().path(), "/etc/sudoers");
+
+        raw.mode = libc::F_OK;
+        let event = EventAccess { raw: &raw };
+        assert!(event.mode_typed().exists_only());
+    }
+}
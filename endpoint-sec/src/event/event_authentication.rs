@@ -276,3 +276,80 @@ impl<'a> EventAuthenticationAutoUnlock<'a> {
 unsafe impl Send for EventAuthenticationAutoUnlock<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventAuthenticationAutoUnlock<'a>; username, type_);
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::ShouldNotBeNull;
+
+    use super::*;
+    use crate::utils::{string_token, zeroed_raw};
+
+    #[test]
+    fn test_data() {
+        /// Wrap `raw`
+        fn event(raw: &es_event_authentication_t) -> EventAuthentication<'_> {
+            EventAuthentication { raw, version: 6 }
+        }
+
+        let mut raw: es_event_authentication_t = zeroed_raw();
+
+        let mut od: es_event_authentication_od_t = zeroed_raw();
+        od.record_type = string_token("Users");
+        od.record_name = string_token("jdoe");
+        od.node_name = string_token("/Local/Default");
+        raw.type_ = es_authentication_type_t::ES_AUTHENTICATION_TYPE_OD;
+        raw.data.od = ShouldNotBeNull::new(&mut od);
+        match event(&raw).data() {
+            Some(AuthenticationData::Od(od)) => {
+                assert_eq!(od.record_type(), "Users");
+                assert_eq!(od.record_name(), "jdoe");
+                assert_eq!(od.node_name(), "/Local/Default");
+                assert_eq!(od.db_path(), "");
+            },
+            _ => panic!("expected OD data"),
+        }
+
+        let mut touchid: es_event_authentication_touchid_t = zeroed_raw();
+        touchid.touchid_mode = es_touchid_mode_t::ES_TOUCHID_MODE_IDENTIFICATION;
+        raw.type_ = es_authentication_type_t::ES_AUTHENTICATION_TYPE_TOUCHID;
+        raw.data.touchid = ShouldNotBeNull::new(&mut touchid);
+        match event(&raw).data() {
+            Some(AuthenticationData::TouchId(touchid)) => {
+                assert_eq!(touchid.touchid_mode(), es_touchid_mode_t::ES_TOUCHID_MODE_IDENTIFICATION);
+                assert_eq!(touchid.uid(), None);
+            },
+            _ => panic!("expected Touch ID data"),
+        }
+
+        let mut token: es_event_authentication_token_t = zeroed_raw();
+        token.token_id = string_token("token");
+        raw.type_ = es_authentication_type_t::ES_AUTHENTICATION_TYPE_TOKEN;
+        raw.data.token = ShouldNotBeNull::new(&mut token);
+        match event(&raw).data() {
+            Some(AuthenticationData::Token(token)) => assert_eq!(token.token_id(), "token"),
+            _ => panic!("expected token data"),
+        }
+
+        let mut auto_unlock: es_event_authentication_auto_unlock_t = zeroed_raw();
+        auto_unlock.username = string_token("jdoe");
+        auto_unlock.type_ = es_auto_unlock_type_t::ES_AUTO_UNLOCK_MACHINE_UNLOCK;
+        raw.type_ = es_authentication_type_t::ES_AUTHENTICATION_TYPE_AUTO_UNLOCK;
+        raw.data.auto_unlock = ShouldNotBeNull::new(&mut auto_unlock);
+        match event(&raw).data() {
+            Some(AuthenticationData::AutoUnlock(auto_unlock)) => {
+                assert_eq!(auto_unlock.username(), "jdoe");
+                assert_eq!(auto_unlock.type_(), es_auto_unlock_type_t::ES_AUTO_UNLOCK_MACHINE_UNLOCK);
+            },
+            _ => panic!("expected auto unlock data"),
+        }
+
+        // Unknown type from a newer macOS
+        raw.type_ = es_authentication_type_t(4);
+        assert!(event(&raw).data().is_none());
+
+        // Null data
+        raw.type_ = es_authentication_type_t::ES_AUTHENTICATION_TYPE_OD;
+        raw.data.od = ShouldNotBeNull::new(std::ptr::null_mut());
+        assert!(event(&raw).data().is_none());
+    }
+}
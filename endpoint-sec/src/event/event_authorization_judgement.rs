@@ -118,3 +118,55 @@ make_event_data_iterator!(
     read_nth_result,
     make_result,
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{string_token, zeroed_raw};
+
+    #[test]
+    fn test_results() {
+        let mut results = [
+            es_authorization_result_t {
+                right_name: string_token("system.privilege.admin"),
+                rule_class: es_authorization_rule_class_t::ES_AUTHORIZATION_RULE_CLASS_USER,
+                granted: true,
+            },
+            es_authorization_result_t {
+                right_name: string_token("system.preferences"),
+                rule_class: es_authorization_rule_class_t::ES_AUTHORIZATION_RULE_CLASS_DENY,
+                granted: false,
+            },
+        ];
+
+        let mut raw: es_event_authorization_judgement_t = zeroed_raw();
+        raw.return_code = -60005;
+        raw.result_count = results.len();
+        raw.results = results.as_mut_ptr();
+
+        let event = EventAuthorizationJudgement { raw: &raw, version: 7 };
+        assert_eq!(event.return_code(), -60005);
+
+        let iter = event.rights();
+        assert_eq!(iter.len(), event.result_count());
+
+        let results: Vec<_> = iter
+            .map(|r| (r.right_name().to_owned(), r.rule_class(), r.granted()))
+            .collect();
+        assert_eq!(
+            results,
+            [
+                (
+                    "system.privilege.admin".into(),
+                    es_authorization_rule_class_t::ES_AUTHORIZATION_RULE_CLASS_USER,
+                    true
+                ),
+                (
+                    "system.preferences".into(),
+                    es_authorization_rule_class_t::ES_AUTHORIZATION_RULE_CLASS_DENY,
+                    false
+                ),
+            ]
+        );
+    }
+}
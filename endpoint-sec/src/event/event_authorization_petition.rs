@@ -74,3 +74,30 @@ make_event_data_iterator!(
     read_nth_right,
     super::as_os_str,
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{string_token, zeroed_raw};
+
+    #[test]
+    fn test_rights() {
+        let mut rights = [string_token("system.privilege.admin"), string_token("system.preferences")];
+
+        let mut raw: es_event_authorization_petition_t = zeroed_raw();
+        raw.flags = 3;
+        raw.right_count = rights.len();
+        raw.rights = rights.as_mut_ptr();
+
+        let event = EventAuthorizationPetition { raw: &raw, version: 7 };
+        assert_eq!(event.flags(), 3);
+        assert!(event.petitioner().is_none());
+
+        let mut iter = event.rights();
+        assert_eq!(iter.len(), event.right_count());
+        assert_eq!(iter.next(), Some(OsStr::new("system.privilege.admin")));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next(), Some(OsStr::new("system.preferences")));
+        assert_eq!(iter.next(), None);
+    }
+}
@@ -44,10 +44,12 @@ impl<'a> EventBtmLaunchItemAdd<'a> {
 
     /// Optional. If available and applicable, the POSIX executable path from the launchd plist. If
     /// the path is relative, it is relative to `item.app_url`.
+    ///
+    /// `None` when empty.
     #[inline(always)]
-    pub fn executable_path(&self) -> &'a OsStr {
+    pub fn executable_path(&self) -> Option<&'a OsStr> {
         // Safety: 'a tied to self, object obtained through ES
-        unsafe { self.raw.executable_path.as_os_str() }
+        unsafe { self.raw.executable_path.as_opt_os_str() }
     }
 }
 
@@ -121,3 +123,67 @@ impl_debug_eq_hash_with_functions!(
     BtmLaunchItem<'a>;
     item_type, legacy, managed, uid, item_url, app_url,
 );
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::{es_process_t, ShouldNotBeNull};
+
+    use super::*;
+    use crate::utils::{string_token, zeroed_raw};
+
+    /// Daemon launch item attributed to no app
+    fn daemon_item() -> es_btm_launch_item_t {
+        let mut item: es_btm_launch_item_t = zeroed_raw();
+        item.item_type = es_btm_item_type_t::ES_BTM_ITEM_TYPE_DAEMON;
+        item.legacy = true;
+        item.uid = 0;
+        item.item_url = string_token("file:///Library/LaunchDaemons/com.example.daemon.plist");
+        item
+    }
+
+    #[test]
+    fn test_btm_launch_item_add_daemon() {
+        let mut item = daemon_item();
+
+        let mut raw: es_event_btm_launch_item_add_t = zeroed_raw();
+        raw.item = ShouldNotBeNull::new(&mut item);
+
+        let event = EventBtmLaunchItemAdd { raw: &raw, version: 6 };
+        assert!(event.instigator().is_none());
+        assert!(event.app().is_none());
+        assert_eq!(event.executable_path(), None);
+
+        let item = event.item();
+        assert_eq!(item.item_type(), es_btm_item_type_t::ES_BTM_ITEM_TYPE_DAEMON);
+        assert!(item.legacy());
+        assert!(item.managed() == false);
+        assert_eq!(item.uid(), 0);
+        assert_eq!(
+            item.item_url(),
+            OsStr::new("file:///Library/LaunchDaemons/com.example.daemon.plist")
+        );
+        assert_eq!(item.app_url(), OsStr::new(""));
+    }
+
+    #[test]
+    fn test_btm_launch_item_add_daemon_with_instigator() {
+        let mut item = daemon_item();
+
+        let mut instigator: es_process_t = zeroed_raw();
+        instigator.ppid = 1;
+        let mut app: es_process_t = zeroed_raw();
+        app.ppid = 42;
+
+        let mut raw: es_event_btm_launch_item_add_t = zeroed_raw();
+        raw.instigator = &mut instigator;
+        raw.app = &mut app;
+        raw.item = ShouldNotBeNull::new(&mut item);
+        raw.executable_path = string_token("/usr/local/bin/daemon");
+
+        let event = EventBtmLaunchItemAdd { raw: &raw, version: 6 };
+        assert_eq!(event.instigator().map(|p| p.ppid()), Some(1));
+        assert_eq!(event.app().map(|p| p.ppid()), Some(42));
+        assert_eq!(event.executable_path(), Some(OsStr::new("/usr/local/bin/daemon")));
+        assert_eq!(event.item().item_type(), es_btm_item_type_t::ES_BTM_ITEM_TYPE_DAEMON);
+    }
+}
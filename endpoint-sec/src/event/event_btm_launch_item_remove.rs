@@ -5,7 +5,7 @@ use endpoint_sec_sys::es_event_btm_launch_item_remove_t;
 use crate::{BtmLaunchItem, Process};
 
 /// A launch item being removed from background task management.
-#[doc(alias = "es_event_btm_launch_item_add_t")]
+#[doc(alias = "es_event_btm_launch_item_remove_t")]
 pub struct EventBtmLaunchItemRemove<'a> {
     /// Raw event
     pub(crate) raw: &'a es_event_btm_launch_item_remove_t,
@@ -15,6 +15,10 @@ pub struct EventClose<'a> {
 
 impl<'a> EventClose<'a> {
     /// true if the target file being closed has been modified.
+    ///
+    /// This only reflects modifications made through filesystem syscalls (`write(2)`, ...). A file
+    /// only modified through a memory mapping is reported as not modified, see
+    /// `was_mapped_writable()` (macOS 13.0+) to detect those.
     #[inline(always)]
     pub fn modified(&self) -> bool {
         self.raw.modified
@@ -28,7 +32,13 @@ impl<'a> EventClose<'a> {
     }
 
     /// If `true`, at some point in the lifetime of the target file vnode it was mapped into a
-    /// process as writable.
+    /// process as writable. `None` if the message version is below 6.
+    ///
+    /// Together with [`Self::modified()`], this detects files that may have been written to:
+    /// `modified() == false` with `was_mapped_writable() == Some(true)` means the file could have
+    /// been changed through memory only. It does not mean the mapped memory was actually written
+    /// to, nor that the file is still mapped: the flag covers the whole lifetime of the vnode in
+    /// the kernel, which can be longer than the lifetime of the file descriptor being closed.
     #[cfg(feature = "macos_13_0_0")]
     #[inline(always)]
     pub fn was_mapped_writable(&self) -> Option<bool> {
@@ -49,3 +59,47 @@ impl_debug_eq_hash_with_functions!(
     target,
     #[cfg(feature = "macos_13_0_0")] was_mapped_writable,
 );
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::ShouldNotBeNull;
+
+    use super::*;
+    use crate::utils::{file_with_path, zeroed_raw};
+
+    #[test]
+    fn test_close() {
+        let mut target = file_with_path("/tmp/file");
+
+        let mut raw: es_event_close_t = zeroed_raw();
+        raw.target = ShouldNotBeNull::new(&mut target);
+
+        let event = EventClose { raw: &raw, version: 1 };
+        assert!(event.modified() == false);
+        assert_eq!(event.target().path(), "/tmp/file");
+
+        raw.modified = true;
+        let event = EventClose { raw: &raw, version: 1 };
+        assert!(event.modified());
+    }
+
+    #[test]
+    #[cfg(feature = "macos_13_0_0")]
+    fn test_was_mapped_writable_version() {
+        let mut target = file_with_path("/tmp/file");
+
+        let mut raw: es_event_close_t = zeroed_raw();
+        raw.target = ShouldNotBeNull::new(&mut target);
+        raw.anon0.was_mapped_writable = true;
+
+        let event = EventClose { raw: &raw, version: 5 };
+        assert_eq!(event.was_mapped_writable(), None);
+
+        let event = EventClose { raw: &raw, version: 6 };
+        assert_eq!(event.was_mapped_writable(), Some(true));
+
+        raw.anon0.was_mapped_writable = false;
+        let event = EventClose { raw: &raw, version: 7 };
+        assert_eq!(event.was_mapped_writable(), Some(false));
+    }
+}
@@ -64,3 +64,40 @@ impl<'a> EventCopyFile<'a> {
 unsafe impl Send for EventCopyFile<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventCopyFile<'a>; source, target_file, target_dir, target_name, mode, flags);
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::ShouldNotBeNull;
+
+    use super::*;
+    use crate::utils::{file_with_path, string_token, zeroed_raw};
+
+    #[test]
+    fn test_copyfile() {
+        let mut source = file_with_path("/tmp/source");
+        let mut target_dir = file_with_path("/tmp/dir");
+        let mut target_file = file_with_path("/tmp/dir/name");
+
+        let mut raw: es_event_copyfile_t = zeroed_raw();
+        raw.source = ShouldNotBeNull::new(&mut source);
+        raw.target_dir = ShouldNotBeNull::new(&mut target_dir);
+        raw.target_name = string_token("name");
+        raw.mode = 0o644;
+        raw.flags = 1;
+
+        // New file
+        let event = EventCopyFile { raw: &raw };
+        assert_eq!(event.source().path(), "/tmp/source");
+        assert!(event.target_file().is_none());
+        assert_eq!(event.target_dir().path(), "/tmp/dir");
+        assert_eq!(event.target_name(), "name");
+        assert_eq!(event.mode(), 0o644);
+        assert_eq!(event.flags(), 1);
+
+        // Overwrite of an existing file
+        raw.target_file = &mut target_file;
+        let event = EventCopyFile { raw: &raw };
+        assert_eq!(event.target_file().unwrap().path(), "/tmp/dir/name");
+        assert_eq!(event.target_dir().path(), "/tmp/dir");
+    }
+}
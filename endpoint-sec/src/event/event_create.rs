@@ -79,3 +79,71 @@ impl<'a> EventCreate<'a> {
 unsafe impl Send for EventCreate<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventCreate<'a> with version; destination, #[cfg(feature = "macos_10_15_1")] acl);
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    #[cfg(feature = "macos_10_15_1")]
+    use endpoint_sec_sys::es_event_create_t_anon_1_anon_0;
+    use endpoint_sec_sys::{es_event_create_t_anon_0_anon_0, ShouldNotBeNull};
+
+    use super::*;
+    use crate::utils::{file_with_path, string_token, zeroed_raw};
+
+    #[test]
+    fn test_destination() {
+        let mut target = file_with_path("/tmp/target");
+
+        let mut raw: es_event_create_t = zeroed_raw();
+
+        raw.destination_type = es_destination_type_t::ES_DESTINATION_TYPE_EXISTING_FILE;
+        raw.destination.existing_file = ShouldNotBeNull::new(&mut target);
+        let event = EventCreate { raw: &raw, version: 1 };
+        match event.destination() {
+            Some(EventCreateDestinationFile::ExistingFile(f)) => assert_eq!(f.path(), "/tmp/target"),
+            _ => panic!("expected an existing file"),
+        }
+
+        raw.destination_type = es_destination_type_t::ES_DESTINATION_TYPE_NEW_PATH;
+        raw.destination.new_path = ManuallyDrop::new(es_event_create_t_anon_0_anon_0 {
+            dir: ShouldNotBeNull::new(&mut target),
+            filename: string_token("name"),
+            mode: 0o644,
+        });
+        let event = EventCreate { raw: &raw, version: 1 };
+        match event.destination() {
+            Some(EventCreateDestinationFile::NewPath {
+                directory,
+                filename,
+                mode,
+            }) => {
+                assert_eq!(directory.path(), "/tmp/target");
+                assert_eq!(filename, "name");
+                assert_eq!(mode, 0o644);
+            },
+            _ => panic!("expected a new path"),
+        }
+
+        raw.destination_type = es_destination_type_t(2);
+        let event = EventCreate { raw: &raw, version: 1 };
+        assert!(event.destination().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "macos_10_15_1")]
+    fn test_acl() {
+        let mut raw: es_event_create_t = zeroed_raw();
+
+        // NULL ACL
+        assert!(EventCreate { raw: &raw, version: 2 }.acl().is_none());
+
+        // `_acl` is opaque and zero-sized, a dangling pointer is enough to represent a present ACL
+        raw.anon_1.anon_0 = ManuallyDrop::new(es_event_create_t_anon_1_anon_0 {
+            acl: std::ptr::NonNull::dangling().as_ptr(),
+        });
+        assert!(EventCreate { raw: &raw, version: 2 }.acl().is_some());
+        // The field is not available before version 2
+        assert!(EventCreate { raw: &raw, version: 1 }.acl().is_none());
+    }
+}
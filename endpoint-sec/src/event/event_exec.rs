@@ -1,6 +1,7 @@
 //! [`EventExec`]
 
 use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
 
 #[cfg(feature = "macos_13_0_0")]
 use endpoint_sec_sys::{cpu_subtype_t, cpu_type_t};
@@ -29,6 +30,9 @@ pub struct Fd<'a>(pub(crate) &'a es_fd_t);
 
 impl<'a> EventExec<'a> {
     /// The new process that is being executed.
+    ///
+    /// [`Message::process()`][crate::Message::process] is the process **before** the exec, see
+    /// [`EventFork`][crate::EventFork] for how to correlate both to build a process tree.
     #[inline(always)]
     pub fn target(&self) -> Process<'a> {
         // Safety: 'a tied to self, object obtained through ES
@@ -150,6 +154,15 @@ impl<'a> EventExec<'a> {
         ExecEnvs::new(self)
     }
 
+    /// Value of the first environment variable named `name`, e.g. `PATH` or
+    /// `DYLD_INSERT_LIBRARIES`.
+    ///
+    /// Entries are split on their first `=`, entries without one are ignored.
+    #[inline]
+    pub fn env_var(&self, name: &OsStr) -> Option<&'a OsStr> {
+        find_env_var(self.envs(), name)
+    }
+
     /// Iterator over the file descriptors
     #[inline(always)]
     #[cfg(feature = "macos_11_0_0")]
@@ -181,6 +194,17 @@ impl<'a> EventExec<'a> {
         }
     }
 
+    /// `true` if the executable image is for another CPU type than the one this crate was compiled
+    /// for, e.g. an `x86_64` image run through Rosetta on Apple silicon. `None` below version 6.
+    ///
+    /// **Note**: the comparison is made with the architecture of the current binary, an `x86_64`
+    /// client itself running through Rosetta will consider `x86_64` images native.
+    #[inline(always)]
+    #[cfg(feature = "macos_13_0_0")]
+    pub fn is_translated(&self) -> Option<bool> {
+        self.image_cputype().map(|cputype| cputype != HOST_CPU_TYPE)
+    }
+
     /// Collect the argument for debug
     fn all_args(&self) -> Vec<&'a OsStr> {
         self.args().collect()
@@ -208,8 +232,25 @@ impl_debug_eq_hash_with_functions!(EventExec<'a> with version;
     image_cputype,
     #[cfg(feature = "macos_13_0_0")]
     image_cpusubtype,
+    #[cfg(feature = "macos_13_3_0")]
+    dyld_exec_path,
 );
 
+/// `CPU_TYPE_X86_64` from `<mach/machine.h>`
+#[cfg(feature = "macos_13_0_0")]
+const CPU_TYPE_X86_64: cpu_type_t = 7 | 0x0100_0000;
+/// `CPU_TYPE_ARM64` from `<mach/machine.h>`
+#[cfg(feature = "macos_13_0_0")]
+const CPU_TYPE_ARM64: cpu_type_t = 12 | 0x0100_0000;
+
+/// CPU type of the architecture this crate is compiled for
+#[cfg(feature = "macos_13_0_0")]
+const HOST_CPU_TYPE: cpu_type_t = if cfg!(target_arch = "aarch64") {
+    CPU_TYPE_ARM64
+} else {
+    CPU_TYPE_X86_64
+};
+
 #[cfg(feature = "macos_11_0_0")]
 impl<'a> Fd<'a> {
     /// File descriptor number
@@ -278,3 +319,111 @@ make_event_data_iterator!(
     es_exec_fd,
     make_fd,
 );
+
+/// Value of the first entry named `name` in `envs`, see [`EventExec::env_var()`]
+fn find_env_var<'a>(mut envs: impl Iterator<Item = &'a OsStr>, name: &OsStr) -> Option<&'a OsStr> {
+    let name = name.as_bytes();
+
+    envs.find_map(|entry| {
+        let entry = entry.as_bytes();
+        let sep = entry.iter().position(|&b| b == b'=')?;
+        (&entry[..sep] == name).then(|| OsStr::from_bytes(&entry[sep + 1..]))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_env_var() {
+        let envs = |entries: &'static [&'static str]| entries.iter().map(OsStr::new);
+        let name = OsStr::new("PATH");
+
+        assert_eq!(find_env_var(envs(&[]), name), None);
+        assert_eq!(find_env_var(envs(&["PATH", "PATHS=/bin", "HOME=/"]), name), None);
+        assert_eq!(
+            find_env_var(envs(&["HOME=/", "PATH=/bin=x", "PATH=/usr/bin"]), name),
+            Some(OsStr::new("/bin=x"))
+        );
+        assert_eq!(find_env_var(envs(&["PATH="]), name), Some(OsStr::new("")));
+    }
+
+    #[test]
+    #[cfg(feature = "macos_10_15_4")]
+    fn test_script_and_cwd_versions() {
+        use endpoint_sec_sys::ShouldNotBeNull;
+
+        use crate::utils::{file_with_path, zeroed_raw};
+
+        let mut script = file_with_path("/tmp/script.sh");
+        let mut cwd = file_with_path("/tmp");
+        let mut raw: es_event_exec_t = zeroed_raw();
+        // Safety: the union is zeroed, writing to its fields is fine
+        unsafe {
+            (*raw.anon_0.anon_0).script = &mut script;
+            (*raw.anon_0.anon_0).cwd = ShouldNotBeNull::new(&mut cwd);
+        }
+
+        let event = EventExec { raw: &raw, version: 1 };
+        assert!(event.script().is_none());
+        assert!(event.cwd().is_none());
+
+        let event = EventExec { raw: &raw, version: 2 };
+        assert_eq!(event.script().unwrap().path(), "/tmp/script.sh");
+        assert!(event.cwd().is_none());
+
+        let event = EventExec { raw: &raw, version: 3 };
+        assert_eq!(event.script().unwrap().path(), "/tmp/script.sh");
+        assert_eq!(event.cwd().unwrap().path(), "/tmp");
+
+        // Not executing a script
+        // Safety: see above
+        unsafe { (*raw.anon_0.anon_0).script = std::ptr::null_mut() };
+        let event = EventExec { raw: &raw, version: 3 };
+        assert!(event.script().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "macos_13_0_0")]
+    fn test_image_cputype_version() {
+        use crate::utils::zeroed_raw;
+
+        let mut raw: es_event_exec_t = zeroed_raw();
+        // Safety: the union is zeroed, writing to its fields is fine
+        unsafe {
+            (*raw.anon_0.anon_0).image_cputype = CPU_TYPE_X86_64;
+            (*raw.anon_0.anon_0).image_cpusubtype = 3; // CPU_SUBTYPE_X86_64_ALL
+        }
+
+        let event = EventExec { raw: &raw, version: 5 };
+        assert_eq!(event.image_cputype(), None);
+        assert_eq!(event.image_cpusubtype(), None);
+        assert_eq!(event.is_translated(), None);
+
+        let event = EventExec { raw: &raw, version: 6 };
+        assert_eq!(event.image_cputype(), Some(CPU_TYPE_X86_64));
+        assert_eq!(event.image_cpusubtype(), Some(3));
+        assert_eq!(event.is_translated(), Some(HOST_CPU_TYPE == CPU_TYPE_ARM64));
+
+        // Safety: see above
+        unsafe { (*raw.anon_0.anon_0).image_cputype = CPU_TYPE_ARM64 };
+        let event = EventExec { raw: &raw, version: 6 };
+        assert_eq!(event.is_translated(), Some(HOST_CPU_TYPE == CPU_TYPE_X86_64));
+    }
+
+    #[test]
+    #[cfg(feature = "macos_13_3_0")]
+    fn test_dyld_exec_path_version() {
+        use crate::utils::{string_token, zeroed_raw};
+
+        let mut raw: es_event_exec_t = zeroed_raw();
+        raw.dyld_exec_path = string_token("/bin/sh");
+
+        let event = EventExec { raw: &raw, version: 6 };
+        assert_eq!(event.dyld_exec_path(), None);
+
+        let event = EventExec { raw: &raw, version: 7 };
+        assert_eq!(event.dyld_exec_path(), Some(OsStr::new("/bin/sh")));
+    }
+}
@@ -24,9 +24,193 @@ impl<'a> EventFcntl<'a> {
     pub fn cmd(&self) -> i32 {
         self.raw.cmd
     }
+
+    /// The cmd argument given to fcntl(2), decoded from [`Self::cmd()`].
+    #[inline(always)]
+    pub fn cmd_typed(&self) -> FcntlCmd {
+        FcntlCmd::from(self.cmd())
+    }
 }
 
 // Safety: safe to send across threads: does not contain any interior mutability nor depend on current thread state
 unsafe impl Send for EventFcntl<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventFcntl<'a>; target, cmd);
+
+// Darwin specific commands of `<sys/fcntl.h>`, not all present in `libc`
+/// `F_NOCACHE`
+const F_NOCACHE: i32 = 48;
+/// `F_GETPATH`
+const F_GETPATH: i32 = 50;
+/// `F_FULLFSYNC`
+const F_FULLFSYNC: i32 = 51;
+/// `F_ADDSIGS`
+const F_ADDSIGS: i32 = 59;
+/// `F_ADDFILESIGS`
+const F_ADDFILESIGS: i32 = 61;
+/// `F_FINDSIGS`
+const F_FINDSIGS: i32 = 78;
+/// `F_ADDFILESIGS_FOR_DYLD_SIM`
+const F_ADDFILESIGS_FOR_DYLD_SIM: i32 = 83;
+/// `F_ADDFILESIGS_RETURN`
+const F_ADDFILESIGS_RETURN: i32 = 97;
+/// `F_CHECK_LV`
+const F_CHECK_LV: i32 = 98;
+
+/// Common commands of fcntl(2), the `F_*` constants of `<sys/fcntl.h>`, see
+/// [`EventFcntl::cmd_typed()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FcntlCmd {
+    /// `F_DUPFD`, duplicate the file descriptor
+    DupFd,
+    /// `F_DUPFD_CLOEXEC`, duplicate the file descriptor and set close-on-exec on the copy
+    DupFdCloexec,
+    /// `F_GETFD`, get the file descriptor flags
+    GetFd,
+    /// `F_SETFD`, set the file descriptor flags
+    SetFd,
+    /// `F_GETFL`, get the file status flags
+    GetFl,
+    /// `F_SETFL`, set the file status flags
+    SetFl,
+    /// `F_GETOWN`, get the process or process group receiving `SIGIO`/`SIGURG`
+    GetOwn,
+    /// `F_SETOWN`, set the process or process group receiving `SIGIO`/`SIGURG`
+    SetOwn,
+    /// `F_GETLK`, get the first lock blocking a lock description
+    GetLk,
+    /// `F_SETLK`, set or clear a lock, without waiting
+    SetLk,
+    /// `F_SETLKW`, set or clear a lock, waiting for conflicting locks to be released
+    SetLkw,
+    /// `F_NOCACHE`, turn data caching off or on
+    NoCache,
+    /// `F_GETPATH`, get the path of the file descriptor
+    GetPath,
+    /// `F_FULLFSYNC`, flush all data to the permanent storage device
+    FullFsync,
+    /// `F_ADDSIGS`, add a detached code signature to the file
+    AddSigs,
+    /// `F_ADDFILESIGS`, add a code signature embedded in the file
+    AddFileSigs,
+    /// `F_FINDSIGS`, find the code signature in the file
+    FindSigs,
+    /// `F_ADDFILESIGS_FOR_DYLD_SIM`, add a code signature for the dyld simulator
+    AddFileSigsForDyldSim,
+    /// `F_ADDFILESIGS_RETURN`, add a code signature embedded in the file and return the end offset
+    /// of the signed range
+    AddFileSigsReturn,
+    /// `F_CHECK_LV`, check if the file can be loaded under library validation
+    CheckLv,
+    /// Any other command
+    Other(i32),
+}
+
+impl FcntlCmd {
+    /// `true` for the commands registering or looking up code signatures (`F_ADDSIGS`,
+    /// `F_ADDFILESIGS*`, `F_FINDSIGS` and `F_CHECK_LV`).
+    pub fn is_codesigning(&self) -> bool {
+        matches!(
+            self,
+            Self::AddSigs
+                | Self::AddFileSigs
+                | Self::FindSigs
+                | Self::AddFileSigsForDyldSim
+                | Self::AddFileSigsReturn
+                | Self::CheckLv
+        )
+    }
+
+    /// Raw command value.
+    pub fn as_raw(&self) -> i32 {
+        match *self {
+            Self::DupFd => libc::F_DUPFD,
+            Self::DupFdCloexec => libc::F_DUPFD_CLOEXEC,
+            Self::GetFd => libc::F_GETFD,
+            Self::SetFd => libc::F_SETFD,
+            Self::GetFl => libc::F_GETFL,
+            Self::SetFl => libc::F_SETFL,
+            Self::GetOwn => libc::F_GETOWN,
+            Self::SetOwn => libc::F_SETOWN,
+            Self::GetLk => libc::F_GETLK,
+            Self::SetLk => libc::F_SETLK,
+            Self::SetLkw => libc::F_SETLKW,
+            Self::NoCache => F_NOCACHE,
+            Self::GetPath => F_GETPATH,
+            Self::FullFsync => F_FULLFSYNC,
+            Self::AddSigs => F_ADDSIGS,
+            Self::AddFileSigs => F_ADDFILESIGS,
+            Self::FindSigs => F_FINDSIGS,
+            Self::AddFileSigsForDyldSim => F_ADDFILESIGS_FOR_DYLD_SIM,
+            Self::AddFileSigsReturn => F_ADDFILESIGS_RETURN,
+            Self::CheckLv => F_CHECK_LV,
+            Self::Other(cmd) => cmd,
+        }
+    }
+}
+
+impl From<i32> for FcntlCmd {
+    fn from(cmd: i32) -> Self {
+        match cmd {
+            libc::F_DUPFD => Self::DupFd,
+            libc::F_DUPFD_CLOEXEC => Self::DupFdCloexec,
+            libc::F_GETFD => Self::GetFd,
+            libc::F_SETFD => Self::SetFd,
+            libc::F_GETFL => Self::GetFl,
+            libc::F_SETFL => Self::SetFl,
+            libc::F_GETOWN => Self::GetOwn,
+            libc::F_SETOWN => Self::SetOwn,
+            libc::F_GETLK => Self::GetLk,
+            libc::F_SETLK => Self::SetLk,
+            libc::F_SETLKW => Self::SetLkw,
+            F_NOCACHE => Self::NoCache,
+            F_GETPATH => Self::GetPath,
+            F_FULLFSYNC => Self::FullFsync,
+            F_ADDSIGS => Self::AddSigs,
+            F_ADDFILESIGS => Self::AddFileSigs,
+            F_FINDSIGS => Self::FindSigs,
+            F_ADDFILESIGS_FOR_DYLD_SIM => Self::AddFileSigsForDyldSim,
+            F_ADDFILESIGS_RETURN => Self::AddFileSigsReturn,
+            F_CHECK_LV => Self::CheckLv,
+            other => Self::Other(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::ShouldNotBeNull;
+
+    use super::*;
+    use crate::utils::{file_with_path, zeroed_raw};
+
+    #[test]
+    fn test_fcntl_cmd() {
+        let mut target = file_with_path("/tmp/payload.dylib");
+
+        let mut raw: es_event_fcntl_t = zeroed_raw();
+        raw.target = ShouldNotBeNull::new(&mut target);
+        raw.cmd = F_ADDSIGS;
+
+        let event = EventFcntl { raw: &raw };
+        assert_eq!(event.target().path(), "/tmp/payload.dylib");
+        assert_eq!(event.cmd_typed(), FcntlCmd::AddSigs);
+        assert!(event.cmd_typed().is_codesigning());
+
+        raw.cmd = libc::F_SETFL;
+        let event = EventFcntl { raw: &raw };
+        assert_eq!(event.cmd_typed(), FcntlCmd::SetFl);
+        assert!(event.cmd_typed().is_codesigning() == false);
+
+        assert_eq!(FcntlCmd::from(libc::F_GETFL), FcntlCmd::GetFl);
+        assert_eq!(FcntlCmd::from(libc::F_GETLK), FcntlCmd::GetLk);
+        assert_eq!(FcntlCmd::from(libc::F_SETLK), FcntlCmd::SetLk);
+        assert_eq!(FcntlCmd::from(F_CHECK_LV), FcntlCmd::CheckLv);
+        assert_eq!(FcntlCmd::from(1000), FcntlCmd::Other(1000));
+        assert_eq!(FcntlCmd::from(-1), FcntlCmd::Other(-1));
+
+        for cmd in -1..=110 {
+            assert_eq!(FcntlCmd::from(cmd).as_raw(), cmd);
+        }
+    }
+}
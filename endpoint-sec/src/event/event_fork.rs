@@ -5,6 +5,68 @@ use endpoint_sec_sys::es_event_fork_t;
 use crate::Process;
 
 /// Fork a new process event.
+///
+/// ## Building a process tree
+///
+/// Process trees are built by correlating `fork` events (a child is created) with `exec` events
+/// (the image of a process is replaced). Processes are identified by the `pid` and `pidversion` of
+/// their [`AuditToken`][crate::AuditToken]: the `pid` alone is reused by the OS. The traps are:
+///
+/// - In a fork event, [`Message::process()`][crate::Message::process] is the parent and
+///   [`EventFork::child()`] the new child.
+/// - In an exec event, `Message::process()` is the process **before** the exec (e.g. the forked
+///   child) and [`EventExec::target()`][crate::EventExec::target] the process **after** it. Both
+///   share the same `pid` but **not** the same `pidversion`, the new one must replace the old one
+///   in the tree.
+///
+/// ```no_run
+/// use std::collections::HashMap;
+/// use std::ffi::OsString;
+///
+/// use endpoint_sec::{AuditToken, Event, Message};
+///
+/// /// Identity of a process: `pid` and `pidversion`
+/// type ProcessKey = (i32, i32);
+///
+/// fn key(token: &AuditToken) -> ProcessKey {
+///     (token.pid(), token.pidversion())
+/// }
+///
+/// #[derive(Default)]
+/// struct ProcessTree {
+///     parents: HashMap<ProcessKey, ProcessKey>,
+///     executables: HashMap<ProcessKey, OsString>,
+/// }
+///
+/// impl ProcessTree {
+///     fn handle(&mut self, message: &Message) {
+///         let process = key(&message.process().audit_token());
+///
+///         match message.event() {
+///             Some(Event::NotifyFork(fork)) => {
+///                 let child = key(&fork.child().audit_token());
+///                 self.parents.insert(child, process);
+///             },
+///             Some(Event::NotifyExec(exec)) => {
+///                 let target = exec.target();
+///                 let after = key(&target.audit_token());
+///
+///                 // Same process, new identity: move its parent link
+///                 if let Some(parent) = self.parents.remove(&process) {
+///                     self.parents.insert(after, parent);
+///                 }
+///                 self.executables.remove(&process);
+///                 self.executables.insert(after, target.executable().path().into());
+///             },
+///             Some(Event::NotifyExit(_)) => {
+///                 self.parents.remove(&process);
+///                 self.executables.remove(&process);
+///             },
+///             _ => (),
+///         }
+///     }
+/// }
+/// ```
 #[doc(alias = "es_event_fork_t")]
 pub struct EventFork<'a> {
     /// The raw reference.
@@ -0,0 +1,154 @@
+//! [`EventGatekeeperUserOverride`]
+
+use std::ffi::OsStr;
+
+use endpoint_sec_sys::{
+    es_event_gatekeeper_user_override_t, es_gatekeeper_user_override_file_type_t, es_sha256_t, es_signed_file_info_t,
+};
+
+use crate::File;
+
+/// Notification for a Gatekeeper override initiated by the user.
+#[doc(alias = "es_event_gatekeeper_user_override_t")]
+pub struct EventGatekeeperUserOverride<'a> {
+    /// Raw event
+    pub(crate) raw: &'a es_event_gatekeeper_user_override_t,
+}
+
+/// File designated by an [`EventGatekeeperUserOverride`].
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[doc(alias = "es_gatekeeper_user_override_file_type_t")]
+pub enum GatekeeperUserOverrideFile<'a> {
+    /// Only the path of the file is known.
+    Path(&'a OsStr),
+    /// The file could be looked up.
+    File(File<'a>),
+}
+
+impl<'a> EventGatekeeperUserOverride<'a> {
+    /// The file that the user overrode the Gatekeeper decision for.
+    #[inline(always)]
+    pub fn file(&self) -> Option<GatekeeperUserOverrideFile<'a>> {
+        match self.raw.file_type {
+            es_gatekeeper_user_override_file_type_t::ES_GATEKEEPER_USER_OVERRIDE_FILE_TYPE_PATH => {
+                Some(GatekeeperUserOverrideFile::Path(
+                    // Safety: Safe as we select the union field corresponding to that type.
+                    unsafe { self.raw.file.file_path.as_os_str() },
+                ))
+            },
+            es_gatekeeper_user_override_file_type_t::ES_GATEKEEPER_USER_OVERRIDE_FILE_TYPE_FILE => {
+                Some(GatekeeperUserOverrideFile::File(
+                    // Safety: Safe as we select the union field corresponding to that type.
+                    File::new(unsafe { self.raw.file.file.as_ref() }),
+                ))
+            },
+            _ => None,
+        }
+    }
+
+    /// SHA-256 of the file, if available.
+    #[inline(always)]
+    pub fn sha256(&self) -> Option<&'a es_sha256_t> {
+        // Safety: 'a tied to self, object obtained through ES
+        unsafe { self.raw.sha256() }
+    }
+
+    /// Code signing information of the file, present when it is signed.
+    #[inline(always)]
+    pub fn signing_info(&self) -> Option<SignedFileInfo<'a>> {
+        // Safety: 'a tied to self, object obtained through ES
+        unsafe { self.raw.signing_info() }.map(|raw| SignedFileInfo { raw })
+    }
+}
+
+// Safety: safe to send across threads: does not contain any interior mutability nor depend on current thread state
+unsafe impl Send for EventGatekeeperUserOverride<'_> {}
+
+impl_debug_eq_hash_with_functions!(EventGatekeeperUserOverride<'a>; file, sha256, signing_info);
+
+/// Code signing information of a file in an [`EventGatekeeperUserOverride`].
+#[doc(alias = "es_signed_file_info_t")]
+pub struct SignedFileInfo<'a> {
+    /// Raw information
+    pub(crate) raw: &'a es_signed_file_info_t,
+}
+
+impl<'a> SignedFileInfo<'a> {
+    /// Code directory hash of the file.
+    #[inline(always)]
+    pub fn cdhash(&self) -> [u8; 20] {
+        self.raw.cdhash
+    }
+
+    /// Signing id of the file.
+    #[inline(always)]
+    pub fn signing_id(&self) -> &'a OsStr {
+        // Safety: 'a tied to self, object obtained through ES
+        unsafe { self.raw.signing_id.as_os_str() }
+    }
+
+    /// Team id of the file.
+    #[inline(always)]
+    pub fn team_id(&self) -> &'a OsStr {
+        // Safety: 'a tied to self, object obtained through ES
+        unsafe { self.raw.team_id.as_os_str() }
+    }
+}
+
+// Safety: safe to send across threads: does not contain any interior mutability nor depend on current thread state
+unsafe impl Send for SignedFileInfo<'_> {}
+
+impl_debug_eq_hash_with_functions!(SignedFileInfo<'a>; cdhash, signing_id, team_id);
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    use endpoint_sec_sys::ShouldNotBeNull;
+
+    use super::*;
+    use crate::utils::{file_with_path, string_token, zeroed_raw};
+
+    #[test]
+    fn test_gatekeeper_user_override() {
+        let mut sha256: es_sha256_t = [0xab; 32];
+        let mut signing_info = es_signed_file_info_t {
+            cdhash: [0x12; 20],
+            signing_id: string_token("com.example.app"),
+            team_id: string_token("TEAMID1234"),
+        };
+
+        let mut raw: es_event_gatekeeper_user_override_t = zeroed_raw();
+        raw.file_type = es_gatekeeper_user_override_file_type_t::ES_GATEKEEPER_USER_OVERRIDE_FILE_TYPE_PATH;
+        raw.file.file_path = ManuallyDrop::new(string_token("/Applications/App.app"));
+
+        let event = EventGatekeeperUserOverride { raw: &raw };
+        assert_eq!(
+            event.file(),
+            Some(GatekeeperUserOverrideFile::Path(OsStr::new("/Applications/App.app")))
+        );
+        assert_eq!(event.sha256(), None);
+        assert!(event.signing_info().is_none());
+
+        let mut file = file_with_path("/Applications/App.app");
+        raw.file_type = es_gatekeeper_user_override_file_type_t::ES_GATEKEEPER_USER_OVERRIDE_FILE_TYPE_FILE;
+        raw.file.file = ShouldNotBeNull::new(&mut file);
+        raw.sha256 = &mut sha256;
+        raw.signing_info = &mut signing_info;
+
+        let event = EventGatekeeperUserOverride { raw: &raw };
+        match event.file() {
+            Some(GatekeeperUserOverrideFile::File(f)) => assert_eq!(f.path(), "/Applications/App.app"),
+            _ => panic!("expected a file"),
+        }
+        assert_eq!(event.sha256(), Some(&[0xab; 32]));
+        let info = event.signing_info().unwrap();
+        assert_eq!(info.cdhash(), [0x12; 20]);
+        assert_eq!(info.signing_id(), "com.example.app");
+        assert_eq!(info.team_id(), "TEAMID1234");
+
+        raw.file_type = es_gatekeeper_user_override_file_type_t(2);
+        let event = EventGatekeeperUserOverride { raw: &raw };
+        assert_eq!(event.file(), None);
+    }
+}
@@ -16,13 +16,11 @@ pub struct EventGetTask<'a> {
 
 impl<'a> EventGetTask<'a> {
     /// Type indicating how the process is obtaining the task port for the target process on version 5 or later, otherwise None.
+    ///
+    /// Same as [`Self::get_task_type()`].
     #[inline(always)]
     pub fn task_type(&self) -> Option<es_get_task_type_t> {
-        if self.version >= 5 {
-            Some(self.raw.type_)
-        } else {
-            None
-        }
+        self.get_task_type()
     }
 
     /// The process for which the task control port will be retrieved.
@@ -33,7 +31,39 @@ impl<'a> EventGetTask<'a> {
     }
 }
 
+impl_get_task_type!(EventGetTask);
+
 // Safety: safe to send across threads: does not contain any interior mutability nor depend on current thread state
 unsafe impl Send for EventGetTask<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventGetTask<'a> with version; task_type, target);
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::{es_process_t, ShouldNotBeNull};
+
+    use super::*;
+    use crate::utils::zeroed_raw;
+
+    #[test]
+    fn test_get_task_type() {
+        let mut target: es_process_t = zeroed_raw();
+        target.ppid = 42;
+
+        let mut raw: es_event_get_task_t = zeroed_raw();
+        raw.target = ShouldNotBeNull::new(&mut target);
+        raw.type_ = es_get_task_type_t::ES_GET_TASK_TYPE_TASK_FOR_PID;
+
+        let event = EventGetTask { raw: &raw, version: 4 };
+        assert_eq!(event.get_task_type(), None);
+        assert_eq!(event.task_type(), None);
+        assert_eq!(event.target().ppid(), 42);
+
+        let event = EventGetTask { raw: &raw, version: 5 };
+        assert_eq!(
+            event.get_task_type(),
+            Some(es_get_task_type_t::ES_GET_TASK_TYPE_TASK_FOR_PID)
+        );
+        assert_eq!(event.task_type(), event.get_task_type());
+    }
+}
@@ -26,17 +26,15 @@ impl<'a> EventGetTaskInspect<'a> {
 
     /// Indicates how the process is obtaining the task for the target process.
     ///
-    /// Note: only available if message version >= 5.
+    /// Note: only available if message version >= 5. Same as [`Self::get_task_type()`].
     #[inline(always)]
     pub fn type_(&self) -> Option<es_get_task_type_t> {
-        if self.version < 5 {
-            None
-        } else {
-            Some(self.raw.type_)
-        }
+        self.get_task_type()
     }
 }
 
+impl_get_task_type!(EventGetTaskInspect);
+
 // Safety: safe to send across threads: does not contain any interior mutability nor depend on current thread state
 unsafe impl Send for EventGetTaskInspect<'_> {}
 
@@ -20,7 +20,7 @@ impl<'a> EventIoKitOpen<'a> {
         self.raw.user_client_type
     }
 
-    /// The name of the new object linked to the source file.
+    /// Meta class name of the user client instance.
     #[inline(always)]
     pub fn user_client_class(&self) -> &'a OsStr {
         // Safety: 'a tied to self, object obtained through ES
@@ -32,3 +32,20 @@ impl<'a> EventIoKitOpen<'a> {
 unsafe impl Send for EventIoKitOpen<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventIoKitOpen<'a>; user_client_type, user_client_class);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{string_token, zeroed_raw};
+
+    #[test]
+    fn test_iokit_open() {
+        let mut raw: es_event_iokit_open_t = zeroed_raw();
+        raw.user_client_type = 42;
+        raw.user_client_class = string_token("IOHIDLibUserClient");
+
+        let event = EventIoKitOpen { raw: &raw };
+        assert_eq!(event.user_client_type(), 42);
+        assert_eq!(event.user_client_class(), OsStr::new("IOHIDLibUserClient"));
+    }
+}
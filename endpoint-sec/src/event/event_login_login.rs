@@ -18,11 +18,11 @@ impl<'a> EventLoginLogin<'a> {
         self.raw.success
     }
 
-    /// Optional. Failure message generated.
+    /// Optional. Failure message generated, `None` if empty.
     #[inline(always)]
-    pub fn failure_message(&self) -> &'a OsStr {
+    pub fn failure_message(&self) -> Option<&'a OsStr> {
         // Safety: 'a tied to self, object obtained through ES
-        unsafe { self.raw.failure_message.as_os_str() }
+        unsafe { self.raw.failure_message.as_opt_os_str() }
     }
 
     /// Username used for login.
@@ -54,3 +54,38 @@ impl_debug_eq_hash_with_functions!(
     EventLoginLogin<'a>;
     success, failure_message, username, has_uid, uid,
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{string_token, zeroed_raw};
+
+    #[test]
+    fn test_login_login_failure() {
+        let mut raw: es_event_login_login_t = zeroed_raw();
+        raw.failure_message = string_token("Invalid password");
+        raw.username = string_token("username");
+
+        let event = EventLoginLogin { raw: &raw };
+        assert!(event.success() == false);
+        assert_eq!(event.failure_message(), Some(OsStr::new("Invalid password")));
+        assert_eq!(event.username(), OsStr::new("username"));
+        assert!(event.has_uid() == false);
+        assert_eq!(event.uid(), None);
+    }
+
+    #[test]
+    fn test_login_login_success() {
+        let mut raw: es_event_login_login_t = zeroed_raw();
+        raw.success = true;
+        raw.username = string_token("username");
+        raw.has_uid = true;
+        raw.anon0.uid = 501;
+
+        let event = EventLoginLogin { raw: &raw };
+        assert!(event.success());
+        assert_eq!(event.failure_message(), None);
+        assert!(event.has_uid());
+        assert_eq!(event.uid(), Some(501));
+    }
+}
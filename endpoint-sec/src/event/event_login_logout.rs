@@ -30,3 +30,20 @@ impl<'a> EventLoginLogout<'a> {
 unsafe impl Send for EventLoginLogout<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventLoginLogout<'a>; username, uid);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{string_token, zeroed_raw};
+
+    #[test]
+    fn test_login_logout() {
+        let mut raw: es_event_login_logout_t = zeroed_raw();
+        raw.username = string_token("username");
+        raw.uid = 501;
+
+        let event = EventLoginLogout { raw: &raw };
+        assert_eq!(event.username(), OsStr::new("username"));
+        assert_eq!(event.uid(), 501);
+    }
+}
@@ -2,7 +2,7 @@
 
 use endpoint_sec_sys::es_event_mmap_t;
 
-use crate::File;
+use crate::{File, MmapFlags, VmProt};
 
 /// Memory map a file event.
 #[doc(alias = "es_event_mmap_t")]
@@ -30,6 +30,33 @@ impl<'a> EventMmap<'a> {
         self.raw.flags
     }
 
+    /// The protection (region accessibility) value, as [`VmProt`].
+    #[inline(always)]
+    pub fn protection_typed(&self) -> VmProt {
+        VmProt(self.raw.protection)
+    }
+
+    /// The maximum allowed protection value the operating system will respect, as [`VmProt`].
+    #[inline(always)]
+    pub fn max_protection_typed(&self) -> VmProt {
+        VmProt(self.raw.max_protection)
+    }
+
+    /// The type and attributes of the mapped file, as [`MmapFlags`].
+    #[inline(always)]
+    pub fn flags_typed(&self) -> MmapFlags {
+        MmapFlags(self.raw.flags)
+    }
+
+    /// `true` if the mapping is requested both writable and executable.
+    ///
+    /// Only the requested protection is checked, see [`Self::max_protection_typed()`] for what
+    /// the protection of the mapping can be changed to later.
+    #[inline(always)]
+    pub fn is_write_exec(&self) -> bool {
+        self.protection_typed().is_write_exec()
+    }
+
     /// The offset into the source file that will be mapped.
     #[inline(always)]
     pub fn file_pos(&self) -> u64 {
@@ -48,3 +75,27 @@ impl<'a> EventMmap<'a> {
 unsafe impl Send for EventMmap<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventMmap<'a>; protection, max_protection, flags, file_pos, source);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::zeroed_raw;
+
+    #[test]
+    fn test_is_write_exec() {
+        let mut raw: es_event_mmap_t = zeroed_raw();
+        raw.protection = libc::PROT_READ | libc::PROT_WRITE;
+        raw.max_protection = libc::PROT_READ | libc::PROT_WRITE | libc::PROT_EXEC;
+        raw.flags = libc::MAP_PRIVATE | libc::MAP_JIT;
+
+        let event = EventMmap { raw: &raw };
+        assert!(event.is_write_exec() == false);
+        assert!(event.max_protection_typed().is_write_exec());
+        assert_eq!(event.flags_typed(), MmapFlags::MAP_PRIVATE | MmapFlags::MAP_JIT);
+
+        raw.protection |= libc::PROT_EXEC;
+        let event = EventMmap { raw: &raw };
+        assert!(event.is_write_exec());
+        assert_eq!(event.protection_typed(), VmProt(event.protection()));
+    }
+}
@@ -22,3 +22,5 @@ impl<'a> EventMount<'a> {
 unsafe impl Send for EventMount<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventMount<'a>; statfs);
+
+impl_statfs_accessors!(EventMount);
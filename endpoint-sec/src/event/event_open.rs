@@ -1,6 +1,6 @@
 //! [`EventOpen`]
 
-use endpoint_sec_sys::es_event_open_t;
+use endpoint_sec_sys::{es_event_open_t, FREAD, FWRITE};
 
 use crate::File;
 
@@ -13,11 +13,50 @@ pub struct EventOpen<'a> {
 
 impl<'a> EventOpen<'a> {
     /// The desired **kernel** flags to be used when opening the file.
+    ///
+    /// Kernel flags differ from the `O_*` flags given to `open(2)` for the access mode: a read
+    /// is [`FREAD`] and a write is [`FWRITE`], so `O_RDWR` is `FREAD | FWRITE`. The other flags
+    /// (`O_APPEND`, `O_CREAT`, ...) keep their values.
+    ///
+    /// Responding to an `AUTH_OPEN` event with
+    /// [`Client::respond_flags_result()`][crate::Client::respond_flags_result] uses the same
+    /// kernel flags: the open is allowed only if all the flags requested here are present in the
+    /// response, see [`ExpectedResponseType::Flags`][crate::ExpectedResponseType::Flags].
     #[inline(always)]
     pub fn fflag(&self) -> i32 {
         self.raw.fflag
     }
 
+    /// `true` if the file is opened for reading (`FREAD`).
+    #[inline(always)]
+    pub fn is_read(&self) -> bool {
+        self.raw.fflag & FREAD != 0
+    }
+
+    /// `true` if the file is opened for writing (`FWRITE`).
+    #[inline(always)]
+    pub fn is_write(&self) -> bool {
+        self.raw.fflag & FWRITE != 0
+    }
+
+    /// `true` if writes append to the file (`O_APPEND`).
+    #[inline(always)]
+    pub fn is_append(&self) -> bool {
+        self.raw.fflag & libc::O_APPEND != 0
+    }
+
+    /// `true` if the file is created if it does not exist (`O_CREAT`).
+    #[inline(always)]
+    pub fn is_create(&self) -> bool {
+        self.raw.fflag & libc::O_CREAT != 0
+    }
+
+    /// `true` if the file is truncated to a size of 0 (`O_TRUNC`).
+    #[inline(always)]
+    pub fn is_truncate(&self) -> bool {
+        self.raw.fflag & libc::O_TRUNC != 0
+    }
+
     /// The file that will be opened.
     #[inline(always)]
     pub fn file(&self) -> File<'a> {
@@ -30,3 +69,42 @@ impl<'a> EventOpen<'a> {
 unsafe impl Send for EventOpen<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventOpen<'a>; fflag, file);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::zeroed_raw;
+
+    #[test]
+    fn test_fflag_predicates() {
+        let mut raw: es_event_open_t = zeroed_raw();
+
+        let predicates = |raw: &es_event_open_t| {
+            let event = EventOpen { raw };
+            [
+                event.is_read(),
+                event.is_write(),
+                event.is_append(),
+                event.is_create(),
+                event.is_truncate(),
+            ]
+        };
+
+        assert_eq!(predicates(&raw), [false; 5]);
+
+        raw.fflag = FREAD;
+        assert_eq!(predicates(&raw), [true, false, false, false, false]);
+        raw.fflag = FWRITE;
+        assert_eq!(predicates(&raw), [false, true, false, false, false]);
+        raw.fflag = FWRITE | libc::O_APPEND;
+        assert_eq!(predicates(&raw), [false, true, true, false, false]);
+        raw.fflag = FREAD | FWRITE | libc::O_CREAT;
+        assert_eq!(predicates(&raw), [true, true, false, true, false]);
+        raw.fflag = FWRITE | libc::O_CREAT | libc::O_TRUNC;
+        assert_eq!(predicates(&raw), [false, true, false, true, true]);
+
+        // `O_RDONLY` is 0, only the kernel flag means a read
+        raw.fflag = libc::O_RDONLY;
+        assert_eq!(predicates(&raw), [false; 5]);
+    }
+}
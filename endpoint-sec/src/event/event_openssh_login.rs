@@ -5,7 +5,7 @@ use std::net::IpAddr;
 use std::str::FromStr;
 
 use endpoint_sec_sys::{
-    es_address_type_t, es_event_openssh_login_t, es_openssh_login_result_type_t, uid_t,
+    es_address_type_t, es_event_openssh_login_t, es_openssh_login_result_type_t, uid_t, OpensshLoginError,
 };
 
 /// OpenSSH login event.
@@ -28,6 +28,14 @@ impl<'a> EventOpensshLogin<'a> {
         self.raw.result_type
     }
 
+    /// Result of the login attempt, `Ok(())` for [`ES_OPENSSH_AUTH_SUCCESS`].
+    ///
+    /// [`ES_OPENSSH_AUTH_SUCCESS`]: es_openssh_login_result_type_t::ES_OPENSSH_AUTH_SUCCESS
+    #[inline(always)]
+    pub fn result(&self) -> Result<(), OpensshLoginError> {
+        self.result_type().ok()
+    }
+
     /// Type of source address.
     #[inline(always)]
     pub fn source_address_type(&self) -> es_address_type_t {
@@ -77,3 +85,57 @@ impl_debug_eq_hash_with_functions!(
     EventOpensshLogin<'a>;
     success, result_type, source_address_type, source_address, username, has_uid, uid,
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{string_token, zeroed_raw};
+
+    /// Raw login event for `username` from `127.0.0.1`, without uid
+    fn raw_login(result_type: es_openssh_login_result_type_t) -> es_event_openssh_login_t {
+        let mut raw: es_event_openssh_login_t = zeroed_raw();
+        raw.success = result_type == es_openssh_login_result_type_t::ES_OPENSSH_AUTH_SUCCESS;
+        raw.result_type = result_type;
+        raw.source_address_type = es_address_type_t::ES_ADDRESS_TYPE_IPV4;
+        raw.source_address = string_token("127.0.0.1");
+        raw.username = string_token("username");
+        raw
+    }
+
+    #[test]
+    fn test_openssh_login_success() {
+        let mut raw = raw_login(es_openssh_login_result_type_t::ES_OPENSSH_AUTH_SUCCESS);
+        raw.has_uid = true;
+        raw.anon0.uid = 501;
+
+        let event = EventOpensshLogin { raw: &raw };
+        assert!(event.success());
+        assert_eq!(event.result(), Ok(()));
+        assert_eq!(event.source_address_type(), es_address_type_t::ES_ADDRESS_TYPE_IPV4);
+        assert_eq!(event.source_address(), OsStr::new("127.0.0.1"));
+        assert_eq!(event.source_address_std(), Some(IpAddr::from([127, 0, 0, 1])));
+        assert_eq!(event.username(), OsStr::new("username"));
+        assert!(event.has_uid());
+        assert_eq!(event.uid(), Some(501));
+    }
+
+    #[test]
+    fn test_openssh_login_failure() {
+        let mut raw = raw_login(es_openssh_login_result_type_t::ES_OPENSSH_AUTH_FAIL_PASSWD);
+        // Not read since `has_uid` is false
+        raw.anon0.uid = 501;
+
+        let event = EventOpensshLogin { raw: &raw };
+        assert!(event.success() == false);
+        assert_eq!(event.result(), Err(OpensshLoginError::AuthPassword));
+        assert!(event.has_uid() == false);
+        assert_eq!(event.uid(), None);
+
+        let raw = raw_login(es_openssh_login_result_type_t(1000));
+        let event = EventOpensshLogin { raw: &raw };
+        assert_eq!(
+            event.result(),
+            Err(OpensshLoginError::Unknown(es_openssh_login_result_type_t(1000)))
+        );
+    }
+}
@@ -55,3 +55,25 @@ impl_debug_eq_hash_with_functions!(
     EventOpensshLogout<'a>;
     source_address_type, source_address, username, uid,
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{string_token, zeroed_raw};
+
+    #[test]
+    fn test_openssh_logout() {
+        let mut raw: es_event_openssh_logout_t = zeroed_raw();
+        raw.source_address_type = es_address_type_t::ES_ADDRESS_TYPE_IPV6;
+        raw.source_address = string_token("::1");
+        raw.username = string_token("username");
+        raw.uid = 501;
+
+        let event = EventOpensshLogout { raw: &raw };
+        assert_eq!(event.source_address_type(), es_address_type_t::ES_ADDRESS_TYPE_IPV6);
+        assert_eq!(event.source_address(), OsStr::new("::1"));
+        assert_eq!(event.source_address_std(), Some(IpAddr::from([0, 0, 0, 0, 0, 0, 0, 1])));
+        assert_eq!(event.username(), OsStr::new("username"));
+        assert_eq!(event.uid(), 501);
+    }
+}
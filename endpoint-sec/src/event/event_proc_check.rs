@@ -30,14 +30,158 @@ impl<'a> EventProcCheck<'a> {
         self.raw.type_
     }
 
+    /// Type of call number used to check the access on the target process.
+    ///
+    /// Same as [`Self::type_()`].
+    #[inline(always)]
+    pub fn proc_check_type(&self) -> es_proc_check_type_t {
+        self.type_()
+    }
+
     /// Flavor used to check the access on the target process.
+    ///
+    /// Its meaning depends on the [type of check][Self::proc_check_type]: for example `1` is
+    /// `PROC_PIDLISTFDS` for `ES_PROC_CHECK_TYPE_PIDINFO` but `PROC_ALL_PIDS` for
+    /// `ES_PROC_CHECK_TYPE_LISTPIDS`. See [`Self::flavor_name()`] for the known names.
     #[inline(always)]
     pub fn flavor(&self) -> i32 {
         self.raw.flavor
     }
+
+    /// Name of the [`flavor`][Self::flavor], as defined in `<sys/proc_info.h>` for the
+    /// [`type_`][Self::type_] of the check (e.g. `PROC_PIDPATHINFO` for
+    /// [`ES_PROC_CHECK_TYPE_PIDINFO`][es_proc_check_type_t::ES_PROC_CHECK_TYPE_PIDINFO]).
+    ///
+    /// `None` if the flavor is unknown or the type of check does not use named flavors.
+    pub fn flavor_name(&self) -> Option<&'static str> {
+        let name = match (self.type_(), self.flavor()) {
+            (es_proc_check_type_t::ES_PROC_CHECK_TYPE_LISTPIDS, flavor) => match flavor {
+                1 => "PROC_ALL_PIDS",
+                2 => "PROC_PGRP_ONLY",
+                3 => "PROC_TTY_ONLY",
+                4 => "PROC_UID_ONLY",
+                5 => "PROC_RUID_ONLY",
+                6 => "PROC_PPID_ONLY",
+                7 => "PROC_KDBG_ONLY",
+                _ => return None,
+            },
+            (es_proc_check_type_t::ES_PROC_CHECK_TYPE_PIDINFO, flavor) => match flavor {
+                1 => "PROC_PIDLISTFDS",
+                2 => "PROC_PIDTASKALLINFO",
+                3 => "PROC_PIDTBSDINFO",
+                4 => "PROC_PIDTASKINFO",
+                5 => "PROC_PIDTHREADINFO",
+                6 => "PROC_PIDLISTTHREADS",
+                7 => "PROC_PIDREGIONINFO",
+                8 => "PROC_PIDREGIONPATHINFO",
+                9 => "PROC_PIDVNODEPATHINFO",
+                10 => "PROC_PIDTHREADPATHINFO",
+                11 => "PROC_PIDPATHINFO",
+                12 => "PROC_PIDWORKQUEUEINFO",
+                13 => "PROC_PIDT_SHORTBSDINFO",
+                14 => "PROC_PIDLISTFILEPORTS",
+                15 => "PROC_PIDTHREADID64INFO",
+                16 => "PROC_PID_RUSAGE",
+                17 => "PROC_PIDUNIQIDENTIFIERINFO",
+                18 => "PROC_PIDT_BSDINFOWITHUNIQID",
+                19 => "PROC_PIDARCHINFO",
+                20 => "PROC_PIDCOALITIONINFO",
+                21 => "PROC_PIDNOTEEXIT",
+                22 => "PROC_PIDREGIONPATHINFO2",
+                23 => "PROC_PIDREGIONPATHINFO3",
+                24 => "PROC_PIDEXITREASONINFO",
+                25 => "PROC_PIDEXITREASONBASICINFO",
+                26 => "PROC_PIDLISTUPTRS",
+                27 => "PROC_PIDLISTDYNKQUEUES",
+                28 => "PROC_PIDLISTTHREADIDS",
+                29 => "PROC_PIDVMRTFAULTINFO",
+                _ => return None,
+            },
+            (es_proc_check_type_t::ES_PROC_CHECK_TYPE_PIDFDINFO, flavor) => match flavor {
+                1 => "PROC_PIDFDVNODEINFO",
+                2 => "PROC_PIDFDVNODEPATHINFO",
+                3 => "PROC_PIDFDSOCKETINFO",
+                4 => "PROC_PIDFDPSEMINFO",
+                5 => "PROC_PIDFDPIPEINFO",
+                6 => "PROC_PIDFDKQUEUEINFO",
+                7 => "PROC_PIDFDATALKINFO",
+                9 => "PROC_PIDFDKQUEUE_EXTINFO",
+                10 => "PROC_PIDFDCHANNELINFO",
+                _ => return None,
+            },
+            (es_proc_check_type_t::ES_PROC_CHECK_TYPE_SETCONTROL, flavor) => match flavor {
+                1 => "PROC_SELFSET_PCONTROL",
+                2 => "PROC_SELFSET_THREADNAME",
+                3 => "PROC_SELFSET_VMRSRCOWNER",
+                4 => "PROC_SELFSET_DELAYIDLESLEEP",
+                _ => return None,
+            },
+            (es_proc_check_type_t::ES_PROC_CHECK_TYPE_PIDFILEPORTINFO, flavor) => match flavor {
+                2 => "PROC_PIDFILEPORTVNODEPATHINFO",
+                3 => "PROC_PIDFILEPORTSOCKETINFO",
+                5 => "PROC_PIDFILEPORTPSHMINFO",
+                6 => "PROC_PIDFILEPORTPIPEINFO",
+                _ => return None,
+            },
+            (es_proc_check_type_t::ES_PROC_CHECK_TYPE_DIRTYCONTROL, flavor) => match flavor {
+                1 => "PROC_DIRTYCONTROL_TRACK",
+                2 => "PROC_DIRTYCONTROL_SET",
+                3 => "PROC_DIRTYCONTROL_GET",
+                4 => "PROC_DIRTYCONTROL_CLEAR",
+                _ => return None,
+            },
+            (es_proc_check_type_t::ES_PROC_CHECK_TYPE_PIDRUSAGE, flavor) => match flavor {
+                0 => "RUSAGE_INFO_V0",
+                1 => "RUSAGE_INFO_V1",
+                2 => "RUSAGE_INFO_V2",
+                3 => "RUSAGE_INFO_V3",
+                4 => "RUSAGE_INFO_V4",
+                5 => "RUSAGE_INFO_V5",
+                6 => "RUSAGE_INFO_V6",
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        Some(name)
+    }
 }
 
 // Safety: safe to send across threads: does not contain any interior mutability nor depend on current thread state
 unsafe impl Send for EventProcCheck<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventProcCheck<'a> with version; target, type_, flavor);
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::es_process_t;
+
+    use super::*;
+    use crate::utils::zeroed_raw;
+
+    #[test]
+    fn test_proc_check() {
+        let mut target: es_process_t = zeroed_raw();
+        target.ppid = 42;
+
+        let mut raw: es_event_proc_check_t = zeroed_raw();
+        raw.type_ = es_proc_check_type_t::ES_PROC_CHECK_TYPE_PIDINFO;
+        raw.flavor = 11;
+
+        // Without target
+        let event = EventProcCheck { raw: &raw, version: 1 };
+        assert!(event.target().is_none());
+        assert_eq!(event.proc_check_type(), es_proc_check_type_t::ES_PROC_CHECK_TYPE_PIDINFO);
+        assert_eq!(event.proc_check_type().to_string(), "pidinfo");
+        assert_eq!(event.flavor(), 11);
+        assert_eq!(event.flavor_name(), Some("PROC_PIDPATHINFO"));
+
+        // With target
+        raw.target = &mut target;
+        raw.type_ = es_proc_check_type_t::ES_PROC_CHECK_TYPE_LISTPIDS;
+        raw.flavor = 1;
+        let event = EventProcCheck { raw: &raw, version: 1 };
+        assert_eq!(event.target().unwrap().ppid(), 42);
+        assert_eq!(event.flavor_name(), Some("PROC_ALL_PIDS"));
+    }
+}
@@ -98,3 +98,38 @@ impl<'a> Profile<'a> {
 unsafe impl Send for Profile<'_> {}
 
 impl_debug_eq_hash_with_functions!(Profile<'a>; identifier, uuid, install_source, organization, display_name, scope);
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::{es_profile_source_t, ShouldNotBeNull};
+
+    use super::*;
+    use crate::utils::{string_token, zeroed_raw};
+
+    #[test]
+    fn test_profile() {
+        let mut profile = es_profile_t {
+            identifier: string_token("com.example.wifi"),
+            uuid: string_token("0F1E2D3C-4B5A-6978-8796-A5B4C3D2E1F0"),
+            install_source: es_profile_source_t::ES_PROFILE_SOURCE_MANAGED,
+            organization: string_token("Example"),
+            display_name: string_token("Wi-Fi"),
+            scope: string_token("System"),
+        };
+
+        let mut raw: es_event_profile_add_t = zeroed_raw();
+        raw.is_update = true;
+        raw.profile = ShouldNotBeNull::new(&mut profile);
+
+        let event = EventProfileAdd { raw: &raw, version: 7 };
+        assert!(event.is_update());
+
+        let profile = event.profile();
+        assert_eq!(profile.identifier(), "com.example.wifi");
+        assert_eq!(profile.uuid(), "0F1E2D3C-4B5A-6978-8796-A5B4C3D2E1F0");
+        assert_eq!(profile.install_source(), es_profile_source_t::ES_PROFILE_SOURCE_MANAGED);
+        assert_eq!(profile.organization(), "Example");
+        assert_eq!(profile.display_name(), "Wi-Fi");
+        assert_eq!(profile.scope(), "System");
+    }
+}
@@ -14,7 +14,7 @@ pub struct EventRemoteThreadCreate<'a> {
 }
 
 impl<'a> EventRemoteThreadCreate<'a> {
-    /// Process for which the task name port will be retrieved.
+    /// Process in which a new thread was created.
     #[inline(always)]
     pub fn target(&self) -> Process<'a> {
         // Safety: 'a tied to self, object obtained through ES
@@ -33,3 +33,46 @@ impl<'a> EventRemoteThreadCreate<'a> {
 unsafe impl Send for EventRemoteThreadCreate<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventRemoteThreadCreate<'a> with version; target, thread_state);
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::{es_process_t, es_thread_state_t, es_token_t, ShouldNotBeNull};
+
+    use super::*;
+    use crate::utils::zeroed_raw;
+
+    #[test]
+    fn test_thread_create() {
+        let mut target: es_process_t = zeroed_raw();
+        target.ppid = 42;
+
+        let mut raw: es_event_remote_thread_create_t = zeroed_raw();
+        raw.target = ShouldNotBeNull::new(&mut target);
+
+        let event = EventRemoteThreadCreate { raw: &raw, version: 4 };
+        assert_eq!(event.target().ppid(), 42);
+        assert!(event.thread_state().is_none());
+    }
+
+    #[test]
+    fn test_thread_create_running() {
+        let mut target: es_process_t = zeroed_raw();
+        let state = [1_u8, 2, 3, 4];
+        let mut thread_state = es_thread_state_t {
+            flavor: 6,
+            state: es_token_t {
+                size: state.len(),
+                data: state.as_ptr(),
+            },
+        };
+
+        let mut raw: es_event_remote_thread_create_t = zeroed_raw();
+        raw.target = ShouldNotBeNull::new(&mut target);
+        raw.thread_state = &mut thread_state;
+
+        let event = EventRemoteThreadCreate { raw: &raw, version: 4 };
+        let thread_state = event.thread_state().unwrap();
+        assert_eq!(thread_state.flavor(), 6);
+        assert_eq!(thread_state.state(), state);
+    }
+}
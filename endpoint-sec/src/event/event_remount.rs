@@ -22,3 +22,5 @@ impl<'a> EventRemount<'a> {
 unsafe impl Send for EventRemount<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventRemount<'a>; statfs);
+
+impl_statfs_accessors!(EventRemount);
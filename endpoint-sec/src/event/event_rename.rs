@@ -65,3 +65,49 @@ impl<'a> EventRename<'a> {
 unsafe impl Send for EventRename<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventRename<'a>; source, destination);
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    use endpoint_sec_sys::{es_event_rename_t_anon_0_anon_0, ShouldNotBeNull};
+
+    use super::*;
+    use crate::utils::{file_with_path, string_token, zeroed_raw};
+
+    #[test]
+    fn test_destination() {
+        let mut source = file_with_path("/tmp/source");
+        let mut target = file_with_path("/tmp/target");
+
+        let mut raw: es_event_rename_t = zeroed_raw();
+        raw.source = ShouldNotBeNull::new(&mut source);
+
+        raw.destination_type = es_destination_type_t::ES_DESTINATION_TYPE_EXISTING_FILE;
+        raw.destination.existing_file = ShouldNotBeNull::new(&mut target);
+        let event = EventRename { raw: &raw };
+        assert_eq!(event.source().path(), "/tmp/source");
+        match event.destination() {
+            Some(EventRenameDestinationFile::ExistingFile(f)) => assert_eq!(f.path(), "/tmp/target"),
+            _ => panic!("expected an existing file"),
+        }
+
+        raw.destination_type = es_destination_type_t::ES_DESTINATION_TYPE_NEW_PATH;
+        raw.destination.new_path = ManuallyDrop::new(es_event_rename_t_anon_0_anon_0 {
+            dir: ShouldNotBeNull::new(&mut target),
+            filename: string_token("name"),
+        });
+        let event = EventRename { raw: &raw };
+        match event.destination() {
+            Some(EventRenameDestinationFile::NewPath { directory, filename }) => {
+                assert_eq!(directory.path(), "/tmp/target");
+                assert_eq!(filename, "name");
+            },
+            _ => panic!("expected a new path"),
+        }
+
+        raw.destination_type = es_destination_type_t(2);
+        let event = EventRename { raw: &raw };
+        assert!(event.destination().is_none());
+    }
+}
@@ -6,7 +6,7 @@ use std::str::FromStr;
 
 use endpoint_sec_sys::{es_address_type_t, es_event_screensharing_attach_t, es_graphical_session_id_t};
 
-/// Screen Sharing has attached from a graphical session..
+/// Screen Sharing has attached from a graphical session.
 #[doc(alias = "es_event_screensharing_attach_t")]
 pub struct EventScreensharingAttach<'a> {
     /// Raw event
@@ -14,7 +14,7 @@ pub struct EventScreensharingAttach<'a> {
 }
 
 impl<'a> EventScreensharingAttach<'a> {
-    /// True iff remediation was successful.
+    /// True iff Screen Sharing successfully attached.
     #[inline(always)]
     pub fn success(&self) -> bool {
         self.raw.success
@@ -28,19 +28,21 @@ impl<'a> EventScreensharingAttach<'a> {
 
     /// Optional. Source address of connection, or empty. Depending on the transport used, the
     /// source address may or may not be available.
+    ///
+    /// `None` when empty.
     #[inline(always)]
-    pub fn source_address(&self) -> &'a OsStr {
+    pub fn source_address(&self) -> Option<&'a OsStr> {
         // Safety: 'a tied to self, object obtained through ES
-        unsafe { self.raw.source_address.as_os_str() }
+        unsafe { self.raw.source_address.as_opt_os_str() }
     }
 
     /// Optional. For screen sharing initiated using an Apple ID (e.g., from Messages or FaceTime),
     /// this is the viewer's (client's) Apple ID. It is not necessarily the Apple ID that invited
-    /// the screen sharing. Empty if unavailable.
+    /// the screen sharing. `None` if unavailable.
     #[inline(always)]
-    pub fn viewer_appleid(&self) -> &'a OsStr {
+    pub fn viewer_appleid(&self) -> Option<&'a OsStr> {
         // Safety: 'a tied to self, object obtained through ES
-        unsafe { self.raw.viewer_appleid.as_os_str() }
+        unsafe { self.raw.viewer_appleid.as_opt_os_str() }
     }
 
     /// Type of authentication.
@@ -50,19 +52,19 @@ impl<'a> EventScreensharingAttach<'a> {
         unsafe { self.raw.authentication_type.as_os_str() }
     }
 
-    /// Optional. Username used for authentication to Screen Sharing. `NULL` if authentication type
+    /// Optional. Username used for authentication to Screen Sharing. `None` if authentication type
     /// doesn't use an username (e.g. simple VNC password).
     #[inline(always)]
-    pub fn authentication_username(&self) -> &'a OsStr {
+    pub fn authentication_username(&self) -> Option<&'a OsStr> {
         // Safety: 'a tied to self, object obtained through ES
-        unsafe { self.raw.authentication_username.as_os_str() }
+        unsafe { self.raw.authentication_username.as_opt_os_str() }
     }
 
-    /// Optional. Username of the loginwindow session if available, `NULL` otherwise.
+    /// Optional. Username of the loginwindow session if available, `None` otherwise.
     #[inline(always)]
-    pub fn session_username(&self) -> &'a OsStr {
+    pub fn session_username(&self) -> Option<&'a OsStr> {
         // Safety: 'a tied to self, object obtained through ES
-        unsafe { self.raw.session_username.as_os_str() }
+        unsafe { self.raw.session_username.as_opt_os_str() }
     }
 
     /// True iff there was an existing user session.
@@ -80,7 +82,7 @@ impl<'a> EventScreensharingAttach<'a> {
     /// Source address as an [`IpAddr`] from the standard library, if possible.
     #[inline(always)]
     pub fn source_address_std(&self) -> Option<IpAddr> {
-        let sa = self.source_address().to_str()?;
+        let sa = self.source_address()?.to_str()?;
         IpAddr::from_str(sa).ok()
     }
 }
@@ -93,3 +95,45 @@ impl_debug_eq_hash_with_functions!(
     success, source_address_type, source_address, viewer_appleid, authentication_type,
     authentication_username, session_username, existing_session, graphical_session_id,
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{string_token, zeroed_raw};
+
+    #[test]
+    fn test_screensharing_attach() {
+        let mut raw: es_event_screensharing_attach_t = zeroed_raw();
+        raw.success = true;
+        raw.source_address_type = es_address_type_t::ES_ADDRESS_TYPE_IPV4;
+        raw.source_address = string_token("192.168.1.2");
+        raw.authentication_type = string_token("vnc");
+        raw.graphical_session_id = 7;
+
+        let event = EventScreensharingAttach { raw: &raw };
+        assert!(event.success());
+        assert_eq!(event.source_address_type(), es_address_type_t::ES_ADDRESS_TYPE_IPV4);
+        assert_eq!(event.source_address(), Some(OsStr::new("192.168.1.2")));
+        assert_eq!(event.source_address_std(), Some(IpAddr::from([192, 168, 1, 2])));
+        assert_eq!(event.authentication_type(), OsStr::new("vnc"));
+        assert_eq!(event.graphical_session_id(), 7);
+
+        // Absent optional fields
+        assert_eq!(event.viewer_appleid(), None);
+        assert_eq!(event.authentication_username(), None);
+        assert_eq!(event.session_username(), None);
+
+        raw.source_address_type = es_address_type_t::ES_ADDRESS_TYPE_NONE;
+        raw.source_address = string_token("");
+        raw.viewer_appleid = string_token("viewer@example.com");
+        raw.authentication_username = string_token("admin");
+        raw.session_username = string_token("user");
+
+        let event = EventScreensharingAttach { raw: &raw };
+        assert_eq!(event.source_address(), None);
+        assert_eq!(event.source_address_std(), None);
+        assert_eq!(event.viewer_appleid(), Some(OsStr::new("viewer@example.com")));
+        assert_eq!(event.authentication_username(), Some(OsStr::new("admin")));
+        assert_eq!(event.session_username(), Some(OsStr::new("user")));
+    }
+}
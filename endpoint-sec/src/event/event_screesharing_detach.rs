@@ -6,7 +6,7 @@ use std::str::FromStr;
 
 use endpoint_sec_sys::{es_address_type_t, es_event_screensharing_detach_t, es_graphical_session_id_t};
 
-/// Screen Sharing has detached from a graphical session..
+/// Screen Sharing has detached from a graphical session.
 #[doc(alias = "es_event_screensharing_detach_t")]
 pub struct EventScreensharingDetach<'a> {
     /// Raw event
@@ -22,19 +22,21 @@ impl<'a> EventScreensharingDetach<'a> {
 
     /// Optional. Source address of connection, or empty. Depending on the transport used, the
     /// source address may or may not be available.
+    ///
+    /// `None` when empty.
     #[inline(always)]
-    pub fn source_address(&self) -> &'a OsStr {
+    pub fn source_address(&self) -> Option<&'a OsStr> {
         // Safety: 'a tied to self, object obtained through ES
-        unsafe { self.raw.source_address.as_os_str() }
+        unsafe { self.raw.source_address.as_opt_os_str() }
     }
 
     /// Optional. For screen sharing initiated using an Apple ID (e.g., from Messages or FaceTime),
     /// this is the viewer's (client's) Apple ID. It is not necessarily the Apple ID that invited
-    /// the screen sharing. Empty if unavailable.
+    /// the screen sharing. `None` if unavailable.
     #[inline(always)]
-    pub fn viewer_appleid(&self) -> &'a OsStr {
+    pub fn viewer_appleid(&self) -> Option<&'a OsStr> {
         // Safety: 'a tied to self, object obtained through ES
-        unsafe { self.raw.viewer_appleid.as_os_str() }
+        unsafe { self.raw.viewer_appleid.as_opt_os_str() }
     }
 
     /// Graphical session id of the screen shared.
@@ -46,7 +48,7 @@ impl<'a> EventScreensharingDetach<'a> {
     /// Source address as an [`IpAddr`] from the standard library, if possible.
     #[inline(always)]
     pub fn source_address_std(&self) -> Option<IpAddr> {
-        let sa = self.source_address().to_str()?;
+        let sa = self.source_address()?.to_str()?;
         IpAddr::from_str(sa).ok()
     }
 }
@@ -58,3 +60,32 @@ impl_debug_eq_hash_with_functions!(
     EventScreensharingDetach<'a>;
     source_address_type, source_address, viewer_appleid, graphical_session_id,
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{string_token, zeroed_raw};
+
+    #[test]
+    fn test_screensharing_detach() {
+        let mut raw: es_event_screensharing_detach_t = zeroed_raw();
+        raw.graphical_session_id = 3;
+
+        let event = EventScreensharingDetach { raw: &raw };
+        assert_eq!(event.source_address_type(), es_address_type_t::ES_ADDRESS_TYPE_NONE);
+        assert_eq!(event.source_address(), None);
+        assert_eq!(event.source_address_std(), None);
+        assert_eq!(event.viewer_appleid(), None);
+        assert_eq!(event.graphical_session_id(), 3);
+
+        raw.source_address_type = es_address_type_t::ES_ADDRESS_TYPE_IPV6;
+        raw.source_address = string_token("::1");
+        raw.viewer_appleid = string_token("viewer@example.com");
+
+        let event = EventScreensharingDetach { raw: &raw };
+        assert_eq!(event.source_address_type(), es_address_type_t::ES_ADDRESS_TYPE_IPV6);
+        assert_eq!(event.source_address(), Some(OsStr::new("::1")));
+        assert_eq!(event.source_address_std(), Some(IpAddr::from([0, 0, 0, 0, 0, 0, 0, 1])));
+        assert_eq!(event.viewer_appleid(), Some(OsStr::new("viewer@example.com")));
+    }
+}
@@ -2,7 +2,7 @@
 
 use endpoint_sec_sys::{acl_t, es_event_setacl_t, es_set_or_clear_t};
 
-use crate::File;
+use crate::{Acl, File};
 
 /// Set a file ACL.
 #[doc(alias = "es_event_setacl_t")]
@@ -36,9 +36,70 @@ impl<'a> EventSetAcl<'a> {
         // Safety: the pointer is behind a lifetime tied to `self`
         unsafe { self.raw.acl() }
     }
+
+    /// Operation on the ACL of the [`target`][Self::target()], with the new ACL when it is set.
+    ///
+    /// This reads the `acl` union only when it is valid. `None` for values of
+    /// [`set_or_clear`][Self::set_or_clear()] unknown to this crate or if Endpoint Security did
+    /// not provide the ACL being set.
+    #[inline(always)]
+    pub fn operation(&self) -> Option<SetOrClear<'a>> {
+        match self.raw.set_or_clear {
+            es_set_or_clear_t::ES_SET => self.set().copied().and_then(Acl::from_raw).map(SetOrClear::Set),
+            es_set_or_clear_t::ES_CLEAR => Some(SetOrClear::Clear),
+            _ => None,
+        }
+    }
+}
+
+/// Operation of an [`EventSetAcl`] on the ACL of its target, see [`EventSetAcl::operation()`].
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub enum SetOrClear<'a> {
+    /// The ACL of the target is set to this one
+    Set(Acl<'a>),
+    /// The ACL of the target is cleared
+    Clear,
 }
 
 // Safety: safe to send across threads: does not contain any interior mutability nor depend on current thread state
 unsafe impl Send for EventSetAcl<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventSetAcl<'a>; target, set_or_clear, set);
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::{es_event_setacl_t_anon_0, ShouldNotBeNull};
+
+    use super::*;
+    use crate::utils::{file_with_path, zeroed_raw};
+
+    #[test]
+    fn test_operation() {
+        let mut target = file_with_path("/tmp/file");
+
+        let mut raw: es_event_setacl_t = zeroed_raw();
+        raw.target = ShouldNotBeNull::new(&mut target);
+
+        // `_acl` is opaque and zero-sized, a dangling pointer is enough to represent a present ACL
+        raw.set_or_clear = es_set_or_clear_t::ES_SET;
+        raw.acl = es_event_setacl_t_anon_0 {
+            set: std::ptr::NonNull::dangling().as_ptr(),
+        };
+        let event = EventSetAcl { raw: &raw };
+        assert_eq!(event.target().path(), "/tmp/file");
+        assert!(matches!(event.operation(), Some(SetOrClear::Set(_))));
+
+        // NULL ACL
+        raw.acl = es_event_setacl_t_anon_0 {
+            set: std::ptr::null_mut(),
+        };
+        assert_eq!(EventSetAcl { raw: &raw }.operation(), None);
+
+        // The union is not read when clearing
+        raw.set_or_clear = es_set_or_clear_t::ES_CLEAR;
+        assert_eq!(EventSetAcl { raw: &raw }.operation(), Some(SetOrClear::Clear));
+
+        raw.set_or_clear = es_set_or_clear_t(2);
+        assert_eq!(EventSetAcl { raw: &raw }.operation(), None);
+    }
+}
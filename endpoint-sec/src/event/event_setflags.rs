@@ -2,7 +2,7 @@
 
 use endpoint_sec_sys::es_event_setflags_t;
 
-use crate::File;
+use crate::{File, FileFlags};
 
 /// Modify file flags information event.
 #[doc(alias = "es_event_setflags_t")]
@@ -18,6 +18,12 @@ impl<'a> EventSetFlags<'a> {
         self.raw.flags
     }
 
+    /// The desired new flags, as [`FileFlags`].
+    #[inline(always)]
+    pub fn flags_typed(&self) -> FileFlags {
+        FileFlags(self.raw.flags)
+    }
+
     /// The file for which flags information will be modified.
     #[inline(always)]
     pub fn target(&self) -> File<'a> {
@@ -30,3 +36,28 @@ impl<'a> EventSetFlags<'a> {
 unsafe impl Send for EventSetFlags<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventSetFlags<'a>; flags, target);
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::ShouldNotBeNull;
+
+    use super::*;
+    use crate::utils::{file_with_path, zeroed_raw};
+
+    #[test]
+    fn test_setflags() {
+        let mut target = file_with_path("/var/log/system.log");
+        target.stat.st_flags = libc::UF_NODUMP;
+
+        let mut raw: es_event_setflags_t = zeroed_raw();
+        raw.flags = libc::SF_APPEND | libc::UF_NODUMP;
+        raw.target = ShouldNotBeNull::new(&mut target);
+
+        let event = EventSetFlags { raw: &raw };
+        assert_eq!(event.flags_typed(), FileFlags::SF_APPEND | FileFlags::UF_NODUMP);
+        assert!(event.flags_typed().is_append_only());
+        // Current flags of the file, before the change
+        assert_eq!(event.target().flags_typed(), FileFlags::UF_NODUMP);
+        assert!(event.target().flags_typed().is_append_only() == false);
+    }
+}
@@ -21,6 +21,13 @@ impl<'a> EventSignal<'a> {
         self.raw.sig
     }
 
+    /// The signal to be delivered, `None` for the null signal (`0`) used to check for the existence
+    /// of a process.
+    #[inline(always)]
+    pub fn signal(&self) -> Option<Signal> {
+        Signal::from_raw(self.sig())
+    }
+
     /// The process that will receive the signal.
     #[inline(always)]
     pub fn target(&self) -> Process<'a> {
@@ -33,3 +40,129 @@ impl<'a> EventSignal<'a> {
 unsafe impl Send for EventSignal<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventSignal<'a> with version; sig, target);
+
+/// Common POSIX signals, see [`EventSignal::signal()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Signal {
+    /// `SIGHUP`
+    Hup,
+    /// `SIGINT`
+    Int,
+    /// `SIGQUIT`
+    Quit,
+    /// `SIGILL`
+    Ill,
+    /// `SIGTRAP`
+    Trap,
+    /// `SIGABRT`
+    Abrt,
+    /// `SIGKILL`, cannot be caught or ignored
+    Kill,
+    /// `SIGBUS`
+    Bus,
+    /// `SIGSEGV`
+    Segv,
+    /// `SIGPIPE`
+    Pipe,
+    /// `SIGALRM`
+    Alrm,
+    /// `SIGTERM`
+    Term,
+    /// `SIGSTOP`, cannot be caught or ignored
+    Stop,
+    /// `SIGTSTP`
+    Tstp,
+    /// `SIGCONT`
+    Cont,
+    /// `SIGCHLD`
+    Chld,
+    /// `SIGUSR1`
+    Usr1,
+    /// `SIGUSR2`
+    Usr2,
+    /// Any other signal number
+    Other(i32),
+}
+
+impl Signal {
+    /// Build [`Self`] from a raw signal number, `None` for `0` and negative numbers.
+    pub fn from_raw(sig: i32) -> Option<Self> {
+        let signal = match sig {
+            ..=0 => return None,
+            libc::SIGHUP => Self::Hup,
+            libc::SIGINT => Self::Int,
+            libc::SIGQUIT => Self::Quit,
+            libc::SIGILL => Self::Ill,
+            libc::SIGTRAP => Self::Trap,
+            libc::SIGABRT => Self::Abrt,
+            libc::SIGKILL => Self::Kill,
+            libc::SIGBUS => Self::Bus,
+            libc::SIGSEGV => Self::Segv,
+            libc::SIGPIPE => Self::Pipe,
+            libc::SIGALRM => Self::Alrm,
+            libc::SIGTERM => Self::Term,
+            libc::SIGSTOP => Self::Stop,
+            libc::SIGTSTP => Self::Tstp,
+            libc::SIGCONT => Self::Cont,
+            libc::SIGCHLD => Self::Chld,
+            libc::SIGUSR1 => Self::Usr1,
+            libc::SIGUSR2 => Self::Usr2,
+            other => Self::Other(other),
+        };
+        Some(signal)
+    }
+
+    /// Raw signal number.
+    pub fn as_raw(&self) -> i32 {
+        match *self {
+            Self::Hup => libc::SIGHUP,
+            Self::Int => libc::SIGINT,
+            Self::Quit => libc::SIGQUIT,
+            Self::Ill => libc::SIGILL,
+            Self::Trap => libc::SIGTRAP,
+            Self::Abrt => libc::SIGABRT,
+            Self::Kill => libc::SIGKILL,
+            Self::Bus => libc::SIGBUS,
+            Self::Segv => libc::SIGSEGV,
+            Self::Pipe => libc::SIGPIPE,
+            Self::Alrm => libc::SIGALRM,
+            Self::Term => libc::SIGTERM,
+            Self::Stop => libc::SIGSTOP,
+            Self::Tstp => libc::SIGTSTP,
+            Self::Cont => libc::SIGCONT,
+            Self::Chld => libc::SIGCHLD,
+            Self::Usr1 => libc::SIGUSR1,
+            Self::Usr2 => libc::SIGUSR2,
+            Self::Other(sig) => sig,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::zeroed_raw;
+
+    #[test]
+    fn test_signal() {
+        let mut raw: es_event_signal_t = zeroed_raw();
+        let signal = |raw: &es_event_signal_t| EventSignal { raw, version: 1 }.signal();
+
+        raw.sig = 9;
+        assert_eq!(signal(&raw), Some(Signal::Kill));
+        raw.sig = 15;
+        assert_eq!(signal(&raw), Some(Signal::Term));
+        raw.sig = 17;
+        assert_eq!(signal(&raw), Some(Signal::Stop));
+        raw.sig = 19;
+        assert_eq!(signal(&raw), Some(Signal::Cont));
+        raw.sig = 0;
+        assert_eq!(signal(&raw), None);
+        raw.sig = 64;
+        assert_eq!(signal(&raw), Some(Signal::Other(64)));
+
+        for sig in 1..=64 {
+            assert_eq!(Signal::from_raw(sig).unwrap().as_raw(), sig);
+        }
+    }
+}
@@ -23,9 +23,9 @@ impl<'a> EventSu<'a> {
     #[inline(always)]
     pub fn failure_message(&self) -> Option<&'a OsStr> {
         match self.success() {
-            false => None,
             // Safety: checked for `success` value, lifetime matches that of event
-            true => Some(unsafe { self.raw.failure_message.as_os_str() }),
+            false => Some(unsafe { self.raw.failure_message.as_os_str() }),
+            true => None,
         }
     }
 
@@ -147,3 +147,51 @@ make_event_data_iterator!(
     read_nth_env,
     super::as_os_str,
 );
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::es_event_su_t_anon0;
+
+    use super::*;
+    use crate::utils::{string_token, zeroed_raw};
+
+    #[test]
+    fn test_su() {
+        let mut argv = [string_token("-l"), string_token("root")];
+        let mut env = [string_token("TERM=xterm")];
+
+        let mut raw: es_event_su_t = zeroed_raw();
+        raw.success = true;
+        raw.failure_message = string_token("ignored");
+        raw.from_uid = 501;
+        raw.from_username = string_token("jdoe");
+        raw.has_to_uid = true;
+        raw.to_uid = es_event_su_t_anon0 { uid: 0 };
+        raw.to_username = string_token("root");
+        raw.shell = string_token("/bin/zsh");
+        raw.argc = argv.len();
+        raw.argv = argv.as_mut_ptr();
+        raw.env_count = env.len();
+        raw.env = env.as_mut_ptr();
+
+        let event = EventSu { raw: &raw };
+        assert_eq!(event.failure_message(), None);
+        assert_eq!(event.from_uid(), 501);
+        assert_eq!(event.from_username(), "jdoe");
+        assert_eq!(event.to_uid(), Some(0));
+        assert_eq!(event.to_username(), Some(OsStr::new("root")));
+        assert_eq!(event.shell(), Some(OsStr::new("/bin/zsh")));
+
+        let args = event.args().unwrap();
+        assert_eq!(args.len(), 2);
+        assert_eq!(args.collect::<Vec<_>>(), ["-l", "root"]);
+        assert_eq!(event.envs().unwrap().collect::<Vec<_>>(), ["TERM=xterm"]);
+
+        raw.success = false;
+        raw.failure_message = string_token("Sorry");
+        let event = EventSu { raw: &raw };
+        assert_eq!(event.failure_message(), Some(OsStr::new("Sorry")));
+        assert_eq!(event.to_uid(), None);
+        assert!(event.args().is_none());
+    }
+}
@@ -25,12 +25,24 @@ impl<'a> EventUipcConnect<'a> {
         self.raw.domain
     }
 
+    /// The communications domain of the socket, decoded from [`Self::domain()`].
+    #[inline(always)]
+    pub fn domain_typed(&self) -> SocketDomain {
+        SocketDomain::from(self.domain())
+    }
+
     /// Type of the socket (see socket(2)).
     #[inline(always)]
     pub fn type_(&self) -> i32 {
         self.raw.type_
     }
 
+    /// Type of the socket, decoded from [`Self::type_()`].
+    #[inline(always)]
+    pub fn socket_type(&self) -> SocketType {
+        SocketType::from(self.type_())
+    }
+
     /// Protocol of the socket (see socket(2)).
     #[inline(always)]
     pub fn protocol(&self) -> i32 {
@@ -42,3 +54,89 @@ impl<'a> EventUipcConnect<'a> {
 unsafe impl Send for EventUipcConnect<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventUipcConnect<'a>; file, domain, type_, protocol);
+
+/// Communications domain of a socket, the `AF_*` constants of `<sys/socket.h>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SocketDomain {
+    /// `AF_UNIX`, local communication
+    Unix,
+    /// `AF_INET`, IPv4
+    Inet,
+    /// `AF_INET6`, IPv6
+    Inet6,
+    /// Any other domain
+    Other(i32),
+}
+
+impl From<i32> for SocketDomain {
+    fn from(domain: i32) -> Self {
+        match domain {
+            libc::AF_UNIX => Self::Unix,
+            libc::AF_INET => Self::Inet,
+            libc::AF_INET6 => Self::Inet6,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// Type of a socket, the `SOCK_*` constants of `<sys/socket.h>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SocketType {
+    /// `SOCK_STREAM`, connection-based byte stream
+    Stream,
+    /// `SOCK_DGRAM`, connectionless datagrams
+    Datagram,
+    /// `SOCK_RAW`, raw protocol interface
+    Raw,
+    /// `SOCK_SEQPACKET`, connection-based datagrams
+    SeqPacket,
+    /// Any other type
+    Other(i32),
+}
+
+impl From<i32> for SocketType {
+    fn from(type_: i32) -> Self {
+        match type_ {
+            libc::SOCK_STREAM => Self::Stream,
+            libc::SOCK_DGRAM => Self::Datagram,
+            libc::SOCK_RAW => Self::Raw,
+            libc::SOCK_SEQPACKET => Self::SeqPacket,
+            other => Self::Other(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::ShouldNotBeNull;
+
+    use super::*;
+    use crate::utils::{file_with_path, zeroed_raw};
+
+    #[test]
+    fn test_socket_parameters() {
+        let mut file = file_with_path("/var/run/socket");
+
+        let mut raw: es_event_uipc_connect_t = zeroed_raw();
+        raw.file = ShouldNotBeNull::new(&mut file);
+        raw.domain = libc::AF_UNIX;
+        raw.type_ = libc::SOCK_STREAM;
+
+        let event = EventUipcConnect { raw: &raw };
+        assert_eq!(event.file().path(), "/var/run/socket");
+        assert_eq!(event.domain_typed(), SocketDomain::Unix);
+        assert_eq!(event.socket_type(), SocketType::Stream);
+        assert_eq!(event.protocol(), 0);
+
+        raw.type_ = libc::SOCK_DGRAM;
+        let event = EventUipcConnect { raw: &raw };
+        assert_eq!(event.socket_type(), SocketType::Datagram);
+
+        assert_eq!(SocketDomain::from(libc::AF_INET), SocketDomain::Inet);
+        assert_eq!(SocketDomain::from(libc::AF_INET6), SocketDomain::Inet6);
+        assert_eq!(SocketDomain::from(1000), SocketDomain::Other(1000));
+        assert_eq!(SocketType::from(libc::SOCK_RAW), SocketType::Raw);
+        assert_eq!(SocketType::from(libc::SOCK_SEQPACKET), SocketType::SeqPacket);
+        assert_eq!(SocketType::from(-1), SocketType::Other(-1));
+    }
+}
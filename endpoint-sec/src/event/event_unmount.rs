@@ -22,3 +22,5 @@ impl<'a> EventUnmount<'a> {
 unsafe impl Send for EventUnmount<'_> {}
 
 impl_debug_eq_hash_with_functions!(EventUnmount<'a>; statfs);
+
+impl_statfs_accessors!(EventUnmount);
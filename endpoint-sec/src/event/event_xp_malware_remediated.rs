@@ -59,10 +59,12 @@ impl<'a> EventXpMalwareRemediated<'a> {
     /// Optional. Path that was subject to remediation, if any. This path is not necessarily
     /// a malicious binary, it can also be a legitimate file containing a malicious portion.
     /// Specifically, the file at this path may still exist after successful remediation.
+    ///
+    /// `None` when empty.
     #[inline(always)]
-    pub fn remediated_path(&self) -> &'a OsStr {
+    pub fn remediated_path(&self) -> Option<&'a OsStr> {
         // Safety: 'a tied to self, object obtained through ES
-        unsafe { self.raw.remediated_path.as_os_str() }
+        unsafe { self.raw.remediated_path.as_opt_os_str() }
     }
 
     /// Audit token of process that was subject to remediation, if any.
@@ -82,3 +84,59 @@ impl_debug_eq_hash_with_functions!(
     signature_version, malware_identifier, incident_identifier, action_type,
     success, result_description, remediated_path, remediated_process_audit_token,
 );
+
+#[cfg(test)]
+mod tests {
+    use endpoint_sec_sys::audit_token_t;
+
+    use super::*;
+    use crate::utils::{string_token, zeroed_raw};
+
+    /// Remediation of `OSX.Example.A` by deleting a path
+    fn raw_remediated() -> es_event_xp_malware_remediated_t {
+        let mut raw: es_event_xp_malware_remediated_t = zeroed_raw();
+        raw.signature_version = string_token("5272");
+        raw.malware_identifier = string_token("OSX.Example.A");
+        raw.incident_identifier = string_token("4A3C5E1F");
+        raw.action_type = string_token("path_delete");
+        raw
+    }
+
+    #[test]
+    fn test_xp_malware_remediated_success() {
+        let mut token = audit_token_t {
+            val: [0, 501, 20, 501, 20, 1234, 100_000, 7],
+        };
+
+        let mut raw = raw_remediated();
+        raw.success = true;
+        raw.result_description = string_token("Remediation succeeded");
+        raw.remediated_path = string_token("/tmp/malware");
+        raw.remediated_process_audit_token = &mut token;
+
+        let event = EventXpMalwareRemediated { raw: &raw };
+        assert_eq!(event.signature_version(), OsStr::new("5272"));
+        assert_eq!(event.malware_identifier(), OsStr::new("OSX.Example.A"));
+        assert_eq!(event.incident_identifier(), OsStr::new("4A3C5E1F"));
+        assert_eq!(event.action_type(), OsStr::new("path_delete"));
+        assert!(event.success());
+        assert_eq!(event.result_description(), OsStr::new("Remediation succeeded"));
+        assert_eq!(event.remediated_path(), Some(OsStr::new("/tmp/malware")));
+
+        let remediated = event.remediated_process_audit_token().unwrap();
+        assert!(remediated.raw_eq(&AuditToken::new(token)));
+        assert_eq!(remediated.pid(), 1234);
+    }
+
+    #[test]
+    fn test_xp_malware_remediated_failure() {
+        let mut raw = raw_remediated();
+        raw.result_description = string_token("Path not found");
+
+        let event = EventXpMalwareRemediated { raw: &raw };
+        assert!(event.success() == false);
+        assert_eq!(event.result_description(), OsStr::new("Path not found"));
+        assert_eq!(event.remediated_path(), None);
+        assert!(event.remediated_process_audit_token().is_none());
+    }
+}
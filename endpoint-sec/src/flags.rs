@@ -0,0 +1,695 @@
+//! Typed wrappers around the raw flags found in Endpoint Security events.
+//!
+//! All wrappers implement [`Flags`], which gives them a uniform [`names()`][Flags::names] iterator
+//! and a consistent [`Display`][fmt::Display] implementation (e.g. `UF_IMMUTABLE | SF_RESTRICTED`).
+
+use std::fmt;
+use std::iter::FusedIterator;
+
+use endpoint_sec_sys::*;
+
+/// Common interface of the typed flag wrappers.
+pub trait Flags: Copy {
+    /// Known single-bit flags with their names, in display order.
+    const KNOWN: &'static [(&'static str, u32)];
+
+    /// Raw value of the flags, as a `u32` whatever the underlying type.
+    fn bits(self) -> u32;
+
+    /// Iterator over the names of the known flags that are set.
+    #[inline]
+    fn names(self) -> FlagNames {
+        FlagNames {
+            bits: self.bits(),
+            known: Self::KNOWN.iter(),
+        }
+    }
+
+    /// Bits that are set but do not correspond to any known flag.
+    #[inline]
+    fn unknown_bits(self) -> u32 {
+        Self::KNOWN.iter().fold(self.bits(), |bits, (_, flag)| bits & !flag)
+    }
+}
+
+/// Iterator over the names of the flags set in a [`Flags`] value, see [`Flags::names()`].
+#[derive(Debug, Clone)]
+pub struct FlagNames {
+    /// Raw value of the flags
+    bits: u32,
+    /// Remaining known flags to check
+    known: std::slice::Iter<'static, (&'static str, u32)>,
+}
+
+impl Iterator for FlagNames {
+    type Item = &'static str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let bits = self.bits;
+        self.known
+            .by_ref()
+            .find(|(_, flag)| *flag != 0 && bits & flag == *flag)
+            .map(|(name, _)| *name)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.known.size_hint().1)
+    }
+}
+
+impl FusedIterator for FlagNames {}
+
+/// Formats the set flags as `NAME_A | NAME_B`, followed by the unknown bits in hexadecimal if any,
+/// or `0` when nothing is set.
+fn fmt_flags<F: Flags>(flags: F, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut first = true;
+    for name in flags.names() {
+        if first == false {
+            f.write_str(" | ")?;
+        }
+        f.write_str(name)?;
+        first = false;
+    }
+
+    let unknown = flags.unknown_bits();
+    match (first, unknown) {
+        (true, 0) => f.write_str("0"),
+        (_, 0) => Ok(()),
+        (true, unknown) => write!(f, "{unknown:#x}"),
+        (false, unknown) => write!(f, " | {unknown:#x}"),
+    }
+}
+
+/// Serializes the set flags as the list of their names, followed by the unknown bits in
+/// hexadecimal if any, e.g. `["UF_IMMUTABLE", "0x40"]`.
+#[cfg(feature = "serde")]
+fn serialize_flags<F: Flags, S: serde::Serializer>(flags: F, serializer: S) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeSeq;
+
+    let unknown = flags.unknown_bits();
+    let mut seq = serializer.serialize_seq(None)?;
+    for name in flags.names() {
+        seq.serialize_element(name)?;
+    }
+    if unknown != 0 {
+        seq.serialize_element(&format!("{unknown:#x}"))?;
+    }
+    seq.end()
+}
+
+/// Helper macro to define a typed flag wrapper implementing [`Flags`].
+macro_rules! define_flags {
+    (
+        $(#[$meta: meta])*
+        pub struct $name: ident($ty: ty) {
+            $( $(#[$fmeta: meta])* $flag: ident = $value: expr, )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        pub struct $name(pub $ty);
+
+        impl $name {
+            $( $(#[$fmeta])* pub const $flag: Self = Self($value as $ty); )*
+
+            /// `true` if all the flags set in `other` are also set in `self`.
+            #[inline(always)]
+            pub fn contains(self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+
+            /// `true` if at least one of the flags set in `other` is also set in `self`.
+            #[inline(always)]
+            pub fn intersects(self, other: Self) -> bool {
+                self.0 & other.0 != 0
+            }
+        }
+
+        impl Flags for $name {
+            const KNOWN: &'static [(&'static str, u32)] = &[$( (::core::stringify!($flag), $value as $ty as u32), )*];
+
+            #[inline(always)]
+            fn bits(self) -> u32 {
+                self.0 as u32
+            }
+        }
+
+        impl ::core::convert::From<$ty> for $name {
+            #[inline(always)]
+            fn from(value: $ty) -> Self {
+                Self(value)
+            }
+        }
+
+        impl ::core::ops::BitOr for $name {
+            type Output = Self;
+
+            #[inline(always)]
+            fn bitor(self, rhs: Self) -> Self {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        impl ::core::ops::BitAnd for $name {
+            type Output = Self;
+
+            #[inline(always)]
+            fn bitand(self, rhs: Self) -> Self {
+                Self(self.0 & rhs.0)
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                fmt_flags(*self, f)
+            }
+        }
+
+        impl ::core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                write!(f, "{}({})", ::core::stringify!($name), self)
+            }
+        }
+
+        /// Serialized as the list of the names of the set flags, see [`Flags::names()`].
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $name {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_flags(*self, serializer)
+            }
+        }
+    };
+}
+
+define_flags! {
+    /// File flags, as set by `chflags(2)` and found in `st_flags`.
+    pub struct FileFlags(u32) {
+        /// Do not dump the file
+        UF_NODUMP = libc::UF_NODUMP,
+        /// File may not be changed
+        UF_IMMUTABLE = libc::UF_IMMUTABLE,
+        /// Writes to the file may only append
+        UF_APPEND = libc::UF_APPEND,
+        /// Directory is opaque with respect to union mounts
+        UF_OPAQUE = libc::UF_OPAQUE,
+        /// File is compressed
+        UF_COMPRESSED = libc::UF_COMPRESSED,
+        /// Renames and deletes of the file are tracked
+        UF_TRACKED = libc::UF_TRACKED,
+        /// Entitlement required for reading and writing
+        UF_DATAVAULT = UF_DATAVAULT,
+        /// Hint that the file should not be displayed in a GUI
+        UF_HIDDEN = libc::UF_HIDDEN,
+        /// File is archived
+        SF_ARCHIVED = libc::SF_ARCHIVED,
+        /// File may not be changed
+        SF_IMMUTABLE = libc::SF_IMMUTABLE,
+        /// Writes to the file may only append
+        SF_APPEND = libc::SF_APPEND,
+        /// Entitlement required for writing
+        SF_RESTRICTED = SF_RESTRICTED,
+        /// Item may not be removed, renamed or mounted on
+        SF_NOUNLINK = SF_NOUNLINK,
+        /// File is a firmlink
+        SF_FIRMLINK = SF_FIRMLINK,
+        /// File is dataless
+        SF_DATALESS = SF_DATALESS,
+    }
+}
+
+define_flags! {
+    /// Permission bits of a file mode (`mode_t`).
+    ///
+    /// The file type bits (`S_IFMT`) are not flags and are displayed as unknown bits.
+    pub struct Mode(libc::mode_t) {
+        /// Set user ID on execution
+        S_ISUID = libc::S_ISUID,
+        /// Set group ID on execution
+        S_ISGID = libc::S_ISGID,
+        /// Sticky bit
+        S_ISVTX = libc::S_ISVTX,
+        /// Read permission, owner
+        S_IRUSR = libc::S_IRUSR,
+        /// Write permission, owner
+        S_IWUSR = libc::S_IWUSR,
+        /// Execute/search permission, owner
+        S_IXUSR = libc::S_IXUSR,
+        /// Read permission, group
+        S_IRGRP = libc::S_IRGRP,
+        /// Write permission, group
+        S_IWGRP = libc::S_IWGRP,
+        /// Execute/search permission, group
+        S_IXGRP = libc::S_IXGRP,
+        /// Read permission, others
+        S_IROTH = libc::S_IROTH,
+        /// Write permission, others
+        S_IWOTH = libc::S_IWOTH,
+        /// Execute/search permission, others
+        S_IXOTH = libc::S_IXOTH,
+    }
+}
+
+define_flags! {
+    /// Code signing flags of a process, as defined in `<kern/cs_blobs.h>`.
+    pub struct CodesigningFlags(u32) {
+        /// Dynamically valid
+        CS_VALID = CS_VALID,
+        /// Ad hoc signed
+        CS_ADHOC = CS_ADHOC,
+        /// Has `get-task-allow` entitlement
+        CS_GET_TASK_ALLOW = CS_GET_TASK_ALLOW,
+        /// Has installer entitlement
+        CS_INSTALLER = CS_INSTALLER,
+        /// Library Validation required by Hardened System Policy
+        CS_FORCED_LV = CS_FORCED_LV,
+        /// Page invalidation allowed by task port policy
+        CS_INVALID_ALLOWED = CS_INVALID_ALLOWED,
+        /// Don't load invalid pages
+        CS_HARD = CS_HARD,
+        /// Kill process if it becomes invalid
+        CS_KILL = CS_KILL,
+        /// Force expiration checking
+        CS_CHECK_EXPIRATION = CS_CHECK_EXPIRATION,
+        /// Tell dyld to treat restricted
+        CS_RESTRICT = CS_RESTRICT,
+        /// Require enforcement
+        CS_ENFORCEMENT = CS_ENFORCEMENT,
+        /// Require library validation
+        CS_REQUIRE_LV = CS_REQUIRE_LV,
+        /// Code signature permits restricted entitlements
+        CS_ENTITLEMENTS_VALIDATED = CS_ENTITLEMENTS_VALIDATED,
+        /// Has `com.apple.rootless.restricted-nvram-variables.heritable` entitlement
+        CS_NVRAM_UNRESTRICTED = CS_NVRAM_UNRESTRICTED,
+        /// Apply hardened runtime policies
+        CS_RUNTIME = CS_RUNTIME,
+        /// Automatically signed by the linker
+        CS_LINKER_SIGNED = CS_LINKER_SIGNED,
+        /// Set `CS_HARD` on any exec'ed process
+        CS_EXEC_SET_HARD = CS_EXEC_SET_HARD,
+        /// Set `CS_KILL` on any exec'ed process
+        CS_EXEC_SET_KILL = CS_EXEC_SET_KILL,
+        /// Set `CS_ENFORCEMENT` on any exec'ed process
+        CS_EXEC_SET_ENFORCEMENT = CS_EXEC_SET_ENFORCEMENT,
+        /// Set `CS_INSTALLER` on any exec'ed process
+        CS_EXEC_INHERIT_SIP = CS_EXEC_INHERIT_SIP,
+        /// Was killed by kernel for invalidity
+        CS_KILLED = CS_KILLED,
+        /// dyld used to load this is a platform binary
+        CS_NO_UNTRUSTED_HELPERS = CS_NO_UNTRUSTED_HELPERS,
+        /// This is a platform binary
+        CS_PLATFORM_BINARY = CS_PLATFORM_BINARY,
+        /// Platform binary by the fact of path
+        CS_PLATFORM_PATH = CS_PLATFORM_PATH,
+        /// Process is currently or has previously been debugged and allowed to run with invalid pages
+        CS_DEBUGGED = CS_DEBUGGED,
+        /// Process has a signature (may have gone invalid)
+        CS_SIGNED = CS_SIGNED,
+        /// Code is dev signed, cannot be loaded into prod signed code
+        CS_DEV_CODE = CS_DEV_CODE,
+        /// Has Data Vault controller entitlement
+        CS_DATAVAULT_CONTROLLER = CS_DATAVAULT_CONTROLLER,
+    }
+}
+
+define_flags! {
+    /// Memory protection of a mapping, as defined in `<sys/mman.h>`.
+    pub struct VmProt(i32) {
+        /// Pages can be read
+        PROT_READ = libc::PROT_READ,
+        /// Pages can be written
+        PROT_WRITE = libc::PROT_WRITE,
+        /// Pages can be executed
+        PROT_EXEC = libc::PROT_EXEC,
+    }
+}
+
+impl VmProt {
+    /// `true` if the pages are both writable and executable.
+    #[inline(always)]
+    pub fn is_write_exec(self) -> bool {
+        self.contains(Self::PROT_WRITE | Self::PROT_EXEC)
+    }
+}
+
+define_flags! {
+    /// Permissions of an ACL entry, as defined in `<sys/acl.h>`.
+    ///
+    /// Directory permissions share their values with file ones and are displayed with the file
+    /// names: `ACL_LIST_DIRECTORY` is `ACL_READ_DATA`, `ACL_ADD_FILE` is `ACL_WRITE_DATA`,
+    /// `ACL_SEARCH` is `ACL_EXECUTE` and `ACL_ADD_SUBDIRECTORY` is `ACL_APPEND_DATA`.
+    pub struct AclPerms(u32) {
+        /// Read the data of a file or list a directory
+        ACL_READ_DATA = ACL_READ_DATA,
+        /// Write the data of a file or add a file to a directory
+        ACL_WRITE_DATA = ACL_WRITE_DATA,
+        /// Execute a file or search a directory
+        ACL_EXECUTE = ACL_EXECUTE,
+        /// Delete the object
+        ACL_DELETE = ACL_DELETE,
+        /// Append data to a file or add a subdirectory to a directory
+        ACL_APPEND_DATA = ACL_APPEND_DATA,
+        /// Delete an entry of a directory
+        ACL_DELETE_CHILD = ACL_DELETE_CHILD,
+        /// Read the attributes
+        ACL_READ_ATTRIBUTES = ACL_READ_ATTRIBUTES,
+        /// Write the attributes
+        ACL_WRITE_ATTRIBUTES = ACL_WRITE_ATTRIBUTES,
+        /// Read the extended attributes
+        ACL_READ_EXTATTRIBUTES = ACL_READ_EXTATTRIBUTES,
+        /// Write the extended attributes
+        ACL_WRITE_EXTATTRIBUTES = ACL_WRITE_EXTATTRIBUTES,
+        /// Read the ACL
+        ACL_READ_SECURITY = ACL_READ_SECURITY,
+        /// Write the ACL
+        ACL_WRITE_SECURITY = ACL_WRITE_SECURITY,
+        /// Change the owner
+        ACL_CHANGE_OWNER = ACL_CHANGE_OWNER,
+        /// Synchronize, unused
+        ACL_SYNCHRONIZE = ACL_SYNCHRONIZE,
+    }
+}
+
+define_flags! {
+    /// Type and attributes of a memory mapping, as defined in `<sys/mman.h>`.
+    pub struct MmapFlags(i32) {
+        /// Changes are shared
+        MAP_SHARED = libc::MAP_SHARED,
+        /// Changes are private
+        MAP_PRIVATE = libc::MAP_PRIVATE,
+        /// Map at exactly the given address
+        MAP_FIXED = libc::MAP_FIXED,
+        /// Sun: rename private pages to file
+        MAP_RENAME = libc::MAP_RENAME,
+        /// Sun: don't reserve needed swap area
+        MAP_NORESERVE = libc::MAP_NORESERVE,
+        /// For `MAP_FILE`, don't change file size
+        MAP_NOEXTEND = libc::MAP_NOEXTEND,
+        /// Region may contain semaphores
+        MAP_HASSEMAPHORE = libc::MAP_HASSEMAPHORE,
+        /// Don't cache pages for this mapping
+        MAP_NOCACHE = libc::MAP_NOCACHE,
+        /// Allocate a region that will be used for JIT purposes
+        MAP_JIT = libc::MAP_JIT,
+        /// Allocated from memory, swap space
+        MAP_ANON = libc::MAP_ANON,
+        /// Map code signed pages even if their signature is invalid
+        MAP_RESILIENT_CODESIGN = 0x2000,
+        /// Map pages from a media that can disappear
+        MAP_RESILIENT_MEDIA = 0x4000,
+    }
+}
+
+define_flags! {
+    /// Accessibility checked by `access(2)`, as defined in `<unistd.h>`.
+    ///
+    /// [`Self::F_OK`] is 0: it is the absence of the other flags, see [`Self::exists_only()`].
+    pub struct AccessMode(i32) {
+        /// Test for existence of the file
+        F_OK = libc::F_OK,
+        /// Test for execute or search permission
+        X_OK = libc::X_OK,
+        /// Test for write permission
+        W_OK = libc::W_OK,
+        /// Test for read permission
+        R_OK = libc::R_OK,
+    }
+}
+
+impl AccessMode {
+    /// `true` if read permission is checked (`R_OK`).
+    #[inline(always)]
+    pub fn reads(self) -> bool {
+        self.contains(Self::R_OK)
+    }
+
+    /// `true` if write permission is checked (`W_OK`).
+    #[inline(always)]
+    pub fn writes(self) -> bool {
+        self.contains(Self::W_OK)
+    }
+
+    /// `true` if execute or search permission is checked (`X_OK`).
+    #[inline(always)]
+    pub fn executes(self) -> bool {
+        self.contains(Self::X_OK)
+    }
+
+    /// `true` if only the existence of the file is checked (`F_OK`, no other flag set).
+    #[inline(always)]
+    pub fn exists_only(self) -> bool {
+        self.0 == libc::F_OK
+    }
+}
+
+impl FileFlags {
+    /// `true` if the file may not be changed, by its owner (`UF_IMMUTABLE`) or by anyone
+    /// (`SF_IMMUTABLE`).
+    #[inline(always)]
+    pub fn is_immutable(self) -> bool {
+        self.intersects(Self::UF_IMMUTABLE | Self::SF_IMMUTABLE)
+    }
+
+    /// `true` if writes to the file may only append, as set by its owner (`UF_APPEND`) or by the
+    /// super-user (`SF_APPEND`).
+    #[inline(always)]
+    pub fn is_append_only(self) -> bool {
+        self.intersects(Self::UF_APPEND | Self::SF_APPEND)
+    }
+
+    /// `true` if the file should not be displayed in a GUI (`UF_HIDDEN`).
+    #[inline(always)]
+    pub fn is_hidden(self) -> bool {
+        self.contains(Self::UF_HIDDEN)
+    }
+
+    /// `true` if the file is protected by System Integrity Protection (`SF_RESTRICTED`).
+    #[inline(always)]
+    pub fn is_restricted(self) -> bool {
+        self.contains(Self::SF_RESTRICTED)
+    }
+
+    /// `true` if the file may not be removed, renamed or mounted on (`SF_NOUNLINK`).
+    #[inline(always)]
+    pub fn is_no_unlink(self) -> bool {
+        self.contains(Self::SF_NOUNLINK)
+    }
+
+    /// `true` if the file is compressed (`UF_COMPRESSED`).
+    #[inline(always)]
+    pub fn is_compressed(self) -> bool {
+        self.contains(Self::UF_COMPRESSED)
+    }
+}
+
+impl CodesigningFlags {
+    /// `true` if the code signature is dynamically valid (`CS_VALID`).
+    ///
+    /// A process can lose its validity while it runs, for example when one of its pages fails
+    /// validation: this only reflects the state at the time of the event.
+    #[inline(always)]
+    pub fn is_valid(self) -> bool {
+        self.contains(Self::CS_VALID)
+    }
+
+    /// `true` if invalid pages are not loaded (`CS_HARD`).
+    #[inline(always)]
+    pub fn is_hard(self) -> bool {
+        self.contains(Self::CS_HARD)
+    }
+
+    /// `true` if the process is killed when it becomes invalid (`CS_KILL`).
+    #[inline(always)]
+    pub fn is_kill(self) -> bool {
+        self.contains(Self::CS_KILL)
+    }
+
+    /// `true` if the process is a platform binary (`CS_PLATFORM_BINARY`).
+    #[inline(always)]
+    pub fn is_platform(self) -> bool {
+        self.contains(Self::CS_PLATFORM_BINARY)
+    }
+
+    /// `true` if the hardened runtime policies apply (`CS_RUNTIME`).
+    #[inline(always)]
+    pub fn is_runtime(self) -> bool {
+        self.contains(Self::CS_RUNTIME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_and_display() {
+        let flags = FileFlags::UF_IMMUTABLE | FileFlags::SF_RESTRICTED;
+        assert_eq!(flags.names().collect::<Vec<_>>(), [
+            "UF_IMMUTABLE",
+            "SF_RESTRICTED"
+        ]);
+        assert_eq!(flags.to_string(), "UF_IMMUTABLE | SF_RESTRICTED");
+        assert_eq!(
+            format!("{flags:?}"),
+            "FileFlags(UF_IMMUTABLE | SF_RESTRICTED)"
+        );
+
+        assert_eq!(FileFlags(0).names().count(), 0);
+        assert_eq!(FileFlags(0).to_string(), "0");
+
+        let mode = Mode(libc::S_IFREG | 0o750);
+        assert_eq!(
+            mode.to_string(),
+            "S_IRUSR | S_IWUSR | S_IXUSR | S_IRGRP | S_IXGRP | 0x8000"
+        );
+        assert_eq!(mode.unknown_bits(), libc::S_IFREG as u32);
+
+        assert_eq!(CodesigningFlags(0x1000_0000).to_string(), "CS_DEBUGGED");
+        assert_eq!(CodesigningFlags(0x0000_0040).to_string(), "0x40");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialize() {
+        use serde_json::json;
+
+        let flags = FileFlags::UF_IMMUTABLE | FileFlags::SF_RESTRICTED;
+        assert_eq!(
+            serde_json::to_value(flags).unwrap(),
+            json!(["UF_IMMUTABLE", "SF_RESTRICTED"])
+        );
+        assert_eq!(serde_json::to_value(FileFlags(0)).unwrap(), json!([]));
+        assert_eq!(
+            serde_json::to_value(CodesigningFlags(CS_VALID | 0x40)).unwrap(),
+            json!(["CS_VALID", "0x40"])
+        );
+        assert_eq!(
+            serde_json::to_value(VmProt(libc::PROT_READ | libc::PROT_EXEC)).unwrap(),
+            json!(["PROT_READ", "PROT_EXEC"])
+        );
+    }
+
+    #[test]
+    fn test_file_flags_predicates() {
+        let none = FileFlags(0);
+        assert!(none.is_immutable() == false);
+        assert!(none.is_append_only() == false);
+        assert!(none.is_hidden() == false);
+        assert!(none.is_restricted() == false);
+        assert!(none.is_no_unlink() == false);
+        assert!(none.is_compressed() == false);
+
+        // User and system variants are both detected
+        assert!(FileFlags::UF_IMMUTABLE.is_immutable());
+        assert!(FileFlags::SF_IMMUTABLE.is_immutable());
+        assert!(FileFlags::UF_APPEND.is_append_only());
+        assert!(FileFlags::SF_APPEND.is_append_only());
+        assert!(FileFlags::SF_APPEND.is_immutable() == false);
+
+        let flags = FileFlags(libc::UF_HIDDEN | libc::UF_COMPRESSED | 0x0008_0000 | 0x0010_0000);
+        assert!(flags.is_hidden());
+        assert!(flags.is_compressed());
+        assert!(flags.is_restricted());
+        assert!(flags.is_no_unlink());
+        assert!(flags.is_immutable() == false);
+        assert_eq!(
+            flags.to_string(),
+            "UF_COMPRESSED | UF_HIDDEN | SF_RESTRICTED | SF_NOUNLINK"
+        );
+    }
+
+    #[test]
+    fn test_codesigning_flags_predicates() {
+        let none = CodesigningFlags(0);
+        assert!(none.is_valid() == false);
+        assert!(none.is_hard() == false);
+        assert!(none.is_kill() == false);
+        assert!(none.is_platform() == false);
+        assert!(none.is_runtime() == false);
+
+        assert!(CodesigningFlags(CS_VALID).is_valid());
+        assert!(CodesigningFlags(CS_HARD).is_hard());
+        assert!(CodesigningFlags(CS_KILL).is_kill());
+        assert!(CodesigningFlags(CS_PLATFORM_BINARY).is_platform());
+        assert!(CodesigningFlags(CS_RUNTIME).is_runtime());
+
+        // Each predicate only looks at its own bit
+        assert!(CodesigningFlags(CS_PLATFORM_PATH).is_platform() == false);
+        assert!(CodesigningFlags(!CS_VALID).is_valid() == false);
+        assert!(CodesigningFlags(!CS_RUNTIME).is_runtime() == false);
+
+        let flags = CodesigningFlags::CS_VALID
+            | CodesigningFlags::CS_SIGNED
+            | CodesigningFlags::CS_HARD
+            | CodesigningFlags::CS_KILL
+            | CodesigningFlags::CS_RUNTIME;
+        assert!(flags.is_valid());
+        assert!(flags.is_hard());
+        assert!(flags.is_kill());
+        assert!(flags.is_runtime());
+        assert!(flags.is_platform() == false);
+    }
+
+    #[test]
+    fn test_vm_prot_and_mmap_flags() {
+        assert!(VmProt(libc::PROT_WRITE | libc::PROT_EXEC).is_write_exec());
+        assert!(VmProt(libc::PROT_READ | libc::PROT_WRITE | libc::PROT_EXEC).is_write_exec());
+        assert!(VmProt(libc::PROT_READ | libc::PROT_EXEC).is_write_exec() == false);
+        assert!(VmProt(libc::PROT_WRITE).is_write_exec() == false);
+        assert!(VmProt(0).is_write_exec() == false);
+
+        assert_eq!(
+            VmProt(libc::PROT_READ | libc::PROT_EXEC).to_string(),
+            "PROT_READ | PROT_EXEC"
+        );
+        assert_eq!(
+            MmapFlags(libc::MAP_PRIVATE | libc::MAP_ANON | libc::MAP_JIT).to_string(),
+            "MAP_PRIVATE | MAP_JIT | MAP_ANON"
+        );
+        let flags = MmapFlags(libc::MAP_SHARED | libc::MAP_FIXED);
+        assert!(flags.contains(MmapFlags::MAP_SHARED));
+        assert!(flags.contains(MmapFlags::MAP_PRIVATE) == false);
+        assert_eq!(flags.unknown_bits(), 0);
+    }
+
+    #[test]
+    fn test_access_mode() {
+        let exists = AccessMode(libc::F_OK);
+        assert!(exists.exists_only());
+        assert!(exists.reads() == false);
+        assert!(exists.writes() == false);
+        assert!(exists.executes() == false);
+        assert_eq!(exists.to_string(), "0");
+
+        let read = AccessMode(libc::R_OK);
+        assert!(read.reads());
+        assert!(read.writes() == false);
+        assert!(read.executes() == false);
+        assert!(read.exists_only() == false);
+
+        let write = AccessMode(libc::W_OK);
+        assert!(write.writes());
+        assert!(write.reads() == false);
+        assert!(write.exists_only() == false);
+
+        let exec = AccessMode(libc::X_OK);
+        assert!(exec.executes());
+        assert!(exec.writes() == false);
+        assert!(exec.exists_only() == false);
+
+        let all = AccessMode::R_OK | AccessMode::W_OK | AccessMode::X_OK;
+        assert!(all.reads());
+        assert!(all.writes());
+        assert!(all.executes());
+        assert!(all.exists_only() == false);
+        assert_eq!(all.to_string(), "X_OK | W_OK | R_OK");
+
+        let read_exec = AccessMode(libc::R_OK | libc::X_OK);
+        assert!(read_exec.reads());
+        assert!(read_exec.executes());
+        assert!(read_exec.writes() == false);
+        assert_eq!(format!("{read_exec:?}"), "AccessMode(X_OK | R_OK)");
+    }
+}
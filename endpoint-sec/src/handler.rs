@@ -0,0 +1,155 @@
+//! Structured alternative to the handler closure: [`EventHandler`]
+//!
+//! [`Client::new()`] takes a single closure, which becomes unwieldy when an app has a lot of rules
+//! for different events. [`Client::new_with_handler()`] takes an object implementing
+//! [`EventHandler`] instead, whose default methods dispatch each message by action type then by
+//! event type to methods like [`EventHandler::on_exec()`] or [`EventHandler::on_open()`].
+
+use std::sync::Mutex;
+
+use endpoint_sec_sys::{es_action_type_t, NewClientError};
+
+use crate::{
+    Client, Event, EventClose, EventCreate, EventExec, EventExit, EventFork, EventOpen, EventRename, EventUnlink,
+    Message,
+};
+
+/// Handler of the messages of a [`Client`] created with [`Client::new_with_handler()`].
+///
+/// Only [`EventHandler::handle()`] is called by the client. By default it calls
+/// [`EventHandler::handle_auth()`] or [`EventHandler::handle_notify()`] depending on the action
+/// type of the message, which both call [`dispatch()`] to reach the `on_*` method matching the
+/// event. All methods do nothing by default: implementors only override the ones they need.
+///
+/// The same `on_*` method is called for the `AUTH` and `NOTIFY` variants of an event. **`AUTH`
+/// messages must be responded to** before their deadline, with [`Client::respond_auth_result()`]
+/// or [`Client::respond_flags_result()`]: an `AUTH` message reaching a method that does not
+/// respond to it will get the client killed. Overriding [`EventHandler::on_other()`] is a good way
+/// to catch the `AUTH` events that were forgotten.
+///
+/// ```no_run
+/// use endpoint_sec::sys::{es_action_type_t, es_auth_result_t, es_event_type_t};
+/// use endpoint_sec::{Client, EventExec, EventHandler, Message};
+///
+/// struct Policy {
+///     execs: u64,
+/// }
+///
+/// impl EventHandler for Policy {
+///     fn on_exec(&mut self, client: &mut Client<'_>, message: &Message, exec: &EventExec<'_>) {
+///         self.execs += 1;
+///         if message.action_type() == es_action_type_t::ES_ACTION_TYPE_AUTH {
+///             let denied = exec.target().executable().path() == "/usr/bin/nc";
+///             let result = if denied {
+///                 es_auth_result_t::ES_AUTH_RESULT_DENY
+///             } else {
+///                 es_auth_result_t::ES_AUTH_RESULT_ALLOW
+///             };
+///             let _ = client.respond_auth_result(message, result, true);
+///         }
+///     }
+/// }
+///
+/// let mut client = Client::new_with_handler(Policy { execs: 0 })?;
+/// client.subscribe(&[es_event_type_t::ES_EVENT_TYPE_AUTH_EXEC])?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub trait EventHandler {
+    /// Handle a message received by the client.
+    #[inline]
+    fn handle(&mut self, client: &mut Client<'_>, message: Message) {
+        if message.action_type() == es_action_type_t::ES_ACTION_TYPE_AUTH {
+            self.handle_auth(client, &message);
+        } else {
+            self.handle_notify(client, &message);
+        }
+    }
+
+    /// Handle an `AUTH` message, it must be responded to.
+    #[inline]
+    fn handle_auth(&mut self, client: &mut Client<'_>, message: &Message) {
+        dispatch(self, client, message);
+    }
+
+    /// Handle a `NOTIFY` message.
+    #[inline]
+    fn handle_notify(&mut self, client: &mut Client<'_>, message: &Message) {
+        dispatch(self, client, message);
+    }
+
+    /// Called for `AUTH_EXEC` and `NOTIFY_EXEC`.
+    #[inline]
+    fn on_exec(&mut self, _client: &mut Client<'_>, _message: &Message, _event: &EventExec<'_>) {}
+
+    /// Called for `AUTH_OPEN` and `NOTIFY_OPEN`.
+    #[inline]
+    fn on_open(&mut self, _client: &mut Client<'_>, _message: &Message, _event: &EventOpen<'_>) {}
+
+    /// Called for `NOTIFY_FORK`.
+    #[inline]
+    fn on_fork(&mut self, _client: &mut Client<'_>, _message: &Message, _event: &EventFork<'_>) {}
+
+    /// Called for `NOTIFY_EXIT`.
+    #[inline]
+    fn on_exit(&mut self, _client: &mut Client<'_>, _message: &Message, _event: &EventExit<'_>) {}
+
+    /// Called for `NOTIFY_CLOSE`.
+    #[inline]
+    fn on_close(&mut self, _client: &mut Client<'_>, _message: &Message, _event: &EventClose<'_>) {}
+
+    /// Called for `AUTH_CREATE` and `NOTIFY_CREATE`.
+    #[inline]
+    fn on_create(&mut self, _client: &mut Client<'_>, _message: &Message, _event: &EventCreate<'_>) {}
+
+    /// Called for `AUTH_RENAME` and `NOTIFY_RENAME`.
+    #[inline]
+    fn on_rename(&mut self, _client: &mut Client<'_>, _message: &Message, _event: &EventRename<'_>) {}
+
+    /// Called for `AUTH_UNLINK` and `NOTIFY_UNLINK`.
+    #[inline]
+    fn on_unlink(&mut self, _client: &mut Client<'_>, _message: &Message, _event: &EventUnlink<'_>) {}
+
+    /// Called for all other events, including the ones unknown to this crate.
+    #[inline]
+    fn on_other(&mut self, _client: &mut Client<'_>, _message: &Message) {}
+}
+
+/// Call the `on_*` method of `handler` matching the event of `message`.
+///
+/// This is what the default [`EventHandler::handle_auth()`] and [`EventHandler::handle_notify()`]
+/// do, it is available for implementations that override them to add some processing.
+pub fn dispatch<H: EventHandler + ?Sized>(handler: &mut H, client: &mut Client<'_>, message: &Message) {
+    match message.event() {
+        Some(Event::AuthExec(ref ev) | Event::NotifyExec(ref ev)) => handler.on_exec(client, message, ev),
+        Some(Event::AuthOpen(ref ev) | Event::NotifyOpen(ref ev)) => handler.on_open(client, message, ev),
+        Some(Event::NotifyFork(ref ev)) => handler.on_fork(client, message, ev),
+        Some(Event::NotifyExit(ref ev)) => handler.on_exit(client, message, ev),
+        Some(Event::NotifyClose(ref ev)) => handler.on_close(client, message, ev),
+        Some(Event::AuthCreate(ref ev) | Event::NotifyCreate(ref ev)) => handler.on_create(client, message, ev),
+        Some(Event::AuthRename(ref ev) | Event::NotifyRename(ref ev)) => handler.on_rename(client, message, ev),
+        Some(Event::AuthUnlink(ref ev) | Event::NotifyUnlink(ref ev)) => handler.on_unlink(client, message, ev),
+        _ => handler.on_other(client, message),
+    }
+}
+
+/// Creation of a [`Client`] with an [`EventHandler`].
+impl Client<'_> {
+    /// Creates a new [`Client`] whose messages are given to `handler`.
+    ///
+    /// Endpoint Security calls the handler from its own serial queue: `handler` is kept behind a
+    /// [`Mutex`] to give it mutable access to itself.
+    ///
+    /// The same requirements as for [`Client::new()`] apply.
+    pub fn new_with_handler<'b, H>(handler: H) -> Result<Client<'b>, NewClientError>
+    where
+        H: EventHandler + 'b,
+    {
+        let handler = Mutex::new(handler);
+
+        Client::new(move |client, message| {
+            // A panic in a previous call poisoned the mutex, the handler is still usable
+            let mut handler = handler.lock().unwrap_or_else(|e| e.into_inner());
+            handler.handle(client, message);
+        })
+    }
+}
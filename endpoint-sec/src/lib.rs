@@ -13,7 +13,15 @@
 //! subscribptions for your client, it will call the handler that was given to `Client::new()` with
 //! the [message][Message] associated to the event. Note that `AUTH` events have an associated
 //! deadline before which your handler must give a response else your client may be killed by macOS
-//! to avoid stalling for the user.
+//! to avoid stalling for the user. To respond after the handler returned, use
+//! [`Message::into_deferred()`].
+//!
+//! Apps that prefer draining messages from their own loop can use [`Client::new_with_pump()`]
+//! instead, which forwards messages into a [`MessagePump`]. Apps with a lot of rules can use
+//! [`Client::new_with_handler()`] with an [`EventHandler`] instead of a single closure.
+//!
+//! With the `serde` feature, [`Event`] and the types it contains implement `serde::Serialize`, to
+//! ship events to log pipelines.
 //!
 //! [esf]: https://developer.apple.com/documentation/endpointsecurity
 
@@ -32,6 +40,8 @@
 
 // Reexports [`endpoint_sec_sys`]
 pub use endpoint_sec_sys as sys;
+#[cfg(all(test, not(feature = "serde")))]
+use serde_json as _;
 #[cfg(all(test, not(feature = "audit_token_from_pid")))]
 use sysinfo as _;
 #[cfg(test)]
@@ -44,8 +54,39 @@ use trybuild as _;
 /// Security, others are behind pointers and associated with another (eg, array + len). This macro
 /// makes it easier to implement [`Debug`] by simply passing the type and the functions to use for
 /// the `Debug` impl. See examples of usage in the modules below.
+///
+/// With the `serde` feature, the same functions are used to implement [`serde::Serialize`], except
+/// for the types passed with `@no_serialize`.
 macro_rules! impl_debug_eq_hash_with_functions {
     ($ty:ident$(<$lt: lifetime>)? $(with $version:ident)?; $($(#[$fmeta: meta])? $fname:ident),* $(,)?) =>  {
+        impl_debug_eq_hash_with_functions!(@no_serialize $ty$(<$lt>)? $(with $version)?; $($(#[$fmeta])? $fname),*);
+
+        #[cfg(feature = "serde")]
+        impl $(<$lt>)? ::serde::Serialize for $ty $(<$lt>)? {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                use ::serde::ser::SerializeStruct;
+
+                let fields: &[&str] = &[
+                    $( ::core::stringify!($version), )?
+                    $( $(#[$fmeta])? ::core::stringify!($fname), )*
+                ];
+                #[allow(unused_mut)]
+                let mut s = serializer.serialize_struct(::core::stringify!($ty), fields.len())?;
+                $( s.serialize_field(::core::stringify!($version), &self.$version)?; )?
+                $( $(#[$fmeta])? $crate::serialize::field(&mut s, ::core::stringify!($fname), &self.$fname())?; )*
+                s.end()
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl $(<$lt>)? $crate::serialize::SerializeField for $ty $(<$lt>)? {
+            fn serialize_field<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                ::serde::Serialize::serialize(self, serializer)
+            }
+        }
+    };
+    // Used for types that cannot be serialized in a meaningful way, like `Message` and its deadline
+    (@no_serialize $ty:ident$(<$lt: lifetime>)? $(with $version:ident)?; $($(#[$fmeta: meta])? $fname:ident),* $(,)?) =>  {
         impl $(<$lt>)? ::core::fmt::Debug for $ty $(<$lt>)? {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 let mut d = f.debug_struct(::core::stringify!($ty));
@@ -99,10 +140,19 @@ mod acl;
 mod action;
 mod audit;
 mod client;
+mod deferred;
 mod event;
+mod flags;
+mod handler;
 mod message;
 mod mute;
+mod pump;
+#[cfg(feature = "macos_10_15_4")]
+mod seq;
+mod subscription;
 // Not public
+#[cfg(feature = "serde")]
+mod serialize;
 mod utils;
 
 #[cfg(feature = "macos_10_15_1")]
@@ -110,15 +160,24 @@ pub use acl::*;
 pub use action::*;
 pub use audit::*;
 pub use client::*;
+pub use deferred::*;
 pub use event::*;
+pub use flags::*;
+pub use handler::*;
 pub use message::*;
 pub use mute::*;
+pub use pump::*;
+#[cfg(feature = "macos_10_15_4")]
+pub use seq::*;
+pub use subscription::*;
 
 /// Helper module to avoid implementing version detection in this crate and make testing easier
 /// by telling the crate its on a lower version than the real one.
 pub mod version {
     use std::sync::atomic::{AtomicU64, Ordering};
 
+    use endpoint_sec_sys::ReturnError;
+
     /// macOS major version
     static MAJOR: AtomicU64 = AtomicU64::new(10);
     /// macOS minor version
@@ -126,6 +185,10 @@ pub mod version {
     /// macOS patch version
     static PATCH: AtomicU64 = AtomicU64::new(0);
 
+    /// Held by the tests changing the runtime version, since tests run concurrently
+    #[cfg(test)]
+    pub(crate) static TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
     /// Setup the runtime version of macOS, detected outside of this library.
     ///
     /// Conservatively, this library assumes the default is 10.15.0 and will refuse to use functions
@@ -147,6 +210,66 @@ pub mod version {
         PATCH.store(patch, Ordering::Release);
     }
 
+    /// Detect the running version of macOS and give it to [`set_runtime_version()`].
+    ///
+    /// The version is read from the `kern.osproductversion` sysctl, which is not affected by the
+    /// `SYSTEM_VERSION_COMPAT` environment variable. The detected version is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the sysctl cannot be read or parsed, or if the detected version is
+    /// below 10.15.0. The runtime version is left unchanged in this case.
+    pub fn detect_and_set() -> std::io::Result<(u64, u64, u64)> {
+        use std::io::{Error, ErrorKind};
+
+        let mut buf = [0u8; 32];
+        let mut len = buf.len();
+        // Safety:
+        // - the name is a valid nul-terminated C string
+        // - `buf` is valid for writes of `len` bytes, and `len` is updated with the written size
+        // - no new value is given
+        let res = unsafe {
+            libc::sysctlbyname(
+                b"kern.osproductversion\0".as_ptr().cast(),
+                buf.as_mut_ptr().cast(),
+                &mut len,
+                std::ptr::null_mut(),
+                0,
+            )
+        };
+        if res != 0 {
+            return Err(Error::last_os_error());
+        }
+
+        let raw = buf.get(..len).unwrap_or(&buf);
+        let raw = raw.strip_suffix(b"\0").unwrap_or(raw);
+        let version = std::str::from_utf8(raw)
+            .ok()
+            .and_then(parse_product_version)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "invalid kern.osproductversion"))?;
+
+        let (major, minor, _) = version;
+        if major < 10 || (major == 10 && minor < 15) {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "Endpoint Security cannot run on versions inferiors to 10.15.0",
+            ));
+        }
+
+        set_runtime_version(version.0, version.1, version.2);
+        Ok(version)
+    }
+
+    /// Parse a product version like `14.2.1`, a missing minor or patch version is 0
+    fn parse_product_version(raw: &str) -> Option<(u64, u64, u64)> {
+        let mut parts = raw.trim().split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+        let patch = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+
+        parts.next().is_none().then_some((major, minor, patch))
+    }
+
     /// `true` if the version setup in [`set_runtime_version()`] is at least the given
     /// `major.minor.patch` here.
     pub fn is_version_or_more(major: u64, minor: u64, patch: u64) -> bool {
@@ -157,10 +280,192 @@ pub mod version {
         (current_major, current_minor, current_patch) >= (major, minor, patch)
     }
 
+    /// Checks that the version setup in [`set_runtime_version()`] is at least the given
+    /// `major.minor.patch`, returning [`ReturnError::ApiUnavailable`] otherwise.
+    ///
+    /// This is the check this crate does before calling Endpoint Security functions introduced
+    /// after macOS 10.15.0, for apps that make such calls themselves through [`sys`][crate::sys]:
+    ///
+    /// ```
+    /// use endpoint_sec::sys::{es_client_t, ReturnError};
+    /// use endpoint_sec::version;
+    ///
+    /// /// Hypothetical wrapper around a function introduced in macOS 13.0.0
+    /// fn call_macos_13_function(_client: *mut es_client_t) -> Result<(), ReturnError> {
+    ///     version::require(13, 0, 0)?;
+    ///     // Safe to call the function from here
+    ///     Ok(())
+    /// }
+    ///
+    /// version::set_runtime_version(12, 6, 0);
+    /// assert_eq!(
+    ///     call_macos_13_function(std::ptr::null_mut()),
+    ///     Err(ReturnError::ApiUnavailable)
+    /// );
+    ///
+    /// version::set_runtime_version(13, 4, 1);
+    /// assert_eq!(call_macos_13_function(std::ptr::null_mut()), Ok(()));
+    /// ```
+    ///
+    /// [`ReturnError::ApiUnavailable`]: crate::sys::ReturnError::ApiUnavailable
+    #[inline]
+    pub fn require(major: u64, minor: u64, patch: u64) -> Result<(), ReturnError> {
+        if is_version_or_more(major, minor, patch) {
+            Ok(())
+        } else {
+            Err(ReturnError::ApiUnavailable)
+        }
+    }
+
+    /// Highest macOS version for which support is enabled by the cargo features of this crate.
+    pub const fn max_feature_version() -> (u64, u64, u64) {
+        if cfg!(feature = "macos_15_0_0") {
+            (15, 0, 0)
+        } else if cfg!(feature = "macos_14_0_0") {
+            (14, 0, 0)
+        } else if cfg!(feature = "macos_13_3_0") {
+            (13, 3, 0)
+        } else if cfg!(feature = "macos_13_0_0") {
+            (13, 0, 0)
+        } else if cfg!(feature = "macos_12_0_0") {
+            (12, 0, 0)
+        } else if cfg!(feature = "macos_11_3_0") {
+            (11, 3, 0)
+        } else if cfg!(feature = "macos_11_0_0") {
+            (11, 0, 0)
+        } else if cfg!(feature = "macos_10_15_4") {
+            (10, 15, 4)
+        } else if cfg!(feature = "macos_10_15_1") {
+            (10, 15, 1)
+        } else {
+            (10, 15, 0)
+        }
+    }
+
+    /// Mismatch between the macOS release targeted by the cargo features and the runtime version,
+    /// see [`check_feature_version_consistency()`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct FeatureVersionMismatch {
+        /// Highest version enabled by the cargo features, see [`max_feature_version()`]
+        pub compiled: (u64, u64, u64),
+        /// Version given to [`set_runtime_version()`]
+        pub runtime: (u64, u64, u64),
+    }
+
+    impl FeatureVersionMismatch {
+        /// `true` if the runtime is newer than the features: events and fields introduced by the
+        /// newer releases are not compiled in, and [`Message::event()`][crate::Message::event]
+        /// returns [`Event::Unknown`][crate::Event::Unknown] for them.
+        pub fn is_runtime_newer(&self) -> bool {
+            self.runtime > self.compiled
+        }
+    }
+
+    impl std::error::Error for FeatureVersionMismatch {}
+
+    impl std::fmt::Display for FeatureVersionMismatch {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let (c_major, c_minor, c_patch) = self.compiled;
+            let (r_major, r_minor, r_patch) = self.runtime;
+            write!(
+                f,
+                "Built for macOS {c_major}.{c_minor}.{c_patch} but running on macOS {r_major}.{r_minor}.{r_patch}"
+            )?;
+            if self.is_runtime_newer() {
+                f.write_str(": events introduced since are not supported")
+            } else {
+                f.write_str(": APIs introduced since are unavailable")
+            }
+        }
+    }
+
+    /// Checks that the highest macOS version enabled by the cargo features belongs to the same
+    /// release as the runtime version given to [`set_runtime_version()`].
+    ///
+    /// A mismatch is not necessarily a bug, but it is a common source of confusion:
+    ///
+    /// - Built for an older release than the runtime one: events introduced by the newer releases
+    ///   are not compiled in, subscribing to them is impossible and they are "missing".
+    /// - Built for a newer release: the APIs of the newer releases are guarded at runtime and
+    ///   return [`ReturnError::ApiUnavailable`][crate::sys::ReturnError::ApiUnavailable].
+    ///
+    /// Releases are compared by major version from macOS 11, and by minor version for 10.15.x.
+    /// Apps should call this after [`set_runtime_version()`] and log the returned mismatch.
+    pub fn check_feature_version_consistency() -> Result<(), FeatureVersionMismatch> {
+        let runtime = (
+            MAJOR.load(Ordering::Acquire),
+            MINOR.load(Ordering::Acquire),
+            PATCH.load(Ordering::Acquire),
+        );
+        check_consistency(max_feature_version(), runtime)
+    }
+
+    /// Implementation of [`check_feature_version_consistency()`], separated for tests
+    fn check_consistency(compiled: (u64, u64, u64), runtime: (u64, u64, u64)) -> Result<(), FeatureVersionMismatch> {
+        /// Release of a version: major, plus minor for macOS 10
+        fn release((major, minor, _): (u64, u64, u64)) -> (u64, u64) {
+            if major == 10 {
+                (major, minor)
+            } else {
+                (major, 0)
+            }
+        }
+
+        if release(compiled) == release(runtime) {
+            Ok(())
+        } else {
+            Err(FeatureVersionMismatch { compiled, runtime })
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
 
+        #[test]
+        fn test_check_consistency() {
+            assert_eq!(check_consistency((13, 0, 0), (13, 4, 1)), Ok(()));
+            assert_eq!(check_consistency((10, 15, 4), (10, 15, 7)), Ok(()));
+
+            let err = check_consistency((12, 0, 0), (14, 2, 0)).unwrap_err();
+            assert!(err.is_runtime_newer());
+            let err = check_consistency((14, 0, 0), (11, 3, 0)).unwrap_err();
+            assert!(err.is_runtime_newer() == false);
+        }
+
+        #[test]
+        fn test_require() {
+            let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            set_runtime_version(12, 3, 0);
+
+            assert_eq!(require(10, 15, 0), Ok(()));
+            assert_eq!(require(12, 3, 0), Ok(()));
+            assert_eq!(require(12, 3, 1), Err(ReturnError::ApiUnavailable));
+            assert_eq!(require(13, 0, 0), Err(ReturnError::ApiUnavailable));
+        }
+
+        #[test]
+        fn test_parse_product_version() {
+            assert_eq!(parse_product_version("14.2.1"), Some((14, 2, 1)));
+            assert_eq!(parse_product_version("15.0"), Some((15, 0, 0)));
+            assert_eq!(parse_product_version("11"), Some((11, 0, 0)));
+            assert_eq!(parse_product_version("10.15.7\n"), Some((10, 15, 7)));
+
+            assert_eq!(parse_product_version(""), None);
+            assert_eq!(parse_product_version("14.a"), None);
+            assert_eq!(parse_product_version("14.2.1.3"), None);
+            assert_eq!(parse_product_version("14..1"), None);
+        }
+
+        #[test]
+        fn test_detect_and_set() {
+            let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+            let detected = detect_and_set().unwrap();
+            assert!(detected >= (10, 15, 0));
+            assert!(is_version_or_more(detected.0, detected.1, detected.2));
+        }
+
         #[test]
         #[should_panic(expected = "Endpoint Security cannot run on versions inferiors to 10.15.0")]
         fn test_cannot_set_version_major_under_10() {
@@ -175,6 +480,7 @@ pub mod version {
 
         #[test]
         fn test_is_version_or_more_with_set_runtime() {
+            let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
             set_runtime_version(10, 15, 0);
 
             assert!(is_version_or_more(10, 14, 99));
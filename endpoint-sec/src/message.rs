@@ -1,14 +1,17 @@
 //! Definitions of Endpoint Security Message.
 
-use std::ffi::OsStr;
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
 use std::ptr::NonNull;
-#[cfg(feature = "macos_10_15_4")]
-use std::time::Duration;
-use std::time::{Instant, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 
 use endpoint_sec_sys::*;
 
-use crate::{utils, Action, ActionResult, AuditToken, Event};
+use crate::{utils, Action, ActionResult, AuditToken, CodesigningFlags, Event, FileFlags};
+
+/// Signing ids of the Apple daemons acting on behalf of Endpoint Security clients, see
+/// [`Message::is_likely_es_feedback()`].
+pub const ES_DAEMON_SIGNING_IDS: &[&str] = &["com.apple.endpointsecurityd"];
 
 /// A message from Endpoint Security.
 ///
@@ -79,6 +82,22 @@ impl Message {
         self.get_raw_ref().version
     }
 
+    /// Size of the message in bytes, as computed by Endpoint Security.
+    ///
+    /// This is only meant for metrics, the size **must not** be used to copy the message (e.g. by
+    /// allocating a buffer of this size and copying the message into it): doing so results in
+    /// use-after-free bugs. Use [`Clone`] to keep a message around instead.
+    ///
+    /// **Deprecated in macOS 11+**: [`es_message_size`] only exists for the deprecated
+    /// `es_copy_message()` API. Apple still ships it but could remove it in a future version, do
+    /// not rely on this method for anything else than best effort metrics.
+    #[doc(alias = "es_message_size")]
+    #[inline(always)]
+    pub fn size(&self) -> usize {
+        // Safety: inner message is a valid live object by construction.
+        unsafe { es_message_size(self.get_raw_ref()) }
+    }
+
     /// Time at which the event was generated.
     ///
     /// See also [`Self::time()`].
@@ -144,7 +163,20 @@ impl Message {
         utils::convert_mach_time_to_instant(self.raw_deadline())
     }
 
+    /// Time left before the [deadline][Self::deadline()] of an AUTH event, [`Duration::ZERO`] if it
+    /// has already passed.
+    ///
+    /// Useful to decide how much work can be done before responding, keeping some margin: the
+    /// client is killed if the deadline is missed.
+    #[inline]
+    pub fn deadline_duration(&self) -> Result<Duration, TimeError> {
+        Ok(time_left(self.deadline()?, Instant::now()))
+    }
+
     /// Describes the process that took the action.
+    ///
+    /// The returned [`Process`] is only a reference to the raw process and the message version,
+    /// building it is as cheap as reading a field and does not need to be cached.
     #[inline(always)]
     pub fn process(&self) -> Process<'_> {
         Process::new(
@@ -154,6 +186,46 @@ impl Message {
         )
     }
 
+    /// Audit token of the process that took the action, the same as
+    /// `self.process().audit_token()`.
+    ///
+    /// Handlers that only need to identify the process, e.g. to look it up in a cache or mute it,
+    /// can use this shortcut.
+    #[inline(always)]
+    pub fn process_audit_token(&self) -> AuditToken {
+        // Safety: 'a tied to self, object obtained through ES
+        AuditToken::new(unsafe { self.get_raw_ref().process() }.audit_token)
+    }
+
+    /// `true` if the message was likely caused by the actions of an Endpoint Security client,
+    /// using [`ES_DAEMON_SIGNING_IDS`] as the list of known Endpoint Security daemons.
+    ///
+    /// See [`Self::is_likely_es_feedback_with()`].
+    #[inline]
+    pub fn is_likely_es_feedback(&self) -> bool {
+        self.is_likely_es_feedback_with(ES_DAEMON_SIGNING_IDS)
+    }
+
+    /// `true` if the message was likely caused by the actions of an Endpoint Security client: the
+    /// process has the Endpoint Security entitlement (see [`Process::is_es_client()`]) or its
+    /// [signing id][Process::signing_id] is one of `signing_ids`.
+    ///
+    /// Two cooperating ES clients reacting to the events caused by each other can create an
+    /// infinite cycle, and an `AUTH` event triggered by another client handling its own `AUTH`
+    /// event can deadlock both. The recommended handling for such messages is to allow them (for
+    /// `AUTH` events) and skip any further processing.
+    ///
+    /// This is a heuristic: an ES client can also do things unrelated to Endpoint Security.
+    pub fn is_likely_es_feedback_with(&self, signing_ids: &[&str]) -> bool {
+        let process = self.process();
+        if process.is_es_client() {
+            return true;
+        }
+
+        let signing_id = process.signing_id();
+        signing_ids.iter().any(|id| signing_id == OsStr::new(id))
+    }
+
     /// Per client event sequence number on version 2 and later, otherwise None.
     #[cfg(feature = "macos_10_15_4")]
     #[inline(always)]
@@ -194,10 +266,53 @@ impl Message {
     }
 
     /// Event associated to this message.
+    ///
+    /// Events of a type unknown to this crate are returned as [`Event::Unknown`].
     #[inline(always)]
     pub fn event(&self) -> Option<Event<'_>> {
         // Safety: all arguments are from the current message instance.
-        unsafe { Event::from_raw_parts(self.event_type(), &self.get_raw_ref().event, self.version()) }
+        unsafe {
+            Event::from_raw_parts(
+                self.event_type(),
+                self.action_type(),
+                &self.get_raw_ref().event,
+                self.version(),
+            )
+        }
+    }
+
+    /// Raw bytes of the event union of the message, for **opaque forwarding only**.
+    ///
+    /// Meant for events unknown to this crate (when [`Self::event()`] returns [`Event::Unknown`]): a
+    /// forward-compatible app can capture them and ship them to a backend that understands the
+    /// newer macOS version, along with [`Self::event_type()`] and [`Self::version()`], instead
+    /// of dropping them.
+    ///
+    /// **The bytes must not be interpreted structurally by the app**: their layout depends on
+    /// the event type and the macOS version, and most events contain pointers (to strings, files,
+    /// processes...) that are only valid in this process and for the lifetime of the message. The
+    /// data they point to is not part of the returned bytes.
+    ///
+    /// The length of the region is bounded by [`es_message_size()`], deprecated since macOS 11 (see
+    /// [`Self::size()`]). Returns `None` if the message is too small to contain an event.
+    pub fn raw_event_bytes(&self) -> Option<&[u8]> {
+        let raw = self.get_raw_ref();
+        let start = (&raw.event as *const es_events_t).cast::<u8>();
+        let offset = start as usize - (raw as *const es_message_t as usize);
+
+        // Safety: `raw` is a valid live message by construction
+        let size = unsafe { es_message_size(raw) };
+        let len = size.checked_sub(offset)?.min(std::mem::size_of::<es_events_t>());
+        if len == 0 {
+            return None;
+        }
+
+        // Safety:
+        // - `start` points into the message, which is at least `size` bytes long according to
+        //   Endpoint Security, and `len` does not go past the end
+        // - the memory is initialized by Endpoint Security and read-only
+        // - the lifetime of the slice is tied to `self`
+        Some(unsafe { std::slice::from_raw_parts(start, len) })
     }
 
     /// Thread associated to this message (if present) on version 4 and later, otherwise None.
@@ -254,7 +369,7 @@ unsafe impl Send for Message {}
 unsafe impl Sync for Message {}
 
 impl_debug_eq_hash_with_functions!(
-    Message;
+    @no_serialize Message;
     action_type,
     action,
     deadline,
@@ -272,6 +387,11 @@ impl_debug_eq_hash_with_functions!(
     version,
 );
 
+/// Time left at `now` before `deadline`, zero if it has already passed
+fn time_left(deadline: Instant, now: Instant) -> Duration {
+    deadline.saturating_duration_since(now)
+}
+
 /// Error produced when trying to access [`Message::deadline()`] or equivalent functions because
 /// computing the `[`Instant`] overflowed.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -333,6 +453,15 @@ impl<'a> File<'a> {
         unsafe { self.0.path.as_os_str() }
     }
 
+    /// The path to the file, as a [`Path`].
+    ///
+    /// Use [`Self::path()`] for byte-level operations.
+    #[doc(alias = "path_as_path")]
+    #[inline(always)]
+    pub fn as_path(&self) -> &'a Path {
+        Path::new(self.path())
+    }
+
     /// Returns true to indicate if the path was truncated.
     #[inline(always)]
     pub fn path_truncated(&self) -> bool {
@@ -344,6 +473,78 @@ impl<'a> File<'a> {
     pub fn stat(&self) -> &'a stat {
         &self.0.stat
     }
+
+    /// Device of the volume containing the file (`st_dev` of [`Self::stat()`]).
+    ///
+    /// Endpoint Security does not attach volume information to file events, but the device can be
+    /// correlated with the mount events received before: for local file systems, the first value
+    /// of the `f_fsid` of the [`statfs`] of [`EventMount`][crate::EventMount] is the device of the
+    /// mounted volume. This allows policies such as "detect writes to external volumes":
+    ///
+    /// ```no_run
+    /// use std::collections::HashMap;
+    /// use std::ffi::{CStr, OsString};
+    /// use std::os::unix::ffi::OsStrExt;
+    ///
+    /// use endpoint_sec::sys::{dev_t, statfs};
+    /// use endpoint_sec::{Event, Message};
+    ///
+    /// /// Mount point of the volumes, by device
+    /// #[derive(Default)]
+    /// struct Volumes(HashMap<dev_t, OsString>);
+    ///
+    /// impl Volumes {
+    ///     fn on_mount(&mut self, statfs: &statfs) {
+    ///         // Safety: `f_mntonname` is a nul-terminated C string
+    ///         let on = unsafe { CStr::from_ptr(statfs.f_mntonname.as_ptr()) };
+    ///         let on = std::ffi::OsStr::from_bytes(on.to_bytes()).to_owned();
+    ///         // Safety: `fsid_t` is two `i32`, the fields are private in `libc`
+    ///         let fsid: [i32; 2] = unsafe { std::mem::transmute(statfs.f_fsid) };
+    ///         self.0.insert(fsid[0] as dev_t, on);
+    ///     }
+    ///
+    ///     fn handle(&mut self, msg: &Message) {
+    ///         match msg.event() {
+    ///             Some(Event::NotifyMount(mount)) => self.on_mount(mount.statfs()),
+    ///             Some(Event::NotifyWrite(write)) => {
+    ///                 let target = write.target();
+    ///                 if let Some(volume) = self.0.get(&target.device()) {
+    ///                     println!("write to {:?} on volume {volume:?}", target.path());
+    ///                 }
+    ///             },
+    ///             _ => {},
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Volumes mounted before the client was created are not reported by mount events: the app
+    /// must list them at startup, with `getmntinfo(3)` for example.
+    #[inline(always)]
+    pub fn device(&self) -> dev_t {
+        self.0.stat.st_dev
+    }
+
+    /// Inode of the file (`st_ino` of [`Self::stat()`]), unique on the [device][Self::device()].
+    #[inline(always)]
+    pub fn inode(&self) -> ino_t {
+        self.0.stat.st_ino
+    }
+
+    /// Flags of the file (`st_flags` of [`Self::stat()`]), as [`FileFlags`].
+    #[inline(always)]
+    pub fn flags_typed(&self) -> FileFlags {
+        FileFlags(self.0.stat.st_flags)
+    }
+
+    /// Owned copy of the file information, which can outlive the message.
+    pub fn to_owned(&self) -> FileInfo {
+        FileInfo {
+            path: self.path().to_os_string(),
+            path_truncated: self.path_truncated(),
+            stat: *self.stat(),
+        }
+    }
 }
 
 // Safety: safe to send across threads: does not contain any interior mutability nor depend on current thread state
@@ -351,6 +552,22 @@ unsafe impl Send for File<'_> {}
 
 impl_debug_eq_hash_with_functions!(File<'a>; path, path_truncated, stat);
 
+/// Owned copy of a [`File`], see [`File::to_owned()`].
+///
+/// Unlike [`File`], it does not borrow from the [`Message`] and can be kept after it is dropped,
+/// across `.await` points or sent to other threads.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileInfo {
+    /// See [`File::path()`]
+    pub path: OsString,
+    /// See [`File::path_truncated()`]
+    pub path_truncated: bool,
+    /// See [`File::stat()`]
+    pub stat: stat,
+}
+
+static_assertions::assert_impl_all!(FileInfo: Send, Sync);
+
 /// Information related to a process.
 pub struct Process<'a> {
     /// The raw reference.
@@ -412,6 +629,12 @@ impl<'a> Process<'a> {
         self.raw.codesigning_flags
     }
 
+    /// Code signing flags of the process, as [`CodesigningFlags`].
+    #[inline(always)]
+    pub fn codesigning_flags_typed(&self) -> CodesigningFlags {
+        CodesigningFlags(self.raw.codesigning_flags)
+    }
+
     /// Indicates whether the process is a platform binary.
     ///
     /// **Note**: A "platform binary" is a binary signed with Apple certificates.
@@ -428,6 +651,11 @@ impl<'a> Process<'a> {
     }
 
     /// Indicates this process has the Endpoint Security entitlement.
+    ///
+    /// **Warning**: take caution when processing events where this is `true`. If multiple ES
+    /// clients exist, actions taken by one client could trigger additional actions by the other
+    /// client, causing a potentially infinite cycle. Muting such processes or only logging their
+    /// events avoids this, see also [`Message::is_likely_es_feedback()`].
     #[inline(always)]
     pub fn is_es_client(&self) -> bool {
         self.raw.is_es_client
@@ -506,6 +734,18 @@ impl<'a> Process<'a> {
         }
     }
 
+    /// On version 4 and later, whether the process is its own responsible process, meaning the
+    /// [`responsible_audit_token`][Self::responsible_audit_token] identifies the process itself.
+    ///
+    /// A process responsible for itself is often a top-level process, launched directly instead of
+    /// on behalf of an app. See the warning on `responsible_audit_token` though.
+    #[cfg(feature = "macos_11_0_0")]
+    #[inline(always)]
+    pub fn is_self_responsible(&self) -> Option<bool> {
+        let responsible = self.responsible_audit_token()?;
+        Some(responsible.is_same_process(&self.audit_token()))
+    }
+
     /// Audit token of the parent process on version 4 and later, otherwise None.
     #[cfg(feature = "macos_11_0_0")]
     #[inline(always)]
@@ -516,6 +756,81 @@ impl<'a> Process<'a> {
             None
         }
     }
+
+    /// Owned copy of the process information, which can outlive the message.
+    ///
+    /// Strings and files are deep-copied, this allocates.
+    pub fn to_owned(&self) -> ProcessInfo {
+        ProcessInfo {
+            audit_token: self.audit_token(),
+            ppid: self.ppid(),
+            original_ppid: self.original_ppid(),
+            group_id: self.group_id(),
+            session_id: self.session_id(),
+            codesigning_flags: self.codesigning_flags(),
+            is_platform_binary: self.is_platform_binary(),
+            is_es_client: self.is_es_client(),
+            cdhash: self.cdhash(),
+            signing_id: self.signing_id().to_os_string(),
+            team_id: self.team_id().to_os_string(),
+            executable: self.executable().to_owned(),
+            #[cfg(feature = "macos_10_15_1")]
+            tty: self.tty().map(|tty| tty.to_owned()),
+            #[cfg(feature = "macos_10_15_4")]
+            start_time: self.start_time(),
+            #[cfg(feature = "macos_11_0_0")]
+            responsible_audit_token: self.responsible_audit_token(),
+            #[cfg(feature = "macos_11_0_0")]
+            parent_audit_token: self.parent_audit_token(),
+        }
+    }
+
+    /// Evaluate a code signing requirement (e.g. a designated requirement like
+    /// `anchor apple generic and identifier "com.example.app"`) against the running process.
+    ///
+    /// Returns `Ok(false)` when the process does not satisfy the requirement, and an error when the
+    /// requirement cannot be parsed or the process cannot be found anymore, for example because
+    /// it has exited.
+    ///
+    /// Identifying software this way is much more robust than comparing
+    /// [`signing_id()`][Self::signing_id] or [`team_id()`][Self::team_id] strings.
+    ///
+    /// **Performance**: this calls into Security.framework, which performs IPC and may validate
+    /// the code signature of the executable. It is far too slow to be called for each message and
+    /// especially in the deadline of `AUTH` messages: cache the results by executable (for example
+    /// by [`cdhash()`][Self::cdhash]). Security.framework may also trigger TCC prompts or fail
+    /// when the executable is located in a TCC-protected location the client has no access to.
+    #[cfg(feature = "security_framework")]
+    pub fn satisfies_requirement(&self, requirement: &str) -> Result<bool, security_framework::base::Error> {
+        use core_foundation::base::TCFType;
+        use core_foundation::data::CFData;
+        use security_framework::os::macos::code_signing::{Flags, GuestAttributes, SecCode, SecRequirement};
+
+        /// `errSecCSReqFailed`: the code does not satisfy the requirement
+        const ERR_SEC_CS_REQ_FAILED: i32 = -67050;
+
+        let requirement: SecRequirement = requirement.parse()?;
+
+        let token = self.raw.audit_token;
+        // Safety: `audit_token_t` is plain old data without padding, reading it as bytes is sound
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                (&token as *const audit_token_t).cast::<u8>(),
+                std::mem::size_of::<audit_token_t>(),
+            )
+        };
+        let token = CFData::from_buffer(bytes);
+
+        let mut attributes = GuestAttributes::new();
+        attributes.set_audit_token(token.as_concrete_TypeRef());
+
+        let code = SecCode::copy_guest_with_attribues(None, &attributes, Flags::NONE)?;
+        match code.check_validity(Flags::NONE, &requirement) {
+            Ok(()) => Ok(true),
+            Err(err) if err.code() == ERR_SEC_CS_REQ_FAILED => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
 }
 
 // Safety: safe to send across threads: does not contain any interior mutability nor depend on current thread state
@@ -545,6 +860,53 @@ impl_debug_eq_hash_with_functions!(
     tty,
 );
 
+/// Owned copy of a [`Process`], see [`Process::to_owned()`].
+///
+/// Unlike [`Process`], it does not borrow from the [`Message`] and can be kept after it is
+/// dropped, across `.await` points or sent to other threads. Fields that depend on the message
+/// version are already resolved: they are `None` when the message was too old to carry them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProcessInfo {
+    /// See [`Process::audit_token()`]
+    pub audit_token: AuditToken,
+    /// See [`Process::ppid()`]
+    pub ppid: pid_t,
+    /// See [`Process::original_ppid()`]
+    pub original_ppid: pid_t,
+    /// See [`Process::group_id()`]
+    pub group_id: pid_t,
+    /// See [`Process::session_id()`]
+    pub session_id: pid_t,
+    /// See [`Process::codesigning_flags()`]
+    pub codesigning_flags: u32,
+    /// See [`Process::is_platform_binary()`]
+    pub is_platform_binary: bool,
+    /// See [`Process::is_es_client()`]
+    pub is_es_client: bool,
+    /// See [`Process::cdhash()`]
+    pub cdhash: [u8; 20],
+    /// See [`Process::signing_id()`]
+    pub signing_id: OsString,
+    /// See [`Process::team_id()`]
+    pub team_id: OsString,
+    /// See [`Process::executable()`]
+    pub executable: FileInfo,
+    /// See [`Process::tty()`]
+    #[cfg(feature = "macos_10_15_1")]
+    pub tty: Option<FileInfo>,
+    /// See [`Process::start_time()`]
+    #[cfg(feature = "macos_10_15_4")]
+    pub start_time: Option<SystemTime>,
+    /// See [`Process::responsible_audit_token()`]
+    #[cfg(feature = "macos_11_0_0")]
+    pub responsible_audit_token: Option<AuditToken>,
+    /// See [`Process::parent_audit_token()`]
+    #[cfg(feature = "macos_11_0_0")]
+    pub parent_audit_token: Option<AuditToken>,
+}
+
+static_assertions::assert_impl_all!(ProcessInfo: Send, Sync);
+
 /// Describes machine-specific thread state as used by `thread_create_running()` and other Mach API functions.
 #[cfg(feature = "macos_11_0_0")]
 pub struct ThreadState<'a>(&'a es_thread_state_t);
@@ -577,3 +939,290 @@ unsafe impl Send for ThreadState<'_> {}
 
 #[cfg(feature = "macos_11_0_0")]
 impl_debug_eq_hash_with_functions!(ThreadState<'a>; flavor, state);
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    use super::*;
+    use crate::utils::{file_with_path, zeroed_raw};
+
+    #[test]
+    fn test_file() {
+        let mut raw = file_with_path("/private/tmp/file");
+        raw.stat.st_dev = 0x1000004;
+        raw.stat.st_ino = 12345678;
+
+        let file = File::new(&raw);
+        assert_eq!(file.as_path(), Path::new("/private/tmp/file"));
+        assert_eq!(file.as_path().file_name().unwrap(), "file");
+        assert_eq!(file.device(), file.stat().st_dev);
+        assert_eq!(file.device(), 0x1000004);
+        assert_eq!(file.inode(), file.stat().st_ino);
+        assert_eq!(file.inode(), 12345678);
+        assert_eq!(file.flags_typed(), FileFlags(0));
+
+        raw.stat.st_flags = libc::UF_HIDDEN | libc::SF_IMMUTABLE;
+        let file = File::new(&raw);
+        assert!(file.flags_typed().is_hidden());
+        assert!(file.flags_typed().is_immutable());
+    }
+
+    #[test]
+    fn test_file_to_owned() {
+        let owned = {
+            // Path and raw file only live in this scope
+            let path = String::from("/private/tmp/file");
+            let mut raw = file_with_path("/private/tmp/file");
+            raw.stat.st_ino = 42;
+            raw.path_truncated = true;
+            raw.path.data = path.as_ptr().cast();
+
+            let file = File::new(&raw);
+            let owned = file.to_owned();
+            assert_eq!(owned.path, file.path());
+            assert_eq!(owned.stat, *file.stat());
+            owned
+        };
+
+        assert_eq!(owned.path, "/private/tmp/file");
+        assert!(owned.path_truncated);
+        assert_eq!(owned.stat.st_ino, 42);
+    }
+
+    #[test]
+    fn test_process_executable_and_tty() {
+        #[cfg_attr(not(feature = "macos_10_15_1"), allow(unused_mut, unused_variables))]
+        let mut tty = file_with_path("/dev/ttys002");
+        let mut executable = file_with_path("/bin/zsh");
+        executable.stat.st_ino = 99;
+
+        let mut raw: es_process_t = zeroed_raw();
+        raw.executable = ShouldNotBeNull::new(&mut executable);
+
+        let process = Process::new(&raw, 2);
+        assert_eq!(process.executable().path(), "/bin/zsh");
+        assert_eq!(process.executable().inode(), 99);
+        #[cfg(feature = "macos_10_15_1")]
+        {
+            // Null tty
+            assert!(process.tty().is_none());
+
+            raw.tty = &mut tty;
+            let process = Process::new(&raw, 2);
+            assert_eq!(process.tty().unwrap().path(), "/dev/ttys002");
+
+            // The tty field only exists from version 2
+            assert!(Process::new(&raw, 1).tty().is_none());
+        }
+    }
+
+    #[test]
+    fn test_process_to_owned() {
+        #[cfg_attr(not(feature = "macos_10_15_1"), allow(unused_mut, unused_variables))]
+        let mut tty = file_with_path("/dev/ttys001");
+        let mut executable = file_with_path("/usr/bin/ssh");
+        executable.stat.st_ino = 7;
+
+        let mut raw: es_process_t = zeroed_raw();
+        raw.audit_token.val[5] = 1234;
+        raw.ppid = 1;
+        raw.original_ppid = 2;
+        raw.group_id = 3;
+        raw.session_id = 4;
+        raw.codesigning_flags = CS_VALID;
+        raw.is_platform_binary = true;
+        raw.cdhash = [0xab; 20];
+        raw.signing_id = utils::string_token("com.apple.ssh");
+        raw.team_id = utils::string_token("");
+        raw.executable = ShouldNotBeNull::new(&mut executable);
+        #[cfg(feature = "macos_10_15_1")]
+        {
+            raw.tty = &mut tty;
+        }
+        #[cfg(feature = "macos_10_15_4")]
+        {
+            raw.start_time.tv_sec = 1_700_000_000;
+        }
+
+        let process = Process::new(&raw, 4);
+        let owned = process.to_owned();
+
+        assert_eq!(owned.audit_token, process.audit_token());
+        assert_eq!(owned.ppid, 1);
+        assert_eq!(owned.original_ppid, 2);
+        assert_eq!(owned.group_id, 3);
+        assert_eq!(owned.session_id, 4);
+        assert_eq!(owned.codesigning_flags, CS_VALID);
+        assert!(owned.is_platform_binary);
+        assert!(owned.is_es_client == false);
+        assert_eq!(owned.cdhash, [0xab; 20]);
+        assert_eq!(owned.signing_id, "com.apple.ssh");
+        assert_eq!(owned.team_id, "");
+        assert_eq!(owned.executable, process.executable().to_owned());
+        assert_eq!(owned.executable.path, "/usr/bin/ssh");
+        assert_eq!(owned.executable.stat.st_ino, 7);
+        #[cfg(feature = "macos_10_15_1")]
+        assert_eq!(owned.tty.as_ref().unwrap().path, "/dev/ttys001");
+        #[cfg(feature = "macos_10_15_4")]
+        assert_eq!(owned.start_time, process.start_time());
+        #[cfg(feature = "macos_11_0_0")]
+        {
+            assert_eq!(
+                owned.responsible_audit_token,
+                process.responsible_audit_token()
+            );
+            assert_eq!(owned.parent_audit_token, process.parent_audit_token());
+        }
+
+        // The copy can be sent to another thread
+        let owned = std::thread::spawn(move || owned).join().unwrap();
+        assert_eq!(owned.executable.path, "/usr/bin/ssh");
+
+        // The copy outlives the raw process and the strings it points to
+        let owned = {
+            let signing_id = String::from("com.example.app");
+            let mut executable = file_with_path("/Applications/App.app/Contents/MacOS/App");
+            let mut raw: es_process_t = zeroed_raw();
+            raw.signing_id.data = signing_id.as_ptr().cast();
+            raw.signing_id.length = signing_id.len();
+            raw.executable = ShouldNotBeNull::new(&mut executable);
+            Process::new(&raw, 4).to_owned()
+        };
+        assert_eq!(owned.signing_id, "com.example.app");
+        assert_eq!(
+            owned.executable.path,
+            "/Applications/App.app/Contents/MacOS/App"
+        );
+
+        // Version dependent fields are resolved at copy time
+        let mut raw: es_process_t = zeroed_raw();
+        raw.executable = ShouldNotBeNull::new(&mut executable);
+        #[cfg(feature = "macos_10_15_1")]
+        {
+            raw.tty = &mut tty;
+        }
+        let owned = Process::new(&raw, 1).to_owned();
+        #[cfg(feature = "macos_10_15_1")]
+        assert!(owned.tty.is_none());
+        #[cfg(feature = "macos_10_15_4")]
+        assert!(owned.start_time.is_none());
+        assert_eq!(owned.executable.path, "/usr/bin/ssh");
+    }
+
+    /// Wrap `raw` in a [`Message`] that is never released: it was not retained through Endpoint
+    /// Security
+    fn message(raw: &es_message_t) -> ManuallyDrop<Message> {
+        ManuallyDrop::new(Message(NonNull::from(raw)))
+    }
+
+    #[test]
+    fn test_deadline_duration() {
+        use mach2::mach_time::{mach_absolute_time, mach_timebase_info};
+
+        /// Mach ticks for `secs` seconds
+        fn ticks(secs: u64) -> u64 {
+            let mut info = mach_timebase_info { numer: 0, denom: 0 };
+            // Safety: `info` is a valid pointer to a mach_timebase_info
+            unsafe { mach_timebase_info(&mut info) };
+            secs * 1_000_000_000 * u64::from(info.denom) / u64::from(info.numer)
+        }
+
+        let mut raw: es_message_t = zeroed_raw();
+        // Safety: always safe to call
+        let now = unsafe { mach_absolute_time() };
+
+        raw.deadline = now + ticks(60);
+        let left = message(&raw).deadline_duration().unwrap();
+        assert!(left <= Duration::from_secs(60), "{left:?}");
+        assert!(left > Duration::from_secs(50), "{left:?}");
+
+        raw.deadline = now - ticks(1);
+        assert_eq!(message(&raw).deadline_duration(), Ok(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_process_audit_token() {
+        let mut process: es_process_t = zeroed_raw();
+        process.audit_token.val = [1, 501, 20, 501, 20, 4242, 100001, 3];
+        let mut raw: es_message_t = zeroed_raw();
+        raw.version = 4;
+        raw.process = ShouldNotBeNull::new(&mut process);
+
+        let message = message(&raw);
+        assert_eq!(
+            message.process_audit_token(),
+            message.process().audit_token()
+        );
+        assert!(message.process_audit_token().raw_eq(&message.process().audit_token()));
+        assert_eq!(message.process_audit_token().raw_token().val[5], 4242);
+    }
+
+    /// Micro-benchmark of [`Message::process_audit_token()`] against going through
+    /// [`Message::process()`], run with:
+    ///
+    /// ```sh
+    /// cargo test --release --features max -- --ignored --nocapture bench_process_audit_token
+    /// ```
+    ///
+    /// Both take the same time: building a [`Process`] only stores a reference and the message
+    /// version, which the optimizer removes. This is why `Message::process()` is not cached.
+    #[test]
+    #[ignore = "benchmark, prints timings"]
+    fn bench_process_audit_token() {
+        use std::hint::black_box;
+
+        const ITERATIONS: u32 = 10_000_000;
+
+        let mut process: es_process_t = zeroed_raw();
+        let mut raw: es_message_t = zeroed_raw();
+        raw.version = 4;
+        raw.process = ShouldNotBeNull::new(&mut process);
+        let message = message(&raw);
+
+        /// Total time of [`ITERATIONS`] calls to `f`
+        fn measure(f: impl Fn() -> AuditToken) -> Duration {
+            let start = Instant::now();
+            for _ in 0..ITERATIONS {
+                black_box(f());
+            }
+            start.elapsed()
+        }
+
+        let through_process = measure(|| black_box(&*message).process().audit_token());
+        let direct = measure(|| black_box(&*message).process_audit_token());
+        println!("process().audit_token(): {through_process:?} for {ITERATIONS} calls");
+        println!("process_audit_token():   {direct:?} for {ITERATIONS} calls");
+    }
+
+    #[test]
+    fn test_time_left() {
+        let now = Instant::now();
+
+        let soon = now + Duration::from_millis(50);
+        assert_eq!(time_left(soon, now), Duration::from_millis(50));
+
+        let past = now.checked_sub(Duration::from_millis(50)).unwrap();
+        assert_eq!(time_left(past, now), Duration::ZERO);
+        assert_eq!(time_left(now, now), Duration::ZERO);
+    }
+
+    #[test]
+    #[cfg(feature = "macos_11_0_0")]
+    fn test_process_audit_tokens_need_version_4() {
+        let mut raw: es_process_t = zeroed_raw();
+        raw.responsible_audit_token.val[5] = 12;
+        raw.parent_audit_token.val[5] = 34;
+
+        // Compare the raw values to keep the test independent from libbsm
+        let raw_pid = |token: AuditToken| token.raw_token().val[5];
+
+        let process = Process::new(&raw, 3);
+        assert!(process.responsible_audit_token().is_none());
+        assert!(process.parent_audit_token().is_none());
+
+        let process = Process::new(&raw, 4);
+        assert_eq!(process.responsible_audit_token().map(raw_pid), Some(12));
+        assert_eq!(process.parent_audit_token().map(raw_pid), Some(34));
+    }
+}
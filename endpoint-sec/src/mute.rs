@@ -1,6 +1,8 @@
 //! Mute types.
 
-use std::ffi::OsString;
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::os::unix::ffi::OsStrExt;
 
 use endpoint_sec_sys::{es_event_type_t, es_mute_path_type_t};
 
@@ -20,6 +22,102 @@ pub struct MutedPath {
 
 static_assertions::assert_impl_all!(MutedPath: Send);
 
+impl MutedPath {
+    /// `true` if the path is muted for `event`.
+    ///
+    /// An empty list of events means the path is muted for all events.
+    #[inline]
+    pub fn matches(&self, event: es_event_type_t) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+
+    /// `true` if `path` is matched by this rule: equal to it for literal rules, starting with it
+    /// for prefix rules. Rules of an unknown type match nothing.
+    ///
+    /// Like in Endpoint Security, prefixes are compared byte by byte without regard for path
+    /// separators: `/usr/bin` matches `/usr/bin/ls` but also `/usr/bin2`. Rules should end with a
+    /// `/` to only match the content of a directory.
+    pub fn matches_path(&self, path: &OsStr) -> bool {
+        // Raw values are used since the target types only exist from macOS 13.0.0
+        match self.ty.0 {
+            0 | 2 => path.as_bytes().starts_with(self.path.as_bytes()),
+            1 | 3 => path == self.path,
+            _ => false,
+        }
+    }
+}
+
+/// `true` if one of the process path rules (`ES_MUTE_PATH_TYPE_PREFIX` and
+/// `ES_MUTE_PATH_TYPE_LITERAL`) of `rules` mutes `path` for `event`, or for all events when `event`
+/// is `None`.
+#[cfg(feature = "macos_12_0_0")]
+pub(crate) fn is_path_muted_by(rules: &[MutedPath], path: &OsStr, event: Option<es_event_type_t>) -> bool {
+    rules.iter().any(|rule| {
+        let is_process_rule = rule.ty == es_mute_path_type_t::ES_MUTE_PATH_TYPE_PREFIX
+            || rule.ty == es_mute_path_type_t::ES_MUTE_PATH_TYPE_LITERAL;
+        let matches_event = match event {
+            Some(event) => rule.matches(event),
+            None => rule.events.is_empty(),
+        };
+        is_process_rule && matches_event && rule.matches_path(path)
+    })
+}
+
+/// Explanation of the muting of an event of type `event_type` involving `path` by `rules`, with
+/// the inversion state of the process path and target path dimensions. See
+/// [`Client::explain_muting()`][crate::Client::explain_muting].
+#[cfg(feature = "macos_13_0_0")]
+pub(crate) fn explain_muting_by(
+    rules: &[MutedPath],
+    path: &OsStr,
+    event_type: es_event_type_t,
+    process_path_inverted: bool,
+    target_path_inverted: bool,
+) -> MutingExplanation {
+    let find_rule = |prefix, literal| {
+        rules
+            .iter()
+            .find(|rule| {
+                (rule.ty == prefix || rule.ty == literal) && rule.matches_path(path) && rule.matches(event_type)
+            })
+            .cloned()
+    };
+
+    MutingExplanation {
+        process_path: MutingDimension {
+            inverted: process_path_inverted,
+            matched_rule: find_rule(
+                es_mute_path_type_t::ES_MUTE_PATH_TYPE_PREFIX,
+                es_mute_path_type_t::ES_MUTE_PATH_TYPE_LITERAL,
+            ),
+        },
+        target_path: MutingDimension {
+            inverted: target_path_inverted,
+            matched_rule: find_rule(
+                es_mute_path_type_t::ES_MUTE_PATH_TYPE_TARGET_PREFIX,
+                es_mute_path_type_t::ES_MUTE_PATH_TYPE_TARGET_LITERAL,
+            ),
+        },
+    }
+}
+
+/// Displayed as the type of path, the path and the events, e.g.
+/// `prefix "/usr/bin/" for notify_exec, notify_open`.
+impl fmt::Display for MutedPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Raw values are used since the target types only exist from macOS 13.0.0
+        match self.ty.0 {
+            0 => f.write_str("prefix")?,
+            1 => f.write_str("literal")?,
+            2 => f.write_str("target prefix")?,
+            3 => f.write_str("target literal")?,
+            ty => write!(f, "type {ty}")?,
+        }
+        write!(f, " {:?} for ", self.path)?;
+        fmt_events(&self.events, f)
+    }
+}
+
 /// See [`endpoint_sec_sys::es_muted_process_t`]
 #[doc(alias = "es_muted_process_t")]
 #[derive(Debug, Clone)]
@@ -31,3 +129,301 @@ pub struct MutedProcess {
 }
 
 static_assertions::assert_impl_all!(MutedProcess: Send);
+
+impl MutedProcess {
+    /// `true` if the process is muted for `event`.
+    ///
+    /// An empty list of events means the process is muted for all events.
+    #[inline]
+    pub fn contains_event(&self, event: es_event_type_t) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+/// Write the names of `events` separated by commas, or `all events` when empty
+fn fmt_events(events: &[es_event_type_t], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if events.is_empty() {
+        return f.write_str("all events");
+    }
+
+    for (i, event) in events.iter().enumerate() {
+        if i != 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{event}")?;
+    }
+    Ok(())
+}
+
+/// Result of [`Client::explain_muting()`][crate::Client::explain_muting]: whether an event involving
+/// a path would be suppressed by the muting of a client, and why.
+///
+/// Endpoint Security suppresses an event as soon as one of the muting dimensions mutes it. In a
+/// dimension that is not inverted, an event is muted if a rule matches it. When the dimension is
+/// inverted (see [`Client::invert_muting()`][crate::Client::invert_muting]), the rules select the
+/// events to deliver instead, so an event is muted if **no** rule matches it.
+#[cfg(feature = "macos_13_0_0")]
+#[derive(Debug, Clone)]
+pub struct MutingExplanation {
+    /// The path seen as the executable of the process performing the action, checked against the
+    /// `ES_MUTE_PATH_TYPE_PREFIX` and `ES_MUTE_PATH_TYPE_LITERAL` rules
+    pub process_path: MutingDimension,
+    /// The path seen as the target of the event, checked against the
+    /// `ES_MUTE_PATH_TYPE_TARGET_PREFIX` and `ES_MUTE_PATH_TYPE_TARGET_LITERAL` rules
+    pub target_path: MutingDimension,
+}
+
+#[cfg(feature = "macos_13_0_0")]
+impl MutingExplanation {
+    /// `true` if the event would be suppressed by at least one dimension.
+    #[inline]
+    pub fn is_suppressed(&self) -> bool {
+        self.process_path.is_muted() || self.target_path.is_muted()
+    }
+}
+
+#[cfg(feature = "macos_13_0_0")]
+impl fmt::Display for MutingExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_suppressed() {
+            f.write_str("suppressed")?;
+        } else {
+            f.write_str("delivered")?;
+        }
+        write!(
+            f,
+            " (process path: {}; target path: {})",
+            self.process_path, self.target_path
+        )
+    }
+}
+
+/// Muting state of a single dimension in a [`MutingExplanation`].
+#[cfg(feature = "macos_13_0_0")]
+#[derive(Debug, Clone)]
+pub struct MutingDimension {
+    /// Whether muting is inverted for this dimension
+    pub inverted: bool,
+    /// First rule matching the path and the event type, if any
+    pub matched_rule: Option<MutedPath>,
+}
+
+#[cfg(feature = "macos_13_0_0")]
+impl MutingDimension {
+    /// `true` if this dimension suppresses the event: a rule matched and muting is not inverted, or
+    /// no rule matched and muting is inverted.
+    #[inline]
+    pub fn is_muted(&self) -> bool {
+        self.matched_rule.is_some() != self.inverted
+    }
+}
+
+#[cfg(feature = "macos_13_0_0")]
+impl fmt::Display for MutingDimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.is_muted() { "muted" } else { "not muted" })?;
+        match (&self.matched_rule, self.inverted) {
+            (Some(rule), false) => write!(f, ", matched {:?} {:?}", rule.ty, rule.path),
+            (Some(rule), true) => write!(
+                f,
+                ", matched {:?} {:?} which is selected by inversion",
+                rule.ty, rule.path
+            ),
+            (None, false) => f.write_str(", no rule matched"),
+            (None, true) => f.write_str(", no rule matched and muting is inverted"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXEC: es_event_type_t = es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXEC;
+    const OPEN: es_event_type_t = es_event_type_t::ES_EVENT_TYPE_NOTIFY_OPEN;
+    const FORK: es_event_type_t = es_event_type_t::ES_EVENT_TYPE_NOTIFY_FORK;
+
+    #[test]
+    fn test_muted_path() {
+        let all = MutedPath {
+            ty: es_mute_path_type_t::ES_MUTE_PATH_TYPE_PREFIX,
+            events: Vec::new(),
+            path: "/usr/bin/".into(),
+        };
+        assert!(all.matches(EXEC));
+        assert!(all.matches(FORK));
+        assert_eq!(all.to_string(), r#"prefix "/usr/bin/" for all events"#);
+
+        let subset = MutedPath {
+            ty: es_mute_path_type_t::ES_MUTE_PATH_TYPE_LITERAL,
+            events: vec![EXEC, OPEN],
+            path: "/bin/ls".into(),
+        };
+        assert!(subset.matches(EXEC));
+        assert!(subset.matches(OPEN));
+        assert!(subset.matches(FORK) == false);
+        assert_eq!(
+            subset.to_string(),
+            r#"literal "/bin/ls" for notify_exec, notify_open"#
+        );
+
+        let unknown = MutedPath {
+            ty: es_mute_path_type_t(42),
+            events: vec![es_event_type_t(1000)],
+            path: "/tmp".into(),
+        };
+        assert_eq!(unknown.to_string(), r#"type 42 "/tmp" for 1000"#);
+    }
+
+    #[test]
+    fn test_muted_path_matches_path() {
+        let rule = |ty, path: &str| MutedPath {
+            ty,
+            events: Vec::new(),
+            path: path.into(),
+        };
+        let path = |s| OsStr::new(s);
+
+        let literal = rule(es_mute_path_type_t::ES_MUTE_PATH_TYPE_LITERAL, "/bin/ls");
+        assert!(literal.matches_path(path("/bin/ls")));
+        assert!(literal.matches_path(path("/bin/ls/")) == false);
+        assert!(literal.matches_path(path("/bin/l")) == false);
+
+        // Byte prefix, not aligned on separators
+        let prefix = rule(es_mute_path_type_t::ES_MUTE_PATH_TYPE_PREFIX, "/usr/bin");
+        assert!(prefix.matches_path(path("/usr/bin")));
+        assert!(prefix.matches_path(path("/usr/bin/ls")));
+        assert!(prefix.matches_path(path("/usr/bin2")));
+        assert!(prefix.matches_path(path("/usr/bi")) == false);
+
+        // Trailing separator: only the content of the directory
+        let dir = rule(es_mute_path_type_t::ES_MUTE_PATH_TYPE_PREFIX, "/usr/bin/");
+        assert!(dir.matches_path(path("/usr/bin/ls")));
+        assert!(dir.matches_path(path("/usr/bin")) == false);
+        assert!(dir.matches_path(path("/usr/bin2")) == false);
+
+        assert!(rule(es_mute_path_type_t(42), "/").matches_path(path("/tmp")) == false);
+    }
+
+    #[test]
+    #[cfg(feature = "macos_12_0_0")]
+    fn test_is_path_muted_by() {
+        let rules = [
+            MutedPath {
+                ty: es_mute_path_type_t::ES_MUTE_PATH_TYPE_LITERAL,
+                events: Vec::new(),
+                path: "/bin/ls".into(),
+            },
+            MutedPath {
+                ty: es_mute_path_type_t::ES_MUTE_PATH_TYPE_PREFIX,
+                events: vec![EXEC],
+                path: "/usr/bin/".into(),
+            },
+            // Target path rule, ignored
+            MutedPath {
+                ty: es_mute_path_type_t(2),
+                events: Vec::new(),
+                path: "/tmp/".into(),
+            },
+        ];
+        let is_muted = |path, event| is_path_muted_by(&rules, OsStr::new(path), event);
+
+        // Literal match, for all events
+        assert!(is_muted("/bin/ls", Some(EXEC)));
+        assert!(is_muted("/bin/ls", Some(OPEN)));
+        assert!(is_muted("/bin/ls", None));
+        assert!(is_muted("/bin/lsof", Some(EXEC)) == false);
+
+        // Prefix match, for exec only
+        assert!(is_muted("/usr/bin/env", Some(EXEC)));
+        assert!(is_muted("/usr/bin/env", Some(OPEN)) == false);
+        assert!(is_muted("/usr/bin/env", None) == false);
+        assert!(is_muted("/usr/binary", Some(EXEC)) == false);
+
+        assert!(is_muted("/tmp/file", Some(OPEN)) == false);
+        assert!(is_path_muted_by(&[], OsStr::new("/bin/ls"), None) == false);
+    }
+
+    #[test]
+    #[cfg(feature = "macos_13_0_0")]
+    fn test_explain_muting_by() {
+        let rules = [
+            MutedPath {
+                ty: es_mute_path_type_t::ES_MUTE_PATH_TYPE_PREFIX,
+                events: vec![EXEC],
+                path: "/usr/bin/".into(),
+            },
+            MutedPath {
+                ty: es_mute_path_type_t::ES_MUTE_PATH_TYPE_TARGET_LITERAL,
+                events: Vec::new(),
+                path: "/etc/hosts".into(),
+            },
+        ];
+        let explain = |path, event, process_inverted, target_inverted| {
+            explain_muting_by(
+                &rules,
+                OsStr::new(path),
+                event,
+                process_inverted,
+                target_inverted,
+            )
+        };
+
+        // Process path rule, only for its events
+        let explanation = explain("/usr/bin/env", EXEC, false, false);
+        assert_eq!(
+            explanation.process_path.matched_rule.as_ref().unwrap().path,
+            "/usr/bin/"
+        );
+        assert!(explanation.target_path.matched_rule.is_none());
+        assert!(explanation.is_suppressed());
+        assert!(explain("/usr/bin/env", OPEN, false, false).is_suppressed() == false);
+
+        // Target path rule, process rules are not used for the target dimension and the other way
+        let explanation = explain("/etc/hosts", OPEN, false, false);
+        assert!(explanation.process_path.matched_rule.is_none());
+        assert_eq!(
+            explanation.target_path.matched_rule.as_ref().unwrap().ty,
+            es_mute_path_type_t::ES_MUTE_PATH_TYPE_TARGET_LITERAL
+        );
+        assert!(explanation.is_suppressed());
+
+        // Nothing matches
+        let explanation = explain("/tmp/file", OPEN, false, false);
+        assert!(explanation.is_suppressed() == false);
+        assert_eq!(
+            explanation.to_string(),
+            "delivered (process path: not muted, no rule matched; target path: not muted, no rule matched)"
+        );
+
+        // Inversion: matched rules select the events to deliver, the others are muted
+        let explanation = explain("/usr/bin/env", EXEC, true, false);
+        assert!(explanation.process_path.is_muted() == false);
+        assert!(explanation.is_suppressed() == false);
+        let explanation = explain("/tmp/file", OPEN, true, false);
+        assert!(explanation.process_path.is_muted());
+        assert!(explanation.is_suppressed());
+        let explanation = explain("/etc/hosts", OPEN, false, true);
+        assert!(explanation.target_path.is_muted() == false);
+        assert!(explanation.is_suppressed() == false);
+    }
+
+    #[test]
+    fn test_muted_process() {
+        let token = AuditToken::new(Default::default());
+
+        let all = MutedProcess {
+            audit_token: token,
+            events: Vec::new(),
+        };
+        assert!(all.contains_event(EXEC));
+        assert!(all.contains_event(FORK));
+
+        let subset = MutedProcess {
+            audit_token: token,
+            events: vec![OPEN],
+        };
+        assert!(subset.contains_event(OPEN));
+        assert!(subset.contains_event(EXEC) == false);
+    }
+}
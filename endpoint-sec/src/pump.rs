@@ -0,0 +1,101 @@
+//! Manual draining of messages through a channel: [`MessagePump`]
+//!
+//! Endpoint Security only supports the callback model: messages are delivered by the framework on
+//! its own dispatch queue to the handler given to [`es_new_client()`][sys_new_client], there is no
+//! API to queue them up inside the framework and fetch them later. For apps that have their own
+//! run loop, [`Client::new_with_pump()`] installs a handler that forwards every message into a
+//! channel which can then be drained explicitly with the [`MessagePump`].
+//!
+//! [sys_new_client]: endpoint_sec_sys::es_new_client
+
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError};
+use std::time::Duration;
+
+use endpoint_sec_sys::NewClientError;
+
+use crate::{Client, Message};
+
+/// Receiving end of the channel filled by a client created with [`Client::new_with_pump()`].
+///
+/// # `AUTH` deadlines
+///
+/// Forwarding a message does not extend its deadline: when an `AUTH` message sits in the pump, its
+/// deadline is still running and macOS will kill the client if it is not answered in time. Apps
+/// using the pump must drain it often enough to answer every `AUTH` message before
+/// [`Message::deadline()`], and should prefer [`MessagePump::recv_timeout()`] over
+/// [`MessagePump::recv()`] when they also need to do other work in their loop. Subscribing only to
+/// `NOTIFY` events avoids the problem entirely.
+///
+/// Responses are sent through the [`Client`] with [`Client::respond_auth_result()`] or
+/// [`Client::respond_flags_result()`]: since the client is neither [`Send`] nor [`Sync`], the
+/// simplest setup is to drain the pump on the thread that owns the client.
+///
+/// # Disconnection
+///
+/// The channel is closed when Endpoint Security releases the handler, which happens after the
+/// client is deleted. After this, [`MessagePump::recv()`] returns `None` and the other methods
+/// return a `Disconnected` error once all pending messages have been drained.
+pub struct MessagePump {
+    /// Receiving end of the channel, the sending end lives in the client handler.
+    receiver: Receiver<Message>,
+}
+
+static_assertions::assert_impl_all!(MessagePump: Send);
+
+/// Creation of a [`Client`] that is drained manually.
+impl Client<'_> {
+    /// Creates a new [`Client`] whose messages are forwarded to the returned [`MessagePump`] instead
+    /// of being handled in a callback.
+    ///
+    /// The same requirements as for [`Client::new()`] apply. Read the documentation of
+    /// [`MessagePump`] for the constraints on `AUTH` messages.
+    pub fn new_with_pump() -> Result<(Client<'static>, MessagePump), NewClientError> {
+        let (sender, receiver) = mpsc::channel();
+
+        let client = Client::new(move |_, message| {
+            // An error here means the pump was dropped: there is no one left to handle the
+            // message and nothing useful to do with it either.
+            let _err = sender.send(message);
+        })?;
+
+        Ok((client, MessagePump { receiver }))
+    }
+}
+
+impl MessagePump {
+    /// Blocks until the next message is available.
+    ///
+    /// Returns `None` once the client has been deleted and all pending messages have been received.
+    #[inline]
+    pub fn recv(&self) -> Option<Message> {
+        self.receiver.recv().ok()
+    }
+
+    /// Returns the next message if one is already available, without blocking.
+    #[inline]
+    pub fn try_recv(&self) -> Result<Message, TryRecvError> {
+        self.receiver.try_recv()
+    }
+
+    /// Waits at most `timeout` for the next message.
+    #[inline]
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<Message, RecvTimeoutError> {
+        self.receiver.recv_timeout(timeout)
+    }
+
+    /// Iterates over the messages that are already available, without blocking.
+    #[inline]
+    pub fn try_iter(&self) -> impl Iterator<Item = Message> + '_ {
+        self.receiver.try_iter()
+    }
+}
+
+/// Blocks on each call to [`next()`][Iterator::next], see [`MessagePump::recv()`].
+impl Iterator for MessagePump {
+    type Item = Message;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.recv()
+    }
+}
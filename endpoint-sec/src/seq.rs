@@ -0,0 +1,214 @@
+//! Detection of dropped messages through sequence numbers: [`SeqTracker`]
+//!
+//! Endpoint Security numbers the messages it sends to a client: `seq_num` is incremented for each
+//! message of a given event type and `global_seq_num` for every message. A gap between two
+//! consecutive messages means the ones in between were dropped, usually because the client did not
+//! handle them fast enough.
+
+use std::collections::HashMap;
+
+use endpoint_sec_sys::es_event_type_t;
+
+use crate::Message;
+
+/// Number of messages dropped between two observations, returned by [`SeqTracker::observe()`].
+///
+/// Counts are 0 when they cannot be computed, for example on the first observation or when the
+/// message version is too low to carry the corresponding sequence number.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DropInfo {
+    /// Messages dropped since the last observed message, whatever their event type.
+    ///
+    /// Computed from [`Message::global_seq_num()`], 0 before macOS 11.0.0.
+    pub global: u64,
+    /// Messages of the same event type dropped since the last observed message of that type.
+    ///
+    /// Computed from [`Message::seq_num()`].
+    pub per_type: u64,
+}
+
+impl DropInfo {
+    /// `true` if any message was dropped.
+    #[inline(always)]
+    pub fn has_drops(&self) -> bool {
+        self.global != 0 || self.per_type != 0
+    }
+}
+
+/// Tracks the sequence numbers of messages to detect the ones that were dropped.
+///
+/// All the messages of a client must be observed, in the order they were received, for the counts
+/// to be accurate. Sequence numbers wrap around on overflow. A sequence number going backwards
+/// (for example after Endpoint Security restarted numbering) is treated as a reset and reports no
+/// drops.
+///
+/// The handler of a [`Client`][crate::Client] only has shared access to its state, the tracker
+/// must be put behind a [`Mutex`][std::sync::Mutex] (or used through an
+/// [`EventHandler`][crate::EventHandler]):
+///
+/// ```no_run
+/// use std::sync::Mutex;
+///
+/// use endpoint_sec::{Client, SeqTracker};
+///
+/// let tracker = Mutex::new(SeqTracker::new());
+/// let client = Client::new(move |_client, message| {
+///     let dropped = tracker.lock().unwrap().observe(&message);
+///     if dropped.has_drops() {
+///         eprintln!("dropped {} messages", dropped.global);
+///     }
+/// })?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct SeqTracker {
+    /// Last seen `global_seq_num`
+    #[cfg(feature = "macos_11_0_0")]
+    last_global: Option<u64>,
+    /// Last seen `seq_num` for each event type
+    last_per_type: HashMap<es_event_type_t, u64>,
+}
+
+impl SeqTracker {
+    /// New tracker, which has not observed any message yet.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the sequence numbers of `message` and return how many messages were dropped since
+    /// the previous observation.
+    #[inline]
+    pub fn observe(&mut self, message: &Message) -> DropInfo {
+        #[cfg(feature = "macos_11_0_0")]
+        let global_seq_num = message.global_seq_num();
+        #[cfg(not(feature = "macos_11_0_0"))]
+        let global_seq_num = None;
+
+        self.observe_raw(message.event_type(), message.seq_num(), global_seq_num)
+    }
+
+    /// Forget all observed sequence numbers, the next observation will report no drops.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Implementation of [`Self::observe()`], separated to be testable without a [`Message`].
+    #[cfg_attr(not(feature = "macos_11_0_0"), allow(unused_variables))]
+    fn observe_raw(
+        &mut self,
+        event_type: es_event_type_t,
+        seq_num: Option<u64>,
+        global_seq_num: Option<u64>,
+    ) -> DropInfo {
+        let mut info = DropInfo::default();
+
+        #[cfg(feature = "macos_11_0_0")]
+        if let Some(current) = global_seq_num {
+            info.global = self.last_global.map_or(0, |last| dropped_between(last, current));
+            self.last_global = Some(current);
+        }
+
+        if let Some(current) = seq_num {
+            if let Some(last) = self.last_per_type.insert(event_type, current) {
+                info.per_type = dropped_between(last, current);
+            }
+        }
+
+        info
+    }
+}
+
+/// Number of sequence numbers strictly between `last` and `current`, accounting for wraparound.
+///
+/// Observing the same sequence number twice is not a drop. When `current` is behind `last`, the
+/// sequence was reset and there is no drop: a distance of more than half the range of `u64` cannot
+/// come from a wraparound, sequence numbers never advance that much between two messages.
+#[inline]
+fn dropped_between(last: u64, current: u64) -> u64 {
+    let distance = current.wrapping_sub(last);
+    if distance > u64::MAX / 2 {
+        return 0;
+    }
+    distance.saturating_sub(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXEC: es_event_type_t = es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXEC;
+    const OPEN: es_event_type_t = es_event_type_t::ES_EVENT_TYPE_NOTIFY_OPEN;
+
+    #[test]
+    fn test_dropped_between() {
+        assert_eq!(dropped_between(4, 5), 0);
+        assert_eq!(dropped_between(4, 8), 3);
+        assert_eq!(dropped_between(4, 4), 0);
+        assert_eq!(dropped_between(u64::MAX, 0), 0);
+        assert_eq!(dropped_between(u64::MAX - 1, 2), 3);
+
+        // Going backwards is a reset, not a wraparound
+        assert_eq!(dropped_between(8, 4), 0);
+        assert_eq!(dropped_between(1000, 0), 0);
+        assert_eq!(dropped_between(2, u64::MAX), 0);
+    }
+
+    #[test]
+    fn test_seq_tracker_per_type() {
+        let mut tracker = SeqTracker::new();
+
+        // First observation of each type cannot know about earlier drops
+        assert_eq!(
+            tracker.observe_raw(EXEC, Some(10), None),
+            DropInfo::default()
+        );
+        assert_eq!(
+            tracker.observe_raw(OPEN, Some(3), None),
+            DropInfo::default()
+        );
+
+        assert_eq!(tracker.observe_raw(EXEC, Some(11), None).per_type, 0);
+        assert_eq!(tracker.observe_raw(EXEC, Some(15), None).per_type, 3);
+        // Types are tracked independently
+        assert_eq!(tracker.observe_raw(OPEN, Some(4), None).per_type, 0);
+
+        // Messages without sequence numbers are ignored
+        assert_eq!(tracker.observe_raw(OPEN, None, None), DropInfo::default());
+        assert_eq!(tracker.observe_raw(OPEN, Some(6), None).per_type, 1);
+
+        // Sequence reset: no drops reported, the next messages are compared to the new numbers
+        assert_eq!(tracker.observe_raw(EXEC, Some(2), None).per_type, 0);
+        assert_eq!(tracker.observe_raw(EXEC, Some(4), None).per_type, 1);
+
+        tracker.reset();
+        assert_eq!(
+            tracker.observe_raw(EXEC, Some(100), None),
+            DropInfo::default()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "macos_11_0_0")]
+    fn test_seq_tracker_global() {
+        let mut tracker = SeqTracker::new();
+
+        assert_eq!(
+            tracker.observe_raw(EXEC, Some(0), Some(u64::MAX - 2)),
+            DropInfo::default()
+        );
+        let info = tracker.observe_raw(OPEN, Some(0), Some(u64::MAX - 1));
+        assert_eq!(info, DropInfo::default());
+        assert!(info.has_drops() == false);
+
+        // Wraparound of the global sequence number
+        let info = tracker.observe_raw(EXEC, Some(2), Some(1));
+        assert_eq!(info, DropInfo { global: 2, per_type: 1 });
+        assert!(info.has_drops());
+
+        // Reset of the global sequence number
+        let info = tracker.observe_raw(OPEN, None, Some(0));
+        assert_eq!(info, DropInfo::default());
+    }
+}
@@ -0,0 +1,539 @@
+//! Implementation of [`serde::Serialize`] for the events and the types they contain
+//!
+//! Event wrappers get theirs from `impl_debug_eq_hash_with_functions!`, which serializes the same
+//! functions as their [`Debug`] implementation. The values returned by those functions are often
+//! foreign types (`OsStr`, `stat`, raw enums from [`endpoint_sec_sys`], ...) with no
+//! [`Serialize`] implementation or one that is not adapted to logs (`OsStr` is serialized as
+//! bytes by `serde`), so they go through [`SerializeField`] instead.
+
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::time::SystemTime;
+
+use endpoint_sec_sys::*;
+use serde::ser::{SerializeSeq, SerializeStruct, SerializeStructVariant};
+use serde::{Serialize, Serializer};
+
+#[cfg(feature = "macos_13_0_0")]
+use crate::AuthenticationData;
+#[cfg(feature = "macos_15_0_0")]
+use crate::GatekeeperUserOverrideFile;
+#[cfg(feature = "macos_14_0_0")]
+use crate::OdMemberIdValue;
+use crate::{AuditToken, EventCreateDestinationFile, EventRenameDestinationFile};
+
+/// Serialization of the values returned by the functions of the wrappers.
+pub(crate) trait SerializeField {
+    /// Serialize `self` with `serializer`.
+    fn serialize_field<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+
+    /// `true` if the field should be skipped, which is the case for `None` values: the field is
+    /// either optional or unavailable at the version of the message.
+    #[inline(always)]
+    fn is_absent(&self) -> bool {
+        false
+    }
+}
+
+/// Adapter to use a [`SerializeField`] where a [`Serialize`] is expected.
+struct Field<'r, T: ?Sized>(&'r T);
+
+impl<T: SerializeField + ?Sized> Serialize for Field<'_, T> {
+    #[inline(always)]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_field(serializer)
+    }
+}
+
+/// Serialize the field `key` of a struct, skipping it if absent.
+pub(crate) fn field<S, T>(s: &mut S, key: &'static str, value: &T) -> Result<(), S::Error>
+where
+    S: SerializeStruct,
+    T: SerializeField + ?Sized,
+{
+    if value.is_absent() {
+        s.skip_field(key)
+    } else {
+        s.serialize_field(key, &Field(value))
+    }
+}
+
+impl<T: SerializeField + ?Sized> SerializeField for &T {
+    #[inline(always)]
+    fn serialize_field<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize_field(serializer)
+    }
+
+    #[inline(always)]
+    fn is_absent(&self) -> bool {
+        (**self).is_absent()
+    }
+}
+
+impl<T: SerializeField> SerializeField for Option<T> {
+    fn serialize_field<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Some(v) => serializer.serialize_some(&Field(v)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    #[inline(always)]
+    fn is_absent(&self) -> bool {
+        self.is_none()
+    }
+}
+
+impl<T: SerializeField> SerializeField for [T] {
+    fn serialize_field<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for v in self {
+            seq.serialize_element(&Field(v))?;
+        }
+        seq.end()
+    }
+}
+
+impl<T: SerializeField> SerializeField for Vec<T> {
+    #[inline(always)]
+    fn serialize_field<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize_field(serializer)
+    }
+}
+
+/// Hashes are serialized as lowercase hex strings.
+impl<const N: usize> SerializeField for [u8; N] {
+    fn serialize_field<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut hex = String::with_capacity(2 * N);
+        for b in self {
+            // Writing to a `String` cannot fail
+            let _ = write!(hex, "{b:02x}");
+        }
+        serializer.serialize_str(&hex)
+    }
+}
+
+/// Paths and other strings from Endpoint Security are serialized as lossy UTF-8.
+impl SerializeField for OsStr {
+    #[inline(always)]
+    fn serialize_field<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string_lossy())
+    }
+}
+
+/// Implement [`SerializeField`] through the [`Serialize`] implementation of the types
+macro_rules! serialize_field_with_serde {
+    ($($ty: ty),* $(,)?) => {
+        $(
+            impl SerializeField for $ty {
+                #[inline(always)]
+                fn serialize_field<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    Serialize::serialize(self, serializer)
+                }
+            }
+        )*
+    };
+}
+
+serialize_field_with_serde!(bool, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, SystemTime);
+
+/// Implement [`SerializeField`] for raw enums from [`endpoint_sec_sys`], serialized as their
+/// integer value
+macro_rules! serialize_field_raw_enum {
+    ($($(#[$meta: meta])* $ty: ident),* $(,)?) => {
+        $(
+            $(#[$meta])*
+            impl SerializeField for $ty {
+                #[inline(always)]
+                fn serialize_field<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    Serialize::serialize(&self.0, serializer)
+                }
+            }
+        )*
+    };
+}
+
+serialize_field_raw_enum!(
+    es_action_type_t,
+    es_auth_result_t,
+    es_event_type_t,
+    es_get_task_type_t,
+    es_proc_check_type_t,
+    es_set_or_clear_t,
+    #[cfg(feature = "macos_11_0_0")]
+    es_proc_suspend_resume_type_t,
+    #[cfg(feature = "macos_13_0_0")]
+    es_address_type_t,
+    #[cfg(feature = "macos_13_0_0")]
+    es_authentication_type_t,
+    #[cfg(feature = "macos_13_0_0")]
+    es_auto_unlock_type_t,
+    #[cfg(feature = "macos_13_0_0")]
+    es_btm_item_type_t,
+    #[cfg(feature = "macos_13_0_0")]
+    es_openssh_login_result_type_t,
+    #[cfg(feature = "macos_13_0_0")]
+    es_touchid_mode_t,
+    #[cfg(feature = "macos_14_0_0")]
+    es_authorization_rule_class_t,
+    #[cfg(feature = "macos_14_0_0")]
+    es_od_account_type_t,
+    #[cfg(feature = "macos_14_0_0")]
+    es_od_member_type_t,
+    #[cfg(feature = "macos_14_0_0")]
+    es_od_record_type_t,
+    #[cfg(feature = "macos_14_0_0")]
+    es_profile_source_t,
+    #[cfg(feature = "macos_14_0_0")]
+    es_sudo_plugin_type_t,
+    #[cfg(feature = "macos_14_0_0")]
+    es_xpc_domain_type_t,
+);
+
+/// Serialize a NUL-terminated C string stored in a fixed-size array as lossy UTF-8.
+fn serialize_c_array<S: Serializer>(array: &[std::os::raw::c_char], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&crate::utils::c_array_to_os_str(array).to_string_lossy())
+}
+
+impl SerializeField for stat {
+    fn serialize_field<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("stat", 15)?;
+        s.serialize_field("st_dev", &self.st_dev)?;
+        s.serialize_field("st_ino", &self.st_ino)?;
+        s.serialize_field("st_mode", &self.st_mode)?;
+        s.serialize_field("st_nlink", &self.st_nlink)?;
+        s.serialize_field("st_uid", &self.st_uid)?;
+        s.serialize_field("st_gid", &self.st_gid)?;
+        s.serialize_field("st_rdev", &self.st_rdev)?;
+        s.serialize_field("st_size", &self.st_size)?;
+        s.serialize_field("st_blocks", &self.st_blocks)?;
+        s.serialize_field("st_blksize", &self.st_blksize)?;
+        s.serialize_field("st_flags", &self.st_flags)?;
+        s.serialize_field("st_atime", &self.st_atime)?;
+        s.serialize_field("st_mtime", &self.st_mtime)?;
+        s.serialize_field("st_ctime", &self.st_ctime)?;
+        s.serialize_field("st_birthtime", &self.st_birthtime)?;
+        s.end()
+    }
+}
+
+impl SerializeField for statfs {
+    fn serialize_field<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// `f_fstypename` as a serializable value
+        struct CArray<'r>(&'r [std::os::raw::c_char]);
+
+        impl Serialize for CArray<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_c_array(self.0, serializer)
+            }
+        }
+
+        let mut s = serializer.serialize_struct("statfs", 12)?;
+        s.serialize_field("f_bsize", &self.f_bsize)?;
+        s.serialize_field("f_iosize", &self.f_iosize)?;
+        s.serialize_field("f_blocks", &self.f_blocks)?;
+        s.serialize_field("f_bfree", &self.f_bfree)?;
+        s.serialize_field("f_bavail", &self.f_bavail)?;
+        s.serialize_field("f_files", &self.f_files)?;
+        s.serialize_field("f_ffree", &self.f_ffree)?;
+        s.serialize_field("f_owner", &self.f_owner)?;
+        s.serialize_field("f_flags", &self.f_flags)?;
+        s.serialize_field("f_fstypename", &CArray(&self.f_fstypename))?;
+        s.serialize_field("f_mntonname", &CArray(&self.f_mntonname))?;
+        s.serialize_field("f_mntfromname", &CArray(&self.f_mntfromname))?;
+        s.end()
+    }
+}
+
+impl SerializeField for attrlist {
+    fn serialize_field<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("attrlist", 6)?;
+        s.serialize_field("bitmapcount", &self.bitmapcount)?;
+        s.serialize_field("commonattr", &self.commonattr)?;
+        s.serialize_field("volattr", &self.volattr)?;
+        s.serialize_field("dirattr", &self.dirattr)?;
+        s.serialize_field("fileattr", &self.fileattr)?;
+        s.serialize_field("forkattr", &self.forkattr)?;
+        s.end()
+    }
+}
+
+/// ACLs are opaque, only their presence is serialized.
+#[cfg(feature = "macos_10_15_1")]
+impl SerializeField for acl_t {
+    #[inline(always)]
+    fn serialize_field<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_unit()
+    }
+}
+
+/// Serialized as a struct of the pid, pidversion, effective uid (`uid`) and effective gid (`gid`).
+impl Serialize for AuditToken {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("AuditToken", 4)?;
+        s.serialize_field("pid", &self.pid())?;
+        s.serialize_field("pidversion", &self.pidversion())?;
+        s.serialize_field("uid", &self.euid())?;
+        s.serialize_field("gid", &self.egid())?;
+        s.end()
+    }
+}
+
+impl SerializeField for AuditToken {
+    #[inline(always)]
+    fn serialize_field<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Serialize::serialize(self, serializer)
+    }
+}
+
+/// Implement [`SerializeField`] through the [`Serialize`] implementation of types with a lifetime
+macro_rules! serialize_field_with_serde_lt {
+    ($($(#[$meta: meta])* $ty: ident),* $(,)?) => {
+        $(
+            $(#[$meta])*
+            impl SerializeField for $ty<'_> {
+                #[inline(always)]
+                fn serialize_field<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    Serialize::serialize(self, serializer)
+                }
+            }
+        )*
+    };
+}
+
+serialize_field_with_serde_lt!(
+    EventCreateDestinationFile,
+    EventRenameDestinationFile,
+    #[cfg(feature = "macos_13_0_0")]
+    AuthenticationData,
+    #[cfg(feature = "macos_14_0_0")]
+    OdMemberIdValue,
+    #[cfg(feature = "macos_15_0_0")]
+    GatekeeperUserOverrideFile,
+);
+
+impl Serialize for EventCreateDestinationFile<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        const NAME: &str = "EventCreateDestinationFile";
+        match self {
+            Self::ExistingFile(file) => serializer.serialize_newtype_variant(NAME, 0, "ExistingFile", file),
+            Self::NewPath {
+                directory,
+                filename,
+                mode,
+            } => {
+                let mut s = serializer.serialize_struct_variant(NAME, 1, "NewPath", 3)?;
+                s.serialize_field("directory", directory)?;
+                s.serialize_field("filename", &Field(filename))?;
+                s.serialize_field("mode", mode)?;
+                s.end()
+            },
+        }
+    }
+}
+
+impl Serialize for EventRenameDestinationFile<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        const NAME: &str = "EventRenameDestinationFile";
+        match self {
+            Self::ExistingFile(file) => serializer.serialize_newtype_variant(NAME, 0, "ExistingFile", file),
+            Self::NewPath { directory, filename } => {
+                let mut s = serializer.serialize_struct_variant(NAME, 1, "NewPath", 2)?;
+                s.serialize_field("directory", directory)?;
+                s.serialize_field("filename", &Field(filename))?;
+                s.end()
+            },
+        }
+    }
+}
+
+#[cfg(feature = "macos_13_0_0")]
+impl Serialize for AuthenticationData<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        const NAME: &str = "AuthenticationData";
+        match self {
+            Self::Od(od) => serializer.serialize_newtype_variant(NAME, 0, "Od", od),
+            Self::TouchId(touchid) => serializer.serialize_newtype_variant(NAME, 1, "TouchId", touchid),
+            Self::Token(token) => serializer.serialize_newtype_variant(NAME, 2, "Token", token),
+            Self::AutoUnlock(auto_unlock) => serializer.serialize_newtype_variant(NAME, 3, "AutoUnlock", auto_unlock),
+        }
+    }
+}
+
+#[cfg(feature = "macos_14_0_0")]
+impl Serialize for OdMemberIdValue<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        const NAME: &str = "OdMemberIdValue";
+        match self {
+            Self::UserName(name) => serializer.serialize_newtype_variant(NAME, 0, "UserName", &Field(name)),
+            Self::UserUuid(uuid) => serializer.serialize_newtype_variant(NAME, 1, "UserUuid", &Field(uuid)),
+            Self::GroupUuid(uuid) => serializer.serialize_newtype_variant(NAME, 2, "GroupUuid", &Field(uuid)),
+        }
+    }
+}
+
+#[cfg(feature = "macos_15_0_0")]
+impl Serialize for GatekeeperUserOverrideFile<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        const NAME: &str = "GatekeeperUserOverrideFile";
+        match self {
+            Self::Path(path) => serializer.serialize_newtype_variant(NAME, 0, "Path", &Field(path)),
+            Self::File(file) => serializer.serialize_newtype_variant(NAME, 1, "File", file),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::ManuallyDrop;
+
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::utils::{file_with_path, string_token, zeroed_raw};
+    use crate::{Event, EventCreate, EventRename};
+
+    /// Serialize `v`, checking the JSON representation goes back to the same value
+    fn to_value<T: Serialize>(v: &T) -> Value {
+        let value = serde_json::to_value(v).unwrap();
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str::<Value>(&json).unwrap(), value);
+        value
+    }
+
+    /// Expected value for a file built with `file_with_path()`
+    fn file_value(path: &str) -> Value {
+        json!({
+            "path": path,
+            "path_truncated": false,
+            "stat": {
+                "st_dev": 0, "st_ino": 0, "st_mode": 0, "st_nlink": 0, "st_uid": 0, "st_gid": 0,
+                "st_rdev": 0, "st_size": 0, "st_blocks": 0, "st_blksize": 0, "st_flags": 0,
+                "st_atime": 0, "st_mtime": 0, "st_ctime": 0, "st_birthtime": 0,
+            },
+        })
+    }
+
+    #[test]
+    fn test_unknown_event() {
+        let event = Event::Unknown {
+            event_type: es_event_type_t(1000),
+            action_type: es_action_type_t::ES_ACTION_TYPE_AUTH,
+        };
+        assert_eq!(
+            to_value(&event),
+            json!({ "Unknown": { "event_type": 1000, "action_type": 0 } })
+        );
+    }
+
+    #[test]
+    fn test_rename() {
+        let mut source = file_with_path("/tmp/source");
+        let mut target = file_with_path("/tmp/target");
+
+        let mut raw: es_event_rename_t = zeroed_raw();
+        raw.source = ShouldNotBeNull::new(&mut source);
+        raw.destination_type = es_destination_type_t::ES_DESTINATION_TYPE_EXISTING_FILE;
+        raw.destination.existing_file = ShouldNotBeNull::new(&mut target);
+
+        let event = Event::NotifyRename(EventRename { raw: &raw });
+        assert_eq!(
+            to_value(&event),
+            json!({
+                "NotifyRename": {
+                    "source": file_value("/tmp/source"),
+                    "destination": { "ExistingFile": file_value("/tmp/target") },
+                },
+            })
+        );
+
+        raw.destination_type = es_destination_type_t::ES_DESTINATION_TYPE_NEW_PATH;
+        raw.destination.new_path = ManuallyDrop::new(es_event_rename_t_anon_0_anon_0 {
+            dir: ShouldNotBeNull::new(&mut target),
+            filename: string_token("name"),
+        });
+        assert_eq!(
+            to_value(&EventRename { raw: &raw }),
+            json!({
+                "source": file_value("/tmp/source"),
+                "destination": { "NewPath": { "directory": file_value("/tmp/target"), "filename": "name" } },
+            })
+        );
+
+        // Unknown destination types are skipped
+        raw.destination_type = es_destination_type_t(2);
+        assert_eq!(
+            to_value(&EventRename { raw: &raw }),
+            json!({ "source": file_value("/tmp/source") })
+        );
+    }
+
+    #[test]
+    fn test_create_skips_unavailable_fields() {
+        let mut target = file_with_path("/tmp/target");
+
+        let mut raw: es_event_create_t = zeroed_raw();
+        raw.destination_type = es_destination_type_t::ES_DESTINATION_TYPE_EXISTING_FILE;
+        raw.destination.existing_file = ShouldNotBeNull::new(&mut target);
+
+        // The ACL is only available from version 2
+        assert_eq!(
+            to_value(&Event::AuthCreate(EventCreate { raw: &raw, version: 1 })),
+            json!({
+                "AuthCreate": {
+                    "version": 1,
+                    "destination": { "ExistingFile": file_value("/tmp/target") },
+                },
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "macos_14_0_0")]
+    fn test_xpc_connect() {
+        use crate::EventXpcConnect;
+
+        let raw = es_event_xpc_connect_t {
+            service_name: string_token("com.example.service"),
+            service_domain_type: es_xpc_domain_type_t::ES_XPC_DOMAIN_TYPE_SYSTEM,
+        };
+        assert_eq!(
+            to_value(&Event::NotifyXpcConnect(EventXpcConnect { raw: &raw })),
+            json!({
+                "NotifyXpcConnect": { "service_name": "com.example.service", "service_domain_type": 1 },
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "macos_15_0_0")]
+    fn test_gatekeeper_user_override() {
+        use crate::EventGatekeeperUserOverride;
+
+        let mut sha256: es_sha256_t = [0xab; 32];
+        let mut signing_info = es_signed_file_info_t {
+            cdhash: [0x12; 20],
+            signing_id: string_token("com.example.app"),
+            team_id: string_token("TEAMID1234"),
+        };
+
+        let mut raw: es_event_gatekeeper_user_override_t = zeroed_raw();
+        raw.file_type = es_gatekeeper_user_override_file_type_t::ES_GATEKEEPER_USER_OVERRIDE_FILE_TYPE_PATH;
+        raw.file.file_path = ManuallyDrop::new(string_token("/Applications/App.app"));
+        raw.sha256 = &mut sha256;
+        raw.signing_info = &mut signing_info;
+
+        assert_eq!(
+            to_value(&EventGatekeeperUserOverride { raw: &raw }),
+            json!({
+                "file": { "Path": "/Applications/App.app" },
+                "sha256": "ab".repeat(32),
+                "signing_info": {
+                    "cdhash": "12".repeat(20),
+                    "signing_id": "com.example.app",
+                    "team_id": "TEAMID1234",
+                },
+            })
+        );
+    }
+}
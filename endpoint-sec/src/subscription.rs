@@ -0,0 +1,239 @@
+//! Declarative management of the events a client is subscribed to: [`Subscription`]
+//!
+//! Reconfiguring a client usually means going from one set of events to another. Instead of
+//! tracking which events to subscribe to and unsubscribe from by hand, apps can describe the
+//! desired set with a [`Subscription`] and give it to [`Client::apply_subscription()`], which only
+//! changes what is needed.
+
+use std::collections::btree_set::{self, BTreeSet};
+
+use endpoint_sec_sys::es_event_type_t;
+
+use crate::{Client, SubscribeError};
+
+/// Set of event types, sorted by raw value.
+///
+/// ```
+/// use endpoint_sec::sys::es_event_type_t;
+/// use endpoint_sec::Subscription;
+///
+/// let current: Subscription = [
+///     es_event_type_t::ES_EVENT_TYPE_AUTH_EXEC,
+///     es_event_type_t::ES_EVENT_TYPE_NOTIFY_FORK,
+/// ]
+/// .into_iter()
+/// .collect();
+/// let desired: Subscription = [
+///     es_event_type_t::ES_EVENT_TYPE_AUTH_EXEC,
+///     es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXIT,
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let (to_subscribe, to_unsubscribe) = current.diff(&desired);
+/// assert_eq!(to_subscribe, [es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXIT]);
+/// assert_eq!(to_unsubscribe, [es_event_type_t::ES_EVENT_TYPE_NOTIFY_FORK]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Subscription {
+    /// Event types in the set
+    events: BTreeSet<es_event_type_t>,
+}
+
+impl Subscription {
+    /// Empty set.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `event` to the set, returns `false` if it was already present.
+    #[inline]
+    pub fn insert(&mut self, event: es_event_type_t) -> bool {
+        self.events.insert(event)
+    }
+
+    /// Remove `event` from the set, returns `false` if it was not present.
+    #[inline]
+    pub fn remove(&mut self, event: es_event_type_t) -> bool {
+        self.events.remove(&event)
+    }
+
+    /// `true` if `event` is in the set.
+    #[inline]
+    pub fn contains(&self, event: es_event_type_t) -> bool {
+        self.events.contains(&event)
+    }
+
+    /// Number of event types in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// `true` if the set contains no event type.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Iterate over the event types of the set, sorted by raw value.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = es_event_type_t> + '_ {
+        self.events.iter().copied()
+    }
+
+    /// Iterate over the `AUTH` event types of the set.
+    ///
+    /// Event types unknown to this crate are neither `AUTH` nor `NOTIFY`.
+    #[inline]
+    pub fn auth_events(&self) -> impl Iterator<Item = es_event_type_t> + '_ {
+        self.iter().filter(|ev| has_prefix(*ev, "auth_"))
+    }
+
+    /// Iterate over the `NOTIFY` event types of the set.
+    ///
+    /// Event types unknown to this crate are neither `AUTH` nor `NOTIFY`.
+    #[inline]
+    pub fn notify_events(&self) -> impl Iterator<Item = es_event_type_t> + '_ {
+        self.iter().filter(|ev| has_prefix(*ev, "notify_"))
+    }
+
+    /// Changes needed to go from `self` to `desired`, as `(to_subscribe, to_unsubscribe)`.
+    ///
+    /// `to_subscribe` contains the events of `desired` missing from `self` and `to_unsubscribe` the
+    /// events of `self` missing from `desired`, both sorted by raw value. They can be given as is
+    /// to [`Client::subscribe()`] and [`Client::unsubscribe()`].
+    pub fn diff(&self, desired: &Subscription) -> (Vec<es_event_type_t>, Vec<es_event_type_t>) {
+        let to_subscribe = desired.events.difference(&self.events).copied().collect();
+        let to_unsubscribe = self.events.difference(&desired.events).copied().collect();
+        (to_subscribe, to_unsubscribe)
+    }
+}
+
+/// `true` if the name of `event` starts with `prefix`, `false` for unknown events.
+fn has_prefix(event: es_event_type_t, prefix: &str) -> bool {
+    event.name().is_some_and(|name| name.starts_with(prefix))
+}
+
+impl FromIterator<es_event_type_t> for Subscription {
+    fn from_iter<I: IntoIterator<Item = es_event_type_t>>(iter: I) -> Self {
+        Self {
+            events: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<es_event_type_t> for Subscription {
+    fn extend<I: IntoIterator<Item = es_event_type_t>>(&mut self, iter: I) {
+        self.events.extend(iter)
+    }
+}
+
+impl From<&[es_event_type_t]> for Subscription {
+    #[inline]
+    fn from(events: &[es_event_type_t]) -> Self {
+        events.iter().copied().collect()
+    }
+}
+
+impl IntoIterator for Subscription {
+    type IntoIter = btree_set::IntoIter<es_event_type_t>;
+    type Item = es_event_type_t;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.into_iter()
+    }
+}
+
+/// Subscriptions through a [`Subscription`]
+impl Client<'_> {
+    /// Make the subscriptions of the client match `desired`, only subscribing to and unsubscribing
+    /// from the events that differ.
+    ///
+    /// The current subscriptions are queried with [`Client::subscriptions()`]. New events are
+    /// subscribed to before the old ones are unsubscribed from, so that events present in both
+    /// sets are never interrupted. Applying the same `desired` set twice does nothing the second
+    /// time.
+    ///
+    /// Events of `desired` that are not available on the
+    /// [runtime version][crate::version::set_runtime_version] are rejected as in
+    /// [`Client::subscribe()`], before anything is changed.
+    pub fn apply_subscription(&mut self, desired: &Subscription) -> Result<(), SubscribeError> {
+        let current: Subscription = self.subscriptions()?.into_iter().collect();
+        let (to_subscribe, to_unsubscribe) = current.diff(desired);
+
+        if to_subscribe.is_empty() == false {
+            self.subscribe(&to_subscribe)?;
+        }
+        if to_unsubscribe.is_empty() == false {
+            self.unsubscribe(&to_unsubscribe)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUTH_EXEC: es_event_type_t = es_event_type_t::ES_EVENT_TYPE_AUTH_EXEC;
+    const AUTH_OPEN: es_event_type_t = es_event_type_t::ES_EVENT_TYPE_AUTH_OPEN;
+    const NOTIFY_EXEC: es_event_type_t = es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXEC;
+    const NOTIFY_FORK: es_event_type_t = es_event_type_t::ES_EVENT_TYPE_NOTIFY_FORK;
+    const NOTIFY_EXIT: es_event_type_t = es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXIT;
+
+    #[test]
+    fn test_subscription_set() {
+        let mut sub = Subscription::new();
+        assert!(sub.is_empty());
+        assert!(sub.insert(NOTIFY_EXIT));
+        assert!(sub.insert(AUTH_EXEC));
+        assert!(sub.insert(NOTIFY_FORK));
+        assert!(sub.insert(es_event_type_t(u32::MAX)));
+        assert!(sub.insert(AUTH_EXEC) == false);
+        assert_eq!(sub.len(), 4);
+        assert!(sub.contains(NOTIFY_FORK));
+
+        assert!(sub.remove(NOTIFY_FORK));
+        assert!(sub.remove(NOTIFY_FORK) == false);
+        assert!(sub.contains(NOTIFY_FORK) == false);
+
+        assert_eq!(sub.iter().collect::<Vec<_>>(), [
+            AUTH_EXEC,
+            NOTIFY_EXIT,
+            es_event_type_t(u32::MAX)
+        ]);
+        assert_eq!(sub.auth_events().collect::<Vec<_>>(), [AUTH_EXEC]);
+        assert_eq!(sub.notify_events().collect::<Vec<_>>(), [NOTIFY_EXIT]);
+    }
+
+    #[test]
+    fn test_subscription_diff() {
+        let current = Subscription::from(&[AUTH_EXEC, AUTH_OPEN, NOTIFY_FORK][..]);
+        let desired = Subscription::from(&[NOTIFY_EXIT, AUTH_EXEC, NOTIFY_EXEC, NOTIFY_FORK][..]);
+
+        let (to_subscribe, to_unsubscribe) = current.diff(&desired);
+        assert_eq!(to_subscribe, [NOTIFY_EXEC, NOTIFY_EXIT]);
+        assert_eq!(to_unsubscribe, [AUTH_OPEN]);
+
+        let (to_subscribe, to_unsubscribe) = desired.diff(&current);
+        assert_eq!(to_subscribe, [AUTH_OPEN]);
+        assert_eq!(to_unsubscribe, [NOTIFY_EXEC, NOTIFY_EXIT]);
+
+        // Identical sets need no change
+        let (to_subscribe, to_unsubscribe) = current.diff(&current.clone());
+        assert!(to_subscribe.is_empty());
+        assert!(to_unsubscribe.is_empty());
+
+        // From and to nothing
+        let (to_subscribe, to_unsubscribe) = Subscription::new().diff(&current);
+        assert_eq!(to_subscribe, [AUTH_EXEC, AUTH_OPEN, NOTIFY_FORK]);
+        assert!(to_unsubscribe.is_empty());
+        let (to_subscribe, to_unsubscribe) = current.diff(&Subscription::new());
+        assert!(to_subscribe.is_empty());
+        assert_eq!(to_unsubscribe, [AUTH_EXEC, AUTH_OPEN, NOTIFY_FORK]);
+    }
+}
@@ -1,7 +1,9 @@
 //! Utilities related to the handling of time, ect.
 
 use std::borrow::Cow;
-use std::ffi::{CStr, CString};
+use std::ffi::{CStr, CString, OsStr};
+use std::os::raw::c_char;
+use std::os::unix::ffi::OsStrExt;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
@@ -28,6 +30,15 @@ pub(crate) fn convert_mach_time_to_instant(mach_time: u64) -> Result<Instant, Ti
 
 /// Convert a Mach absolute time to a [Duration].
 pub(crate) fn convert_mach_time_to_duration(mach_time: u64) -> Duration {
+    let value = timebase_info();
+    let nanos = (mach_time * u64::from(value.numer)) / u64::from(value.denom);
+
+    Duration::from_nanos(nanos)
+}
+
+/// Timebase of Mach absolute times, only queried once with [`mach_timebase_info()`] and then
+/// cached.
+fn timebase_info() -> mach_timebase_info {
     /// Storage for [struct@mach_timebase_info]
     ///
     /// NOTE: We pack the [struct@mach_timebase_info] structure into a [u64] to take advantage of atomics.
@@ -36,6 +47,9 @@ pub(crate) fn convert_mach_time_to_duration(mach_time: u64) -> Duration {
     let mut value = mti_from_u64(TIME_BASE_RAW_INFO.load(Ordering::Relaxed));
 
     // Similar to https://github.com/rust-lang/rust/blob/master/library/std/src/sys/unix/time.rs#L226-L253
+    //
+    // Concurrent first calls may all query the timebase, they store the same value so no
+    // synchronization is needed beyond the atomic itself.
     if value.denom == 0 || value.numer == 0 {
         // Safety: value needs to be a pointer initialized to a mach_timebase_info.
         unsafe { mach_timebase_info(&mut value) };
@@ -43,9 +57,7 @@ pub(crate) fn convert_mach_time_to_duration(mach_time: u64) -> Duration {
         TIME_BASE_RAW_INFO.store(u64_from_mti(value), Ordering::Relaxed)
     }
 
-    let nanos = (mach_time * u64::from(value.numer)) / u64::from(value.denom);
-
-    Duration::from_nanos(nanos)
+    value
 }
 
 /// [`u64`] to [`mach_timebase_info`][struct@mach_timebase_info]
@@ -87,3 +99,268 @@ pub(crate) fn convert_byte_slice_to_cow_cstr(bytes: &[u8]) -> Cow<'_, CStr> {
         None => Cow::Owned(unsafe { CString::from_vec_unchecked(bytes.into()) }),
     }
 }
+
+/// View a NUL-terminated C string stored in a fixed-size array, like the names in a `statfs`, as an
+/// [`OsStr`].
+///
+/// The string stops at the first NUL, or at the end of the array if there is none.
+pub(crate) fn c_array_to_os_str(array: &[c_char]) -> &OsStr {
+    // Safety: `c_char` and `u8` have the same layout
+    let bytes = unsafe { std::slice::from_raw_parts(array.as_ptr().cast::<u8>(), array.len()) };
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    OsStr::from_bytes(&bytes[..len])
+}
+
+/// System-wide unique ID of the current thread, the same kind of ID as
+/// [`Thread::thread_id()`][crate::Thread::thread_id].
+#[inline]
+pub(crate) fn current_thread_id() -> u64 {
+    let mut id = 0;
+    // Safety: a null thread designates the current one, `id` is valid for writes
+    unsafe { libc::pthread_threadid_np(0, &mut id) };
+    id
+}
+
+/// List the PIDs of all the running descendants of `root`, parents before their children.
+#[cfg(feature = "audit_token_from_pid")]
+pub(crate) fn descendant_pids(root: libc::pid_t) -> Vec<libc::pid_t> {
+    // Safety: passing a null buffer asks for the number of PIDs
+    let count = unsafe { libc::proc_listallpids(std::ptr::null_mut(), 0) };
+    if count <= 0 {
+        return Vec::new();
+    }
+
+    // Leave some room for processes created between the two calls
+    let mut pids: Vec<libc::pid_t> = vec![0; count as usize + 32];
+    // Safety: the buffer is valid for writes of the given size in bytes
+    let count = unsafe {
+        libc::proc_listallpids(
+            pids.as_mut_ptr().cast(),
+            (pids.len() * std::mem::size_of::<libc::pid_t>()) as libc::c_int,
+        )
+    };
+    pids.truncate(count.max(0) as usize);
+
+    let parents: Vec<(libc::pid_t, libc::pid_t)> = pids
+        .into_iter()
+        .filter_map(|pid| {
+            // Safety: all zeroes is a valid value for this plain C struct
+            let mut info: libc::proc_bsdshortinfo = unsafe { std::mem::zeroed() };
+            let size = std::mem::size_of::<libc::proc_bsdshortinfo>() as libc::c_int;
+            // Safety: `info` is valid for writes of `size` bytes, the flavor matches its type
+            let res = unsafe {
+                libc::proc_pidinfo(
+                    pid,
+                    libc::PROC_PIDT_SHORTBSDINFO,
+                    0,
+                    (&mut info as *mut libc::proc_bsdshortinfo).cast(),
+                    size,
+                )
+            };
+            (res == size).then_some((pid, info.pbsi_ppid as libc::pid_t))
+        })
+        .collect();
+
+    let mut descendants = Vec::new();
+    let mut to_visit = vec![root];
+    while let Some(parent) = to_visit.pop() {
+        for &(pid, ppid) in &parents {
+            // `pid != parent` avoids looping on `launchd`, which is its own parent
+            if ppid == parent && pid != parent && descendants.contains(&pid) == false {
+                descendants.push(pid);
+                to_visit.push(pid);
+            }
+        }
+    }
+
+    descendants
+}
+
+/// Raw Endpoint Security types that tests can build with [`zeroed_raw()`].
+///
+/// # Safety
+///
+/// All zeroes must be a valid value for the type.
+#[cfg(test)]
+pub(crate) unsafe trait Zeroable: Sized {}
+
+/// Implement [`Zeroable`] for the given types
+#[cfg(test)]
+macro_rules! impl_zeroable {
+    ($($(#[$cfg:meta])* $ty:ty),* $(,)?) => {
+        $(
+            $(#[$cfg])*
+            // Safety: the raw types of Endpoint Security are C structs and unions only made of
+            // integers, booleans, raw enums, string tokens, raw pointers (including
+            // `ShouldNotBeNull`) and arrays or unions of them: all zeroes is a valid value for
+            // each of them. Pointers are null until the test fills them.
+            unsafe impl Zeroable for $ty {}
+        )*
+    };
+}
+
+#[cfg(test)]
+impl_zeroable!(
+    endpoint_sec_sys::es_message_t,
+    endpoint_sec_sys::es_process_t,
+    endpoint_sec_sys::es_file_t,
+    endpoint_sec_sys::es_events_t,
+    endpoint_sec_sys::statfs,
+    endpoint_sec_sys::es_event_close_t,
+    endpoint_sec_sys::es_event_create_t,
+    endpoint_sec_sys::es_event_exec_t,
+    endpoint_sec_sys::es_event_exit_t,
+    endpoint_sec_sys::es_event_get_task_t,
+    endpoint_sec_sys::es_event_iokit_open_t,
+    endpoint_sec_sys::es_event_mmap_t,
+    endpoint_sec_sys::es_event_mount_t,
+    endpoint_sec_sys::es_event_open_t,
+    endpoint_sec_sys::es_event_rename_t,
+    endpoint_sec_sys::es_event_setflags_t,
+    endpoint_sec_sys::es_event_signal_t,
+    endpoint_sec_sys::es_event_unlink_t,
+    endpoint_sec_sys::es_event_unmount_t,
+    #[cfg(feature = "macos_10_15_1")]
+    endpoint_sec_sys::es_event_access_t,
+    #[cfg(feature = "macos_10_15_1")]
+    endpoint_sec_sys::es_event_fcntl_t,
+    #[cfg(feature = "macos_10_15_1")]
+    endpoint_sec_sys::es_event_remount_t,
+    #[cfg(feature = "macos_10_15_1")]
+    endpoint_sec_sys::es_event_setacl_t,
+    #[cfg(feature = "macos_10_15_1")]
+    endpoint_sec_sys::es_event_uipc_connect_t,
+    #[cfg(feature = "macos_10_15_4")]
+    endpoint_sec_sys::es_event_proc_check_t,
+    #[cfg(feature = "macos_11_0_0")]
+    endpoint_sec_sys::es_event_remote_thread_create_t,
+    #[cfg(feature = "macos_12_0_0")]
+    endpoint_sec_sys::es_event_copyfile_t,
+    #[cfg(feature = "macos_13_0_0")]
+    endpoint_sec_sys::es_btm_launch_item_t,
+    #[cfg(feature = "macos_13_0_0")]
+    endpoint_sec_sys::es_event_authentication_t,
+    #[cfg(feature = "macos_13_0_0")]
+    endpoint_sec_sys::es_event_authentication_auto_unlock_t,
+    #[cfg(feature = "macos_13_0_0")]
+    endpoint_sec_sys::es_event_authentication_od_t,
+    #[cfg(feature = "macos_13_0_0")]
+    endpoint_sec_sys::es_event_authentication_token_t,
+    #[cfg(feature = "macos_13_0_0")]
+    endpoint_sec_sys::es_event_authentication_touchid_t,
+    #[cfg(feature = "macos_13_0_0")]
+    endpoint_sec_sys::es_event_btm_launch_item_add_t,
+    #[cfg(feature = "macos_13_0_0")]
+    endpoint_sec_sys::es_event_login_login_t,
+    #[cfg(feature = "macos_13_0_0")]
+    endpoint_sec_sys::es_event_login_logout_t,
+    #[cfg(feature = "macos_13_0_0")]
+    endpoint_sec_sys::es_event_openssh_login_t,
+    #[cfg(feature = "macos_13_0_0")]
+    endpoint_sec_sys::es_event_openssh_logout_t,
+    #[cfg(feature = "macos_13_0_0")]
+    endpoint_sec_sys::es_event_screensharing_attach_t,
+    #[cfg(feature = "macos_13_0_0")]
+    endpoint_sec_sys::es_event_screensharing_detach_t,
+    #[cfg(feature = "macos_13_0_0")]
+    endpoint_sec_sys::es_event_xp_malware_remediated_t,
+    #[cfg(feature = "macos_14_0_0")]
+    endpoint_sec_sys::es_event_authorization_judgement_t,
+    #[cfg(feature = "macos_14_0_0")]
+    endpoint_sec_sys::es_event_authorization_petition_t,
+    #[cfg(feature = "macos_14_0_0")]
+    endpoint_sec_sys::es_event_profile_add_t,
+    #[cfg(feature = "macos_14_0_0")]
+    endpoint_sec_sys::es_event_su_t,
+    #[cfg(feature = "macos_15_0_0")]
+    endpoint_sec_sys::es_event_gatekeeper_user_override_t,
+);
+
+/// Zeroed raw Endpoint Security value, to build raw events in tests by filling only the fields
+/// (and pointers) they need
+#[cfg(test)]
+pub(crate) fn zeroed_raw<T: Zeroable>() -> T {
+    // Safety: all zeroes is a valid value for `T`, as guaranteed by `Zeroable`
+    unsafe { std::mem::zeroed() }
+}
+
+/// String token pointing to `s`, to build raw events in tests
+#[cfg(test)]
+pub(crate) fn string_token(s: &'static str) -> endpoint_sec_sys::es_string_token_t {
+    endpoint_sec_sys::es_string_token_t {
+        length: s.len(),
+        data: s.as_ptr().cast(),
+    }
+}
+
+/// Zeroed file with only its path set, to build raw events in tests
+#[cfg(test)]
+pub(crate) fn file_with_path(path: &'static str) -> endpoint_sec_sys::es_file_t {
+    let mut file: endpoint_sec_sys::es_file_t = zeroed_raw();
+    file.path = string_token(path);
+    file
+}
+
+/// Zeroed `statfs` with only its names set, to build raw events in tests.
+///
+/// The bytes after the NUL terminator of each name are filled with `X` to check they are ignored.
+#[cfg(test)]
+pub(crate) fn statfs_with_names(from: &str, on: &str, fs_type: &str) -> endpoint_sec_sys::statfs {
+    /// Write `s` and its NUL terminator at the start of `array`, filling the rest with `X`
+    fn fill(array: &mut [c_char], s: &str) {
+        array.fill(b'X' as c_char);
+        for (dst, &src) in array.iter_mut().zip(s.as_bytes()) {
+            *dst = src as c_char;
+        }
+        array[s.len()] = 0;
+    }
+
+    let mut statfs: endpoint_sec_sys::statfs = zeroed_raw();
+    fill(&mut statfs.f_mntfromname, from);
+    fill(&mut statfs.f_mntonname, on);
+    fill(&mut statfs.f_fstypename, fs_type);
+    statfs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timebase_info_cached() {
+        let mut fresh = mach_timebase_info { numer: 0, denom: 0 };
+        // Safety: `fresh` is a valid pointer to a mach_timebase_info
+        unsafe { mach_timebase_info(&mut fresh) };
+        assert_ne!(fresh.denom, 0);
+
+        let (first, second) = (timebase_info(), timebase_info());
+        assert_eq!((first.numer, first.denom), (fresh.numer, fresh.denom));
+        assert_eq!((second.numer, second.denom), (fresh.numer, fresh.denom));
+
+        let info = mti_from_u64(u64_from_mti(fresh));
+        assert_eq!((info.numer, info.denom), (fresh.numer, fresh.denom));
+
+        let ticks = u64::from(fresh.denom) * 1_000;
+        assert_eq!(
+            convert_mach_time_to_duration(ticks),
+            Duration::from_nanos(u64::from(fresh.numer) * 1_000)
+        );
+    }
+
+    #[test]
+    fn test_c_array_to_os_str() {
+        let array = |s: &[u8]| s.iter().map(|&b| b as c_char).collect::<Vec<_>>();
+
+        assert_eq!(
+            c_array_to_os_str(&array(b"apfs\0\0\0\0")),
+            OsStr::new("apfs")
+        );
+        assert_eq!(
+            c_array_to_os_str(&array(b"apfs\0hfs\0")),
+            OsStr::new("apfs")
+        );
+        assert_eq!(c_array_to_os_str(&array(b"apfs")), OsStr::new("apfs"));
+        assert_eq!(c_array_to_os_str(&array(b"\0apfs")), OsStr::new(""));
+        assert_eq!(c_array_to_os_str(&[]), OsStr::new(""));
+    }
+}
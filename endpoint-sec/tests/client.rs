@@ -32,3 +32,138 @@ fn reproduce_double_drop() {
     })
     .unwrap();
 }
+
+#[test]
+fn test_client_builder_rolls_back_on_subscribe_failure() {
+    use endpoint_sec::sys::es_event_type_t;
+    use endpoint_sec::{ClientBuildError, ClientBuilder};
+
+    let res = ClientBuilder::new(|_client, _msg| {})
+        .subscribe(&[es_event_type_t(u32::MAX - 1)])
+        .build();
+
+    assert!(matches!(res, Err(ClientBuildError::Subscribe(_))));
+}
+
+#[test]
+fn test_message_size() {
+    use std::time::Duration;
+
+    use endpoint_sec::sys::es_event_type_t;
+
+    let (mut client, pump) = Client::new_with_pump().unwrap();
+    client.subscribe(&[es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXEC]).unwrap();
+
+    std::process::Command::new("/usr/bin/true").status().unwrap();
+
+    let msg = pump.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_ne!(msg.size(), 0);
+}
+
+#[test]
+fn test_handler_thread_id() {
+    use std::sync::{mpsc, Mutex};
+    use std::time::Duration;
+
+    use endpoint_sec::sys::es_event_type_t;
+
+    let (sender, receiver) = mpsc::channel();
+    let sender = Mutex::new(sender);
+    let mut client = Client::new(move |client, _msg| {
+        // Stable for the whole call
+        let first = client.handler_thread_id();
+        let second = client.handler_thread_id();
+        let _ = sender.lock().unwrap().send((first, second));
+    })
+    .unwrap();
+    assert_eq!(client.handler_thread_id(), None);
+
+    client.subscribe(&[es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXEC]).unwrap();
+    for _ in 0..3 {
+        std::process::Command::new("/usr/bin/true").status().unwrap();
+    }
+
+    for _ in 0..3 {
+        let (first, second) = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(first.is_some());
+        assert_eq!(first, second);
+    }
+    assert!(client.handler_thread_id().is_some());
+}
+
+#[test]
+fn test_panic_handler() {
+    use std::sync::{mpsc, Mutex};
+    use std::time::Duration;
+
+    use endpoint_sec::sys::es_event_type_t;
+
+    let (sender, receiver) = mpsc::channel();
+    let sender = Mutex::new(sender);
+    let mut client = Client::new_with_panic_handler(
+        |_client, _msg| panic!("handler panicked"),
+        move |payload| {
+            let msg = payload.downcast_ref::<&str>().map(|s| s.to_string());
+            let _ = sender.lock().unwrap().send(msg);
+        },
+    )
+    .unwrap();
+
+    client.subscribe(&[es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXEC]).unwrap();
+    std::process::Command::new("/usr/bin/true").status().unwrap();
+
+    let msg = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(msg.as_deref(), Some("handler panicked"));
+}
+
+#[test]
+fn test_deferred_auth() {
+    use std::sync::{mpsc, Mutex};
+    use std::time::Duration;
+
+    use endpoint_sec::sys::{es_action_type_t, es_auth_result_t, es_event_type_t};
+    use endpoint_sec::{Event, NotAuthMessage};
+
+    let (sender, receiver) = mpsc::channel();
+    let sender = Mutex::new(sender);
+    let mut client = Client::new(move |_client, msg| match msg.into_deferred() {
+        // The message is moved out of the handler, which returns without responding
+        Ok(deferred) => {
+            let _ = sender.lock().unwrap().send(deferred);
+        },
+        Err(NotAuthMessage(msg)) => assert_eq!(msg.action_type(), es_action_type_t::ES_ACTION_TYPE_NOTIFY),
+    })
+    .unwrap();
+    client
+        .subscribe(&[
+            es_event_type_t::ES_EVENT_TYPE_AUTH_EXEC,
+            es_event_type_t::ES_EVENT_TYPE_NOTIFY_EXIT,
+        ])
+        .unwrap();
+
+    // Blocked until its exec is allowed
+    let child = std::thread::spawn(|| std::process::Command::new("/usr/bin/true").status().unwrap());
+
+    // Other processes exec too, allow them all until ours is seen
+    loop {
+        let deferred = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        // The message is still alive and readable after the handler returned
+        let is_ours = match deferred.message().event() {
+            Some(Event::AuthExec(exec)) => exec.target().executable().path() == "/usr/bin/true",
+            _ => false,
+        };
+        assert!(deferred.deadline().is_ok());
+        deferred
+            .respond_auth(&mut client, es_auth_result_t::ES_AUTH_RESULT_ALLOW, false)
+            .unwrap();
+        if is_ours {
+            break;
+        }
+    }
+    assert!(child.join().unwrap().success());
+
+    client.unsubscribe_all().unwrap();
+    while let Ok(deferred) = receiver.recv_timeout(Duration::from_millis(100)) {
+        let _ = deferred.respond_auth(&mut client, es_auth_result_t::ES_AUTH_RESULT_ALLOW, false);
+    }
+}